@@ -1,13 +1,14 @@
 use std::fmt::Display;
 use std::num::NonZeroUsize;
-use std::ops::RangeInclusive;
 
 use mathstuff_types::print::{PrintWithVar, PrintableCoeff, DisplayWithVar, print_as_factor};
 use num::integer::Roots;
 use num::traits::Inv;
-use num::{BigInt, BigRational, One, Zero, Integer};
+use num::{BigInt, BigRational, One, Zero, Integer, Signed};
 
-use mathstuff_types::traits::{CommutativeRing, Field, FromUsize};
+use mathstuff_types::traits::{
+    Characteristic, CommutativeRing, Field, FromUsize, GcdDomain, IntegralDomain,
+};
 use mathstuff_types::Polynomial;
 
 /*
@@ -34,20 +35,19 @@ pub fn binomial_product<Ring: CommutativeRing>(nums: Vec<Ring>) -> Polynomial<Ri
 /// divisors.sort_unstable();
 /// assert_eq!(vec![1, 2, 3, 4, 6, 9, 12, 18, 36], divisors);
 /// ```
-pub fn integer_divisors<N: Roots + Clone>(x: N) -> Vec<N>
-where
-    RangeInclusive<N>: IntoIterator<Item = N>,
-{
+pub fn integer_divisors<N: Roots + Clone>(x: N) -> Vec<N> {
     let n = x.sqrt();
-    let mut res = vec![ N::one(), x.clone() ];
-    for div in (N::one() + N::one()) ..= n {
+    let mut res = vec![];
+    let mut div = N::one();
+    while div <= n {
         if x.is_multiple_of(&div) {
             let other = x.div_floor(&div);
             if other != div {
                 res.push(other);
             }
-            res.push(div);
+            res.push(div.clone());
         }
+        div = div + N::one();
     }
     res
 }
@@ -87,10 +87,63 @@ pub fn lagrange_interpolation(points: Vec<(BigRational, BigRational)>) -> Polyno
         .sum()
 }
 
+/// Finds the rational roots of a polynomial using the rational root theorem.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::factorization::rational_roots;
+/// # use mathstuff_types::Polynomial;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// // (2x - 1)(x + 3)(x^2 + 1)
+/// let p = Polynomial::new(vec![n(-3), n(5), n(-1), n(5), n(2)]);
+/// let mut roots = rational_roots(&p);
+/// roots.sort();
+/// assert_eq!(roots, vec![n(-3), BigRational::new(1.into(), 2.into())]);
+/// ```
+pub fn rational_roots(p: &Polynomial<BigRational>) -> Vec<BigRational> {
+    let coeffs: Vec<_> = p.clone().into_iter().collect();
+    let denom = coeffs.iter().fold(BigInt::one(), |acc, c| Integer::lcm(&acc, c.denom()));
+    let coeffs: Vec<_> = coeffs
+        .into_iter()
+        .map(|c| (c * &denom).to_integer())
+        .collect();
+
+    let mut roots = vec![];
+    let zeros = coeffs.iter().take_while(|c| c.is_zero()).count();
+    if zeros > 0 {
+        roots.push(BigRational::zero());
+    }
+
+    let coeffs = &coeffs[zeros..];
+    let (Some(first), Some(last)) = (coeffs.first(), coeffs.last()) else {
+        return roots;
+    };
+
+    let polynomial = Polynomial::new(coeffs.to_vec());
+
+    for p in integer_divisors(first.abs()) {
+        for q in integer_divisors(last.abs()) {
+            for candidate in [BigRational::new(p.clone(), q.clone()), BigRational::new(-&p, q)] {
+                if polynomial.eval(&candidate).is_zero() && !roots.contains(&candidate) {
+                    roots.push(candidate);
+                }
+            }
+        }
+    }
+
+    roots
+}
+
+/// A polynomial written as `leading_coeff * Π f_i^i`, where the `f_i` are square-free and
+/// pairwise coprime. With [`SquareFreeFactorization::factor_polynomial`] the `f_i` are monic and
+/// `leading_coeff` is the leading coefficient; with [`SquareFreeFactorization::factor_primitive`]
+/// they are primitive and `leading_coeff` is the content.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct SquareFreeFactorization<F> {
-    pub leading_coeff: F,
-    pub factors: Vec<(Polynomial<F>, NonZeroUsize)>,
+pub struct SquareFreeFactorization<R> {
+    pub leading_coeff: R,
+    pub factors: Vec<(Polynomial<R>, NonZeroUsize)>,
 }
 
 impl<F: Field> SquareFreeFactorization<F> {
@@ -129,6 +182,52 @@ impl<F: Field> SquareFreeFactorization<F> {
     }
 }
 
+impl<R: GcdDomain + Characteristic> SquareFreeFactorization<R> {
+    /// Returns the square free factorization of this polynomial over a gcd domain like the
+    /// integers, using Yun's algorithm on its primitive part.
+    ///
+    /// # Panics
+    ///
+    /// If `R` does not have characteristic zero, where the derivative of a nonconstant
+    /// polynomial can vanish.
+    pub fn factor_primitive(x: Polynomial<R>) -> Self where R: FromUsize + PartialEq {
+        assert_eq!(R::characteristic(), 0, "Yun's algorithm needs characteristic zero");
+        if x.is_zero() {
+            return SquareFreeFactorization {
+                leading_coeff: R::zero(),
+                factors: Vec::new(),
+            };
+        }
+
+        let divide = |a: Polynomial<R>, b: &Polynomial<R>| {
+            IntegralDomain::exact_div(&a, b).expect("the gcd divides both polynomials")
+        };
+        let leading_coeff = x.content();
+        let u = x.primitive_part();
+        let mut factors = Vec::new();
+        let mut r = u.clone().gcd(u.clone().derivative());
+        let mut f = divide(u, &r);
+        let mut j = NonZeroUsize::new(1).unwrap();
+        while !r.is_one() {
+            let g = r.clone().gcd(f.clone());
+            let s = divide(f, &g);
+            if !s.is_one() {
+                factors.push((s, j));
+            }
+            r = divide(r, &g);
+            f = g;
+            j = j.saturating_add(1);
+        }
+        if !f.is_one() {
+            factors.push((f, j));
+        }
+        SquareFreeFactorization {
+            leading_coeff,
+            factors,
+        }
+    }
+}
+
 impl<T: PrintableCoeff> DisplayWithVar for SquareFreeFactorization<T> {
     fn fmt_with_var<'a>(this: &'a PrintWithVar<'a, Self>, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         print_as_factor(&this.thing().leading_coeff, f)?;
@@ -142,17 +241,159 @@ impl<T: PrintableCoeff> DisplayWithVar for SquareFreeFactorization<T> {
     }
 }
 
+/// The factorization of a polynomial into irreducible factors with Kronecker's method: the values
+/// of a factor of degree `d` at `d + 1` points divide the values of the polynomial there, and
+/// determine the factor by interpolation. This takes exponential time in the degree.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::factorization::Kronecker;
+/// # use mathstuff_types::Polynomial;
+/// # use num::BigInt;
+/// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+/// // 2x^4 + 8 = 2(x^2 - 2x + 2)(x^2 + 2x + 2)
+/// let factors = Kronecker::<BigInt>::new(p(&[8, 0, 0, 0, 2])).into_factors();
+/// assert_eq!(factors, [p(&[2]), p(&[2, -2, 1]), p(&[2, 2, 1])]);
+/// ```
 pub struct Kronecker<Ring: CommutativeRing> {
     factors: Vec<Polynomial<Ring>>,
 }
 
+impl<Ring: CommutativeRing> Kronecker<Ring> {
+    /// The factors, with repetitions, whose product is the polynomial.
+    pub fn factors(&self) -> &[Polynomial<Ring>] {
+        &self.factors
+    }
+
+    pub fn into_factors(self) -> Vec<Polynomial<Ring>> {
+        self.factors
+    }
+}
+
 impl Kronecker<BigInt> {
+    /// Factors `polynomial` into its content, unless it is one, and primitive irreducible
+    /// polynomials with positive leading coefficients in increasing order of degree.
     pub fn new(polynomial: Polynomial<BigInt>) -> Self {
-        if polynomial.degree().map_or(true, |x| x <= 1) {
+        let content = polynomial.content();
+        if content.is_zero() {
+            return Kronecker {
+                factors: vec![polynomial],
+            };
+        }
+        let mut factors = vec![];
+        if !content.is_one() {
+            factors.push(Polynomial::new(vec![content]));
+        }
+        // the factor of least degree is irreducible, and the next one has at least its degree
+        let mut rest = polynomial.primitive_part();
+        while let Some(factor) = least_factor(&rest) {
+            rest = rest.exact_div(factor.clone()).expect("the factor divides the polynomial");
+            factors.push(factor);
+        }
+        if rest.degree() != Some(0) {
+            factors.push(rest);
+        }
+        Kronecker { factors }
+    }
+}
+
+impl Kronecker<BigRational> {
+    /// Factors `polynomial` into its leading coefficient, unless it is one, and monic irreducible
+    /// polynomials in increasing order of degree.
+    pub fn new(polynomial: Polynomial<BigRational>) -> Self {
+        let Some(lc) = polynomial.leading_coefficient().cloned() else {
             return Kronecker {
                 factors: vec![polynomial],
             };
+        };
+        let denom = polynomial
+            .clone()
+            .into_iter()
+            .fold(BigInt::one(), |acc, c| Integer::lcm(&acc, c.denom()));
+        let integral = polynomial
+            .into_iter()
+            .map(|c| (c * &denom).to_integer())
+            .collect();
+        let monic = Kronecker::<BigInt>::new(Polynomial::new(integral))
+            .factors
+            .into_iter()
+            .filter(|f| f.degree() != Some(0))
+            .map(|f| {
+                let lc = BigRational::from_integer(f.leading_coefficient_cloned());
+                let f = Polynomial::new(f.into_iter().map(BigRational::from_integer).collect());
+                f.scalar_mul(lc.inv())
+            });
+        let mut factors = vec![];
+        if !lc.is_one() {
+            factors.push(Polynomial::new(vec![lc]));
+        }
+        factors.extend(monic);
+        Kronecker { factors }
+    }
+}
+
+/// A nonconstant factor of least degree of the primitive `p` over the integers, with a positive
+/// leading coefficient, if `p` is reducible.
+fn least_factor(p: &Polynomial<BigInt>) -> Option<Polynomial<BigInt>> {
+    let n = p.degree()?;
+    // evaluation points where p is nonzero, of which there are at most n exceptions
+    let points = (0..)
+        .flat_map(|a: i64| [a, -a])
+        .skip(1)
+        .map(BigInt::from)
+        .map(|a| {
+            let value = p.eval(&a);
+            (a, value)
+        })
+        .filter(|(_, value)| !value.is_zero())
+        .take(n / 2 + 1)
+        .collect::<Vec<_>>();
+
+    for d in 1..=n / 2 {
+        let points = &points[..=d];
+        // the values of the factor, up to its sign, which is fixed by the first one
+        let candidates = points
+            .iter()
+            .enumerate()
+            .map(|(i, (_, value))| {
+                let divisors = integer_divisors(value.abs());
+                if i == 0 {
+                    divisors
+                } else {
+                    divisors.iter().flat_map(|d| [d.clone(), -d]).collect()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut indices = vec![0; d + 1];
+        loop {
+            let interpolated = lagrange_interpolation(
+                points
+                    .iter()
+                    .zip(&indices)
+                    .zip(&candidates)
+                    .map(|(((a, _), &i), values)| {
+                        let n = |x: &BigInt| BigRational::from_integer(x.clone());
+                        (n(a), n(&values[i]))
+                    })
+                    .collect(),
+            );
+            let coeffs = interpolated.into_iter().collect::<Vec<_>>();
+            if coeffs.len() == d + 1 && coeffs.iter().all(BigRational::is_integer) {
+                let factor = Polynomial::new(coeffs.iter().map(BigRational::to_integer).collect());
+                if p.clone().exact_div(factor.clone()).is_some() {
+                    return Some(factor.primitive_part());
+                }
+            }
+
+            // the next combination of values
+            let Some(k) = (0..=d).find(|&k| indices[k] + 1 < candidates[k].len()) else {
+                break;
+            };
+            indices[k] += 1;
+            indices[..k].fill(0);
         }
-        todo!()
     }
+    None
 }
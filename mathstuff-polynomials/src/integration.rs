@@ -0,0 +1,284 @@
+//! Integration of rational functions.
+//!
+//! The rational part of the integral is found with Hermite reduction, and the logarithmic part
+//! with the Lazard-Rioboo-Trager algorithm. Both are from Bronstein's *Symbolic Integration I*.
+
+use num::{One, Zero};
+
+use mathstuff_types::traits::{CommutativeRing, Field, FromUsize, IntegralDomain};
+use mathstuff_types::Polynomial;
+
+use crate::factorization::SquareFreeFactorization;
+use crate::resultant::resultant_of_prs;
+
+/// The result of [`hermite_reduce`]: `a / d = (g_num / g_den)' + h_num / h_den`, where `h_den` is
+/// square-free.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HermiteReduction<F> {
+    pub g_num: Polynomial<F>,
+    pub g_den: Polynomial<F>,
+    pub h_num: Polynomial<F>,
+    pub h_den: Polynomial<F>,
+}
+
+/// A sum `Σ t log(argument(t, x))` over the roots `t` of `roots_of`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LogTerm<F> {
+    pub roots_of: Polynomial<F>,
+    /// A polynomial in the integration variable whose coefficients are polynomials in `t`.
+    pub argument: Polynomial<Polynomial<F>>,
+}
+
+/// An antiderivative of a rational function, `numerator / denominator + Σ logs`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RationalIntegral<F> {
+    pub numerator: Polynomial<F>,
+    pub denominator: Polynomial<F>,
+    pub logs: Vec<LogTerm<F>>,
+}
+
+fn pow<R: CommutativeRing>(x: R, n: usize) -> R {
+    (0..n).fold(R::one(), |acc, _| acc * x.clone())
+}
+
+/// Finds `(s, t)` with `s * a + t * b = c` and `deg s < deg b`, given that `gcd(a, b)` divides `c`.
+fn solve_bezout<F: Field>(
+    a: Polynomial<F>,
+    b: Polynomial<F>,
+    c: Polynomial<F>,
+) -> (Polynomial<F>, Polynomial<F>) {
+    let (g, s, t) = a.clone().gcd_ext(b.clone());
+    let s = IntegralDomain::exact_div(&(s * c.clone()), &g).expect("the gcd divides c");
+    let t = IntegralDomain::exact_div(&(t * c), &g).expect("the gcd divides c");
+    let (q, s) = s.div_rem(b);
+    (s, t + q * a)
+}
+
+/// Hermite reduction. Given a rational function `a / d`, finds a rational function `g` and a
+/// rational function `h` with a square-free denominator such that `a / d = g' + h`.
+pub fn hermite_reduce<F: Field + FromUsize + PartialEq>(
+    a: Polynomial<F>,
+    d: Polynomial<F>,
+) -> HermiteReduction<F> {
+    hermite_reduce_with(a, d, |p| p.clone().derivative())
+}
+
+/// [`hermite_reduce`] with respect to an arbitrary derivation `derive` of `F[t]`, so that
+/// `a / d = derive(g) + h`. Every square-free factor `v` of `d` must be normal, i.e.
+/// `gcd(v, derive(v)) = 1`.
+pub fn hermite_reduce_with<F: Field + FromUsize + PartialEq>(
+    a: Polynomial<F>,
+    d: Polynomial<F>,
+    derive: impl Fn(&Polynomial<F>) -> Polynomial<F>,
+) -> HermiteReduction<F> {
+    let mut g_num = Polynomial::zero();
+    let mut g_den = Polynomial::one();
+    let mut a = a;
+    let mut d = d;
+
+    let sqf = SquareFreeFactorization::factor_polynomial(d.clone());
+    for (v, i) in sqf.factors {
+        let i = i.get();
+        if i < 2 {
+            continue;
+        }
+
+        let u = IntegralDomain::exact_div(&d, &pow(v.clone(), i)).expect("v^i divides d");
+        let dv = derive(&v);
+
+        for j in (1..i).rev() {
+            let rhs = a.scalar_mul(-F::from_usize(j).checked_inv().unwrap());
+            let (b, c) = solve_bezout(u.clone() * dv.clone(), v.clone(), rhs);
+
+            let vj = pow(v.clone(), j);
+            g_num = g_num * vj.clone() + b.clone() * g_den.clone();
+            g_den = g_den * vj;
+
+            a = c.scalar_mul(-F::from_usize(j)) - u.clone() * derive(&b);
+        }
+
+        d = u * v;
+    }
+
+    let g_common = g_num.clone().gcd(g_den.clone());
+    let h_common = a.clone().gcd(d.clone());
+    HermiteReduction {
+        g_num: IntegralDomain::exact_div(&g_num, &g_common).expect("the gcd divides g_num"),
+        g_den: IntegralDomain::exact_div(&g_den, &g_common).expect("the gcd divides g_den"),
+        h_num: IntegralDomain::exact_div(&a, &h_common).expect("the gcd divides a"),
+        h_den: IntegralDomain::exact_div(&d, &h_common).expect("the gcd divides d"),
+    }
+}
+
+type BiPolynomial<F> = Polynomial<Polynomial<F>>;
+
+fn exact_div_coeffs<F: Field>(p: BiPolynomial<F>, c: &Polynomial<F>) -> BiPolynomial<F> {
+    let div = |x| IntegralDomain::exact_div(&x, c).expect("division was not exact");
+    Polynomial::new(p.into_iter().map(div).collect())
+}
+
+/// The Lazard-Rioboo-Trager algorithm. Given `a / d` where `d` is square-free and
+/// `deg a < deg d`, returns the logarithmic terms whose derivative is `a / d`.
+pub fn lazard_rioboo_trager<F: Field + FromUsize + PartialEq>(
+    a: Polynomial<F>,
+    d: Polynomial<F>,
+) -> Vec<LogTerm<F>> {
+    let common = a.clone().gcd(d.clone());
+    let a = IntegralDomain::exact_div(&a, &common).expect("the gcd divides a");
+    let d = IntegralDomain::exact_div(&d, &common).expect("the gcd divides d");
+    let dd = d.clone().derivative();
+    lazard_rioboo_trager_with(a, d, dd)
+}
+
+/// The log terms of [`lazard_rioboo_trager`] with respect to an arbitrary derivation, given
+/// `a / d` in lowest terms and the derivative `dd` of `d`, where `deg dd <= deg d`.
+///
+/// The terms are only guaranteed to be correct if the roots of every `roots_of` are constants.
+pub fn lazard_rioboo_trager_with<F: Field + FromUsize + PartialEq>(
+    a: Polynomial<F>,
+    d: Polynomial<F>,
+    dd: Polynomial<F>,
+) -> Vec<LogTerm<F>> {
+    if a.is_zero() || d.degree() == Some(0) {
+        return vec![];
+    }
+
+    let lift = |p: Polynomial<F>| -> BiPolynomial<F> {
+        p.into_iter()
+            .map(|c| Polynomial::new_trim_zeroes(vec![c]))
+            .collect()
+    };
+
+    // a - t d'
+    let t = Polynomial::new(vec![F::zero(), F::one()]);
+    let rhs = lift(a) - lift(dd).scalar_mul(t);
+
+    let prs = lift(d.clone()).subresultant_prs(rhs);
+    let resultant = resultant_of_prs(&prs);
+
+    let sqf = SquareFreeFactorization::factor_polynomial(resultant);
+    sqf.factors
+        .into_iter()
+        .map(|(q, i)| {
+            let i = i.get();
+            let argument = if Some(i) == d.degree() {
+                lift(d.clone())
+            } else {
+                let s = prs
+                    .iter()
+                    .find(|r| r.degree() == Some(i))
+                    .expect("the prs contains a subresultant of every degree of a factor")
+                    .clone();
+
+                let lc = SquareFreeFactorization::factor_polynomial(s.leading_coefficient_cloned());
+                lc.factors.into_iter().fold(s, |s, (aj, j)| {
+                    exact_div_coeffs(s, &pow(aj.gcd(q.clone()), j.get()))
+                })
+            };
+
+            let argument = Polynomial::new_trim_zeroes(
+                argument
+                    .into_iter()
+                    .map(|c| c.div_rem(q.clone()).1)
+                    .collect(),
+            );
+
+            LogTerm {
+                roots_of: q,
+                argument,
+            }
+        })
+        .collect()
+}
+
+/// Rioboo's conversion of complex logarithms to real arctangents. Returns polynomials `p_k` such
+/// that `Σ 2 atan(p_k)` has the same derivative as `i log((a + i b) / (a - i b))`.
+pub fn log_to_atan<F: Field + PartialEq>(a: Polynomial<F>, b: Polynomial<F>) -> Vec<Polynomial<F>> {
+    let (q, r) = a.clone().div_rem(b.clone());
+    if r.is_zero() {
+        return vec![q];
+    }
+
+    if a.degree() < b.degree() {
+        return log_to_atan(-b, a);
+    }
+
+    let (g, d, c) = b.clone().gcd_ext(-a.clone());
+    let quotient = IntegralDomain::exact_div(&(a * d.clone() + b * c.clone()), &g)
+        .expect("the gcd divides a d + b c");
+    let mut res = vec![quotient];
+    res.extend(log_to_atan(d, c));
+    res
+}
+
+/// [`log_to_atan`] over `F(√k)`, for `a` and `b` that are either in `F[x]` or in `√k F[x]`.
+///
+/// Each of `a`, `b` and the returned polynomials is a pair `(p, odd)` that stands for `p √k` if
+/// `odd` is true and for `p` otherwise.
+pub fn log_to_atan_sqrt<F: Field + PartialEq>(
+    (a, a_odd): (Polynomial<F>, bool),
+    (b, b_odd): (Polynomial<F>, bool),
+    k: &F,
+) -> Vec<(Polynomial<F>, bool)> {
+    let k_inv = k.checked_inv().expect("k is nonzero");
+    // p √k^i / √k^j
+    let adjust = |p: Polynomial<F>, i: bool, j: bool| match (i, j) {
+        (false, true) => (p.scalar_mul(k_inv.clone()), true),
+        (true, true) => (p, false),
+        (i, false) => (p, i),
+    };
+
+    let (q, r) = a.clone().div_rem(b.clone());
+    if r.is_zero() {
+        return vec![adjust(q, a_odd, b_odd)];
+    }
+
+    if a.degree() < b.degree() {
+        return log_to_atan_sqrt((-b, b_odd), (a, a_odd), k);
+    }
+
+    // with d b - c a = g over F, D = d / √k^b_odd and C = c / √k^a_odd satisfy D B - C A = g.
+    let (g, d, c) = b.clone().gcd_ext(-a.clone());
+    let ad = adjust(a * d.clone(), a_odd, b_odd);
+    let bc = adjust(b * c.clone(), b_odd, a_odd);
+    debug_assert_eq!(ad.1, bc.1);
+
+    let (d, d_odd) = adjust(d, false, b_odd);
+    let (c, c_odd) = adjust(c, false, a_odd);
+
+    let quotient =
+        IntegralDomain::exact_div(&(ad.0 + bc.0), &g).expect("the gcd divides a d + b c");
+    let mut res = vec![(quotient, ad.1)];
+    res.extend(log_to_atan_sqrt((d, d_odd), (c, c_odd), k));
+    res
+}
+
+/// Integrates `a / d`. The result is `numerator / denominator + Σ logs`.
+pub fn integrate_rational_function<F: Field + FromUsize + PartialEq>(
+    a: Polynomial<F>,
+    d: Polynomial<F>,
+) -> RationalIntegral<F> {
+    let (p, a) = a.div_rem(d.clone());
+
+    let HermiteReduction {
+        g_num,
+        g_den,
+        h_num,
+        h_den,
+    } = hermite_reduce(a, d);
+
+    // the polynomial part can be integrated term by term
+    let mut coeffs = vec![F::zero()];
+    coeffs.extend(
+        p.into_iter()
+            .enumerate()
+            .map(|(i, c)| c * F::from_usize(i + 1).checked_inv().unwrap()),
+    );
+    let p = Polynomial::new_trim_zeroes(coeffs);
+
+    RationalIntegral {
+        numerator: g_num + p * g_den.clone(),
+        denominator: g_den,
+        logs: lazard_rioboo_trager(h_num, h_den),
+    }
+}
@@ -1,7 +1,13 @@
 pub mod factorization;
+pub mod integration;
 pub mod print;
+pub mod resultant;
+pub mod risch;
+pub mod roots;
 pub mod traits;
 pub mod modular_arith;
+pub mod multivariate_gcd;
+pub mod partial_fractions;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,403 @@
+//! Greatest common divisors of multivariate polynomials over `Z` and `Q`.
+//!
+//! [`subresultant_gcd`] views the polynomials as univariate polynomials in their first variable
+//! with coefficients in the remaining ones, and recursively computes contents and subresultant
+//! polynomial remainder sequences. [`modular_gcd`] is Brown's dense modular algorithm: it
+//! computes the gcd modulo several primes by evaluating all but the first variable and
+//! interpolating, then combines the images with the Chinese remainder theorem and checks the
+//! result by trial division. It is usually much faster since the coefficients never grow.
+//!
+//! The gcds over `Z` have a positive leading coefficient and the ones over `Q` are monic, with
+//! respect to the monomial order of the arguments.
+
+use std::collections::HashMap;
+
+use num::{BigInt, BigRational, Integer, One, Signed, ToPrimitive, Zero};
+
+use mathstuff_types::finite_field::Fp;
+use mathstuff_types::traits::{CheckedInv, CommutativeRing, IntegralDomain};
+use mathstuff_types::{MonomialOrder, MultiPolynomial, Polynomial};
+
+type Poly = MultiPolynomial<BigInt>;
+
+fn constant_like<R: CommutativeRing>(like: &MultiPolynomial<R>, c: R) -> MultiPolynomial<R> {
+    like.with_terms(vec![(vec![0; like.vars().len()], c)])
+}
+
+/// `p` with a positive leading coefficient.
+fn normalize_sign(p: Poly) -> Poly {
+    match p.leading_coefficient() {
+        Some(lc) if lc.is_negative() => -p,
+        _ => p,
+    }
+}
+
+/// The gcd of the coefficients and the primitive part of `p`.
+fn integer_content(p: &Poly) -> (BigInt, Poly) {
+    let content = p.terms().fold(BigInt::zero(), |acc, (_, c)| acc.gcd(c));
+    if content.is_zero() {
+        return (content, p.clone());
+    }
+    let pp = p.clone().map_coefficients(|c| c / &content);
+    (content, pp)
+}
+
+/// `p` as a univariate polynomial in the `k`-th variable, with coefficients in the others.
+pub(crate) fn to_recursive<R: CommutativeRing>(
+    p: &MultiPolynomial<R>,
+    k: usize,
+) -> Polynomial<MultiPolynomial<R>> {
+    let mut coeffs = vec![vec![]; p.degree_in(k).map_or(0, |d| d + 1)];
+    for (exps, c) in p.terms() {
+        let mut exps = exps.to_vec();
+        let e = std::mem::take(&mut exps[k]);
+        coeffs[e].push((exps, c.clone()));
+    }
+    Polynomial::new(coeffs.into_iter().map(|t| p.with_terms(t)).collect())
+}
+
+fn from_recursive(p: Polynomial<Poly>, k: usize, like: &Poly) -> Poly {
+    p.into_iter()
+        .enumerate()
+        .fold(like.with_terms(vec![]), |acc, (i, c)| {
+            let mut exps = vec![0; like.vars().len()];
+            exps[k] = i;
+            acc + c.mul_term(&exps, BigInt::one())
+        })
+}
+
+fn recursive_exact_div(p: Polynomial<Poly>, d: &Poly) -> Polynomial<Poly> {
+    p.into_iter()
+        .map(|c| IntegralDomain::exact_div(&c, d).expect("division was not exact"))
+        .collect()
+}
+
+/// The content of `p` in the variables after the `k`-th.
+fn recursive_content(p: &Polynomial<Poly>, k: usize, like: &Poly) -> Poly {
+    p.clone()
+        .into_iter()
+        .fold(like.with_terms(vec![]), |acc, c| gcd_rec(acc, c, k + 1))
+}
+
+/// The gcd of `a` and `b`, which only involve the variables from the `k`-th one on.
+fn gcd_rec(a: Poly, b: Poly, k: usize) -> Poly {
+    if a.is_zero() {
+        return b;
+    }
+    if b.is_zero() {
+        return a;
+    }
+    if k == a.vars().len() {
+        let c = a.leading_coefficient_cloned();
+        return constant_like(&a, c.gcd(b.leading_coefficient().unwrap()));
+    }
+    if a.degree_in(k) == Some(0) && b.degree_in(k) == Some(0) {
+        return gcd_rec(a, b, k + 1);
+    }
+
+    let (a_rec, b_rec) = (to_recursive(&a, k), to_recursive(&b, k));
+    let ca = recursive_content(&a_rec, k, &a);
+    let cb = recursive_content(&b_rec, k, &a);
+    let c = gcd_rec(ca.clone(), cb.clone(), k + 1);
+
+    // the last nonzero polynomial of the subresultant remainder sequence is a multiple of the
+    // gcd, which is one if that polynomial is constant
+    let mut prs = recursive_exact_div(a_rec, &ca).subresultant_prs(recursive_exact_div(b_rec, &cb));
+    let g = match prs.pop().unwrap() {
+        g if g.degree() == Some(0) => Polynomial::one(),
+        g => g,
+    };
+    let g = recursive_exact_div(g.clone(), &recursive_content(&g, k, &a));
+    from_recursive(g, k, &a) * c
+}
+
+/// The gcd of `a` and `b` with the recursive subresultant algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use mathstuff_polynomials::multivariate_gcd::subresultant_gcd;
+/// use mathstuff_types::{MonomialOrder, MultiPolynomial};
+/// use num::{BigInt, One};
+///
+/// let [x, y] = MultiPolynomial::<BigInt>::generators(&["x", "y"], MonomialOrder::Lex);
+/// let g = x.clone() * y.clone() + MultiPolynomial::one();
+/// let a = g.clone() * (x.clone() + y.clone()).pow(2);
+/// let b = g.clone() * (x - y) * MultiPolynomial::constant(BigInt::from(6));
+/// assert_eq!(subresultant_gcd(&a, &b), g);
+/// ```
+pub fn subresultant_gcd(a: &Poly, b: &Poly) -> Poly {
+    let (a, b) = a.clone().unify(b.clone());
+    normalize_sign(gcd_rec(a, b, 0))
+}
+
+type PolyP<const P: u64> = MultiPolynomial<Fp<P>>;
+
+/// The univariate polynomial `u` in the `k`-th variable as a polynomial like `like`.
+fn embed<const P: u64>(u: Polynomial<Fp<P>>, k: usize, like: &PolyP<P>) -> PolyP<P> {
+    let terms = u
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let mut exps = vec![0; like.vars().len()];
+            exps[k] = i;
+            (exps, c)
+        })
+        .collect();
+    like.with_terms(terms)
+}
+
+/// The coefficients of `p` as a polynomial in the variables before the `k`-th, which are
+/// univariate polynomials in the `k`-th variable, with the leading one first.
+fn coefficients_in_last<const P: u64>(p: &PolyP<P>, k: usize) -> Vec<Polynomial<Fp<P>>> {
+    let mut coeffs: Vec<(&[usize], Vec<Fp<P>>)> = vec![];
+    // in lex order the terms with the same exponents in the variables before the `k`-th are
+    // adjacent
+    for (exps, c) in p.terms() {
+        let (prefix, e) = (&exps[..k], exps[k]);
+        if coeffs.last().map(|(last, _)| *last) != Some(prefix) {
+            coeffs.push((prefix, vec![]));
+        }
+        let coeff = &mut coeffs.last_mut().unwrap().1;
+        if coeff.len() <= e {
+            coeff.resize(e + 1, Fp::zero());
+        }
+        coeff[e] = *c;
+    }
+    coeffs
+        .into_iter()
+        .map(|(_, c)| Polynomial::new_trim_zeroes(c))
+        .collect()
+}
+
+fn content_in_last<const P: u64>(p: &PolyP<P>, k: usize) -> Polynomial<Fp<P>> {
+    coefficients_in_last(p, k)
+        .into_iter()
+        .fold(Polynomial::zero(), Polynomial::gcd)
+}
+
+fn divides<const P: u64>(a: &PolyP<P>, b: &PolyP<P>) -> bool {
+    IntegralDomain::exact_div(b, a).is_some()
+}
+
+/// The monic gcd of the nonzero `a` and `b` in `Z_p[x_0, ..., x_k]`, given in lex order.
+/// `None` if there were not enough evaluation points.
+fn pgcd<const P: u64>(a: &PolyP<P>, b: &PolyP<P>, k: usize) -> Option<PolyP<P>> {
+    let (ca, cb) = (content_in_last(a, k), content_in_last(b, k));
+    let c = embed(ca.clone().gcd(cb.clone()), k, a);
+    if k == 0 {
+        return Some(c);
+    }
+    let a = IntegralDomain::exact_div(a, &embed(ca, k, a)).unwrap();
+    let b = IntegralDomain::exact_div(b, &embed(cb, k, b)).unwrap();
+
+    // the leading coefficient of the gcd divides g, so scaling the images to have the leading
+    // coefficient g(α) gives the images of a multiple of the gcd of bounded degree
+    let lc_a = coefficients_in_last(&a, k).swap_remove(0);
+    let lc_b = coefficients_in_last(&b, k).swap_remove(0);
+    let g = lc_a.clone().gcd(lc_b.clone());
+    let bound = g.degree().unwrap() + a.degree_in(k).min(b.degree_in(k)).unwrap();
+
+    let mut q = Polynomial::<Fp<P>>::one();
+    let mut h: Option<(PolyP<P>, Vec<usize>)> = None;
+    for alpha in (0..P as i64).map(Fp::new) {
+        if lc_a.eval(&alpha).is_zero() || lc_b.eval(&alpha).is_zero() {
+            continue;
+        }
+        let image = pgcd(&a.eval_var(k, &alpha), &b.eval_var(k, &alpha), k - 1)?;
+        let lm = image.leading_monomial().unwrap().to_vec();
+        if lm.iter().all(Zero::is_zero) {
+            return Some(c);
+        }
+        let image = image.scalar_mul(g.eval(&alpha));
+        let linear = Polynomial::new_trim_zeroes(vec![-alpha, Fp::one()]);
+
+        match &mut h {
+            // Newton interpolation
+            Some((h, h_lm)) if lm == *h_lm => {
+                let diff = image - h.eval_var(k, &alpha);
+                let scale = q.eval(&alpha).checked_inv().unwrap();
+                *h = h.clone() + embed(q.clone(), k, &a) * diff.scalar_mul(scale);
+                q = q * linear;
+            }
+            // α is unlucky
+            Some((_, h_lm)) if lm > *h_lm => continue,
+            // all the previous points were unlucky
+            _ => {
+                h = Some((image, lm));
+                q = linear;
+            }
+        }
+
+        let (h, _) = h.as_ref().unwrap();
+        if q.degree().unwrap() > bound {
+            let content = embed(content_in_last(h, k), k, h);
+            let pp = IntegralDomain::exact_div(h, &content).unwrap();
+            if divides(&pp, &a) && divides(&pp, &b) {
+                return Some((pp * c).monic());
+            }
+        }
+    }
+    None
+}
+
+/// The image modulo `P` of the gcd of `a` and `b` in `Z[x_0, ..., x_k]`, given in lex order,
+/// scaled to the leading coefficient `gamma` and with coefficients in `[0, P)`. `None` if there
+/// were not enough evaluation points.
+fn modular_image<const P: u64>(a: &Poly, b: &Poly, gamma: &BigInt, k: usize) -> Option<Poly> {
+    let reduce = |c: &BigInt| Fp::<P>::new(c.mod_floor(&BigInt::from(P)).to_i64().unwrap());
+    let (a, b) = (
+        a.clone().map_coefficients(|c| reduce(&c)),
+        b.clone().map_coefficients(|c| reduce(&c)),
+    );
+    let g = pgcd(&a, &b, k)?;
+    Some(
+        g.scalar_mul(reduce(gamma))
+            .map_coefficients(|c| BigInt::from(c.value())),
+    )
+}
+
+type ImageFn = fn(&Poly, &Poly, &BigInt, usize) -> Option<Poly>;
+
+macro_rules! primes {
+    ($($p:literal),* $(,)?) => {
+        [$(($p, modular_image::<$p> as ImageFn)),*]
+    };
+}
+
+/// The largest primes below `2^31`, so that products of residues fit in a `u64`, with the
+/// [`modular_image`] for each of them.
+const PRIMES: [(u64, ImageFn); 32] = primes![
+    2147483647, 2147483629, 2147483587, 2147483579, 2147483563, 2147483549, 2147483543,
+    2147483497, 2147483489, 2147483477, 2147483423, 2147483399, 2147483353, 2147483323,
+    2147483269, 2147483249, 2147483237, 2147483179, 2147483171, 2147483137, 2147483123,
+    2147483077, 2147483069, 2147483059, 2147483053, 2147483033, 2147483029, 2147482951,
+    2147482949, 2147482943, 2147482937, 2147482921,
+];
+
+/// The polynomial congruent to `h` modulo `m` and to `image` modulo `p`, with coefficients in
+/// `[0, m p)`.
+fn chinese_remainder(h: &Poly, m: &BigInt, image: &Poly, p: &BigInt) -> Poly {
+    let m_inv = m.extended_gcd(p).x;
+    let mut coeffs = HashMap::<_, (BigInt, BigInt)>::new();
+    for (exps, c) in h.terms() {
+        coeffs.entry(exps.to_vec()).or_default().0 = c.clone();
+    }
+    for (exps, c) in image.terms() {
+        coeffs.entry(exps.to_vec()).or_default().1 = c.clone();
+    }
+    let terms = coeffs
+        .into_iter()
+        .map(|(exps, (u, v))| {
+            let t = ((v - &u) * &m_inv).mod_floor(p);
+            (exps, u + m * t)
+        })
+        .collect();
+    h.with_terms(terms)
+}
+
+/// `h` with coefficients in `(-m / 2, m / 2]`.
+fn symmetric(h: &Poly, m: &BigInt) -> Poly {
+    h.clone()
+        .map_coefficients(|c| if &c * 2 > *m { c - m } else { c })
+}
+
+/// The gcd of `a` and `b` with Brown's modular algorithm. If the coefficients of the gcd are too
+/// big to be determined by the images modulo all the primes it uses, the gcd is computed with
+/// [`subresultant_gcd`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use mathstuff_polynomials::multivariate_gcd::modular_gcd;
+/// use mathstuff_types::{MonomialOrder, MultiPolynomial};
+/// use num::BigInt;
+///
+/// let [x, y, z] = MultiPolynomial::<BigInt>::generators(&["x", "y", "z"], MonomialOrder::GrLex);
+/// let c = |n: i64| MultiPolynomial::constant(BigInt::from(n));
+/// let g = c(3) * x.clone() * z.clone() - y.clone().pow(2) + c(1);
+/// let a = g.clone() * (x.clone() - z.clone()) * c(4);
+/// let b = g.clone() * (y + z.pow(3)) * c(6);
+/// assert_eq!(modular_gcd(&a, &b), g * c(2));
+/// ```
+pub fn modular_gcd(a: &Poly, b: &Poly) -> Poly {
+    let (a, b) = a.clone().unify(b.clone());
+    let order = a.order();
+    if a.is_zero() || b.is_zero() {
+        return normalize_sign(a + b);
+    }
+
+    let (ca, a) = integer_content(&a.with_order(MonomialOrder::Lex));
+    let (cb, b) = integer_content(&b.with_order(MonomialOrder::Lex));
+    let c = constant_like(&a, ca.gcd(&cb));
+    let n = a.vars().len();
+    if n == 0 {
+        return c.with_order(order);
+    }
+
+    let (lc_a, lc_b) = (
+        a.leading_coefficient_cloned(),
+        b.leading_coefficient_cloned(),
+    );
+    let gamma = lc_a.gcd(&lc_b);
+
+    let mut h: Option<(Poly, BigInt, Vec<usize>)> = None;
+    let mut last_candidate = None;
+    for &(p, modular_image) in &PRIMES {
+        let big_p = BigInt::from(p);
+        if (&lc_a % &big_p).is_zero() || (&lc_b % &big_p).is_zero() {
+            continue;
+        }
+        let Some(image) = modular_image(&a, &b, &gamma, n - 1) else {
+            continue;
+        };
+        let lm = image.leading_monomial().unwrap().to_vec();
+        if lm.iter().all(Zero::is_zero) {
+            return c.with_order(order);
+        }
+
+        let (combined, m) = match h.take() {
+            Some((h, m, h_lm)) if h_lm == lm => {
+                (chinese_remainder(&h, &m, &image, &big_p), m * big_p)
+            }
+            // p is unlucky
+            Some(prev) if lm > prev.2 => {
+                h = Some(prev);
+                continue;
+            }
+            // all the previous primes were unlucky
+            _ => (image, big_p),
+        };
+
+        let candidate = integer_content(&symmetric(&combined, &m)).1;
+        h = Some((combined, m, lm));
+        // the images stabilized, check whether the candidate divides both polynomials
+        if last_candidate.as_ref() == Some(&candidate)
+            && IntegralDomain::exact_div(&a, &candidate).is_some()
+            && IntegralDomain::exact_div(&b, &candidate).is_some()
+        {
+            return normalize_sign((candidate * c).with_order(order));
+        }
+        last_candidate = Some(candidate);
+    }
+    normalize_sign((subresultant_gcd(&a, &b) * c).with_order(order))
+}
+
+/// The monic gcd of `a` and `b`, computed with [`modular_gcd`] after clearing denominators.
+pub fn rational_gcd(
+    a: &MultiPolynomial<BigRational>,
+    b: &MultiPolynomial<BigRational>,
+) -> MultiPolynomial<BigRational> {
+    let to_integer = |p: &MultiPolynomial<BigRational>| {
+        let l = p
+            .terms()
+            .fold(BigInt::one(), |acc, (_, c)| acc.lcm(c.denom()));
+        p.clone()
+            .map_coefficients(|c| (c * BigRational::from_integer(l.clone())).to_integer())
+    };
+    let g = modular_gcd(&to_integer(a), &to_integer(b)).map_coefficients(BigRational::from_integer);
+    match g.leading_coefficient().and_then(CheckedInv::checked_inv) {
+        Some(lc) => g.scalar_mul(lc),
+        None => g,
+    }
+}
@@ -0,0 +1,129 @@
+//! Partial fraction decomposition of rational functions.
+//!
+//! Given `n / d` and a factorization `d = c Π f_i^e_i` into pairwise coprime factors, the
+//! numerator of the part with denominator `f_i^e_i` is `n / c` times the inverse of
+//! `d / f_i^e_i` modulo `f_i^e_i`, from the extended Euclidean algorithm. Its `f_i`-adic
+//! expansion then gives the numerators of `1 / f_i^k` for `k = 1, ..., e_i`.
+
+use std::num::NonZeroUsize;
+
+use num::{BigRational, Zero};
+
+use mathstuff_types::traits::{Field, FromUsize};
+use mathstuff_types::Polynomial;
+
+use crate::factorization::{Kronecker, SquareFreeFactorization};
+
+/// A term `numerator / factor^power` of a partial fraction decomposition, where
+/// `deg numerator < deg factor`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PartialFraction<F> {
+    pub numerator: Polynomial<F>,
+    pub factor: Polynomial<F>,
+    pub power: NonZeroUsize,
+}
+
+/// A rational function written as `polynomial + Σ terms`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PartialFractions<F> {
+    pub polynomial: Polynomial<F>,
+    pub terms: Vec<PartialFraction<F>>,
+}
+
+/// The partial fraction decomposition of `numerator / denominator`, given the factorization
+/// `denominator = c Π f_i^e_i` as the pairs `(f_i, e_i)`. The factors must be pairwise coprime.
+/// The terms are ordered like the factors and then by increasing power, and the ones with a zero
+/// numerator are left out.
+///
+/// # Panics
+///
+/// If the factors are not coprime or their product does not divide `denominator`.
+pub fn apart_with_factors<F: Field + PartialEq>(
+    numerator: Polynomial<F>,
+    denominator: Polynomial<F>,
+    factors: &[(Polynomial<F>, NonZeroUsize)],
+) -> PartialFractions<F> {
+    let (polynomial, remainder) = numerator.div_rem(denominator.clone());
+
+    let mut terms = vec![];
+    for (factor, power) in factors {
+        let full = num::pow(factor.clone(), power.get());
+        let (cofactor, r) = denominator.clone().div_rem(full.clone());
+        assert!(r.is_zero(), "the factors must divide the denominator");
+        let inverse = cofactor
+            .inverse_mod(&full)
+            .expect("the factors must be coprime");
+        let mut a = (remainder.clone() * inverse).div_rem(full).1;
+
+        // a = Σ c_k f^(e - k) with deg c_k < deg f, so a / f^e = Σ c_k / f^k
+        let mut expansion = vec![];
+        for _ in 0..power.get() {
+            let (q, c) = a.div_rem(factor.clone());
+            expansion.push(c);
+            a = q;
+        }
+        for (k, c) in expansion.into_iter().rev().enumerate() {
+            if !c.is_zero() {
+                terms.push(PartialFraction {
+                    numerator: c,
+                    factor: factor.clone(),
+                    power: NonZeroUsize::new(k + 1).unwrap(),
+                });
+            }
+        }
+    }
+
+    PartialFractions { polynomial, terms }
+}
+
+/// The partial fraction decomposition of `numerator / denominator` over `F`, with the factors of
+/// the square-free factorization of the denominator. See [`apart`] for a finer decomposition
+/// over `Q`.
+pub fn apart_square_free<F: Field + FromUsize + PartialEq>(
+    numerator: Polynomial<F>,
+    denominator: Polynomial<F>,
+) -> PartialFractions<F> {
+    let factors = SquareFreeFactorization::factor_polynomial(denominator.clone()).factors;
+    apart_with_factors(numerator, denominator, &factors)
+}
+
+/// The partial fraction decomposition of `numerator / denominator` over `Q`. The denominator is
+/// factored with [`SquareFreeFactorization`], and each square-free factor is split into its
+/// monic irreducible factors over `Q` with [`Kronecker`], so every term has an irreducible
+/// denominator.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::partial_fractions::apart;
+/// # use mathstuff_types::Polynomial;
+/// # use num::BigRational;
+/// let p = |coeffs: &[i32]| {
+///     Polynomial::new(coeffs.iter().map(|&c| BigRational::from_integer(c.into())).collect())
+/// };
+/// // (x + 3) / (x^2 - 1) = 2 / (x - 1) - 1 / (x + 1)
+/// let apart = apart(p(&[3, 1]), p(&[-1, 0, 1]));
+/// let terms = apart.terms.iter().map(|t| (&t.numerator, &t.factor)).collect::<Vec<_>>();
+/// assert_eq!(terms, [(&p(&[-1]), &p(&[1, 1])), (&p(&[2]), &p(&[-1, 1]))]);
+/// ```
+///
+/// # Panics
+///
+/// If `denominator` is zero.
+pub fn apart(
+    numerator: Polynomial<BigRational>,
+    denominator: Polynomial<BigRational>,
+) -> PartialFractions<BigRational> {
+    let mut factors = vec![];
+    let square_free = SquareFreeFactorization::factor_polynomial(denominator.clone());
+    for (factor, power) in square_free.factors {
+        let irreducibles = Kronecker::<BigRational>::new(factor).into_factors();
+        factors.extend(
+            irreducibles
+                .into_iter()
+                .filter(|f| f.degree() != Some(0))
+                .map(|f| (f, power)),
+        );
+    }
+    apart_with_factors(numerator, denominator, &factors)
+}
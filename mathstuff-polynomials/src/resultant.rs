@@ -0,0 +1,221 @@
+//! Resultants and discriminants of polynomials.
+//!
+//! The resultant of `a = a_m x^m + ...` and `b = b_n x^n + ...` is the determinant of their
+//! Sylvester matrix, and equals `a_m^n b_n^m Π (α_i - β_j)` over the roots `α_i` of `a` and
+//! `β_j` of `b`. It vanishes iff `a` and `b` have a common root (or both leading coefficients
+//! vanish), which makes it useful to eliminate a variable from two equations.
+//!
+//! [`sylvester_resultant`] computes the determinant without divisions, so it works over any
+//! commutative ring. [`resultant`] follows the subresultant polynomial remainder sequence instead,
+//! which is much faster but needs the exact divisions of an integral domain.
+
+use num::Zero;
+
+use mathstuff_types::matrix::Matrix;
+use mathstuff_types::traits::{CommutativeRing, FromUsize, IntegralDomain};
+use mathstuff_types::{MultiPolynomial, Polynomial};
+
+use crate::multivariate_gcd::to_recursive;
+
+/// The Sylvester matrix of `a` and `b` of degrees `m` and `n`. Its first `n` rows hold the
+/// coefficients of `x^(n - 1) a, ..., a` and its last `m` rows those of `x^(m - 1) b, ..., b`,
+/// from the highest degree to the lowest.
+///
+/// Zero polynomials are treated as constants.
+pub fn sylvester_matrix<R: CommutativeRing>(a: &Polynomial<R>, b: &Polynomial<R>) -> Matrix<R> {
+    let (m, n) = (a.degree().unwrap_or(0), b.degree().unwrap_or(0));
+    let rows = |p: &Polynomial<R>, count: usize| {
+        let mut coeffs = p.clone().into_iter().collect::<Vec<_>>();
+        coeffs.reverse();
+        (0..count).map(move |i| {
+            let mut row = vec![R::zero(); m + n];
+            for (j, c) in coeffs.iter().enumerate() {
+                row[i + j] = c.clone();
+            }
+            row
+        })
+    };
+    Matrix::from_rows(rows(a, n).chain(rows(b, m)).collect())
+}
+
+/// The determinant of a square matrix, from its characteristic polynomial, which Berkowitz's
+/// algorithm computes without divisions.
+fn determinant<R: CommutativeRing>(matrix: &Matrix<R>) -> R {
+    let n = matrix.rows();
+    // the constant coefficient of det(x I - A) is (-1)^n det(A)
+    let det = matrix.charpoly().coeff_at(0).clone();
+    if n.is_multiple_of(2) {
+        det
+    } else {
+        -det
+    }
+}
+
+/// The resultant of `a` and `b`, as the determinant of their [`sylvester_matrix`]. This works
+/// over any commutative ring, but takes `O((m + n)^4)` ring operations.
+pub fn sylvester_resultant<R: CommutativeRing>(a: &Polynomial<R>, b: &Polynomial<R>) -> R {
+    if a.is_zero() || b.is_zero() {
+        return R::zero();
+    }
+    determinant(&sylvester_matrix(a, b))
+}
+
+/// The resultant of the first two polynomials of their subresultant polynomial remainder sequence
+/// `prs` (see [`Polynomial::subresultant_prs`]), which has the one of higher degree first.
+pub(crate) fn resultant_of_prs<R: IntegralDomain>(prs: &[Polynomial<R>]) -> R {
+    let [.., before, last] = prs else {
+        return R::zero();
+    };
+    if last.degree() != Some(0) {
+        return R::zero();
+    }
+
+    // Collins' algorithm, see Cohen, "A Course in Computational Algebraic Number Theory",
+    // algorithm 3.3.7
+    let exact = "the subresultant divisions are exact";
+    let mut negate = false;
+    let mut h = R::one();
+    for pair in prs[..prs.len() - 1].windows(2) {
+        let (da, db) = (pair[0].degree().unwrap(), pair[1].degree().unwrap());
+        if da % 2 == 1 && db % 2 == 1 {
+            negate = !negate;
+        }
+        let delta = da - db;
+        if delta > 0 {
+            let g = num::pow(pair[1].leading_coefficient_cloned(), delta);
+            h = g.exact_div(&num::pow(h, delta - 1)).expect(exact);
+        }
+    }
+    let d = before.degree().unwrap();
+    let res = num::pow(last.leading_coefficient_cloned(), d)
+        .exact_div(&num::pow(h, d.saturating_sub(1)))
+        .expect(exact);
+    if negate {
+        -res
+    } else {
+        res
+    }
+}
+
+/// The resultant of `a` and `b`, computed with the subresultant polynomial remainder sequence,
+/// whose divisions are exact in any integral domain. Zero if either polynomial is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::resultant::resultant;
+/// # use mathstuff_types::Polynomial;
+/// # use num::BigInt;
+/// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+/// // res(x^2 - 2, x - 3) = (√2 - 3)(-√2 - 3) = 7
+/// assert_eq!(resultant(&p(&[-2, 0, 1]), &p(&[-3, 1])), BigInt::from(7));
+/// // x^2 - 1 and x^2 + 2x + 1 have the common root -1
+/// assert_eq!(resultant(&p(&[-1, 0, 1]), &p(&[1, 2, 1])), BigInt::from(0));
+/// ```
+pub fn resultant<R: IntegralDomain>(a: &Polynomial<R>, b: &Polynomial<R>) -> R {
+    let (Some(m), Some(n)) = (a.degree(), b.degree()) else {
+        return R::zero();
+    };
+    let res = resultant_of_prs(&a.clone().subresultant_prs(b.clone()));
+    // res(a, b) = (-1)^(mn) res(b, a), and the sequence starts with the one of higher degree
+    if m < n && m % 2 == 1 && n % 2 == 1 {
+        -res
+    } else {
+        res
+    }
+}
+
+/// The discriminant `(-1)^(n(n - 1) / 2) res(p, p') / lc(p)` of `p` of degree `n ≥ 1`, which
+/// vanishes iff `p` has a repeated root.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::resultant::discriminant;
+/// # use mathstuff_types::Polynomial;
+/// # use num::BigInt;
+/// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+/// // b^2 - 4ac for a x^2 + b x + c
+/// assert_eq!(discriminant(&p(&[3, 5, 2])), BigInt::from(1));
+/// // -4p^3 - 27q^2 for x^3 + p x + q
+/// assert_eq!(discriminant(&p(&[1, -1, 0, 1])), BigInt::from(-23));
+/// ```
+pub fn discriminant<R: IntegralDomain + FromUsize>(p: &Polynomial<R>) -> R {
+    let n = p.degree().unwrap_or(0);
+    let res = resultant(p, &p.clone().derivative())
+        .exact_div(&p.leading_coefficient_cloned())
+        .expect("the leading coefficient divides the resultant");
+    if n % 4 < 2 {
+        res
+    } else {
+        -res
+    }
+}
+
+/// `a` and `b` in the union of their variables and `var`, and the index of `var`.
+fn with_var<R: CommutativeRing>(
+    a: &MultiPolynomial<R>,
+    b: &MultiPolynomial<R>,
+    var: &str,
+) -> (MultiPolynomial<R>, MultiPolynomial<R>, usize) {
+    let (a, b) = a.clone().unify(b.clone());
+    let mut vars = a.vars().to_vec();
+    if !vars.iter().any(|x| x == var) {
+        vars.push(var.into());
+    }
+    let vars = vars.iter().map(String::as_str).collect::<Vec<_>>();
+    let (a, b) = (a.with_vars(&vars), b.with_vars(&vars));
+    let k = a.var_index(var).unwrap();
+    (a, b, k)
+}
+
+/// The resultant of `a` and `b` as polynomials in `var`, which is a polynomial in the other
+/// variables that lies in the ideal generated by `a` and `b`. See [`resultant`] for the
+/// requirements on `R`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::resultant::multivariate_resultant;
+/// # use mathstuff_types::{MonomialOrder, MultiPolynomial};
+/// # use num::BigInt;
+/// let one = MultiPolynomial::constant(BigInt::from(1));
+/// let [t, x, y] = MultiPolynomial::generators(&["t", "x", "y"], MonomialOrder::Lex);
+/// // implicitizing the parabola x = t + 1, y = t^2 gives y = (x - 1)^2
+/// let a = x.clone() - t.clone() - one.clone();
+/// let b = y.clone() - t.clone() * t;
+/// let expected = y - (x.clone() - one.clone()) * (x - one);
+/// assert_eq!(multivariate_resultant(&a, &b, "t"), expected);
+/// ```
+pub fn multivariate_resultant<R>(
+    a: &MultiPolynomial<R>,
+    b: &MultiPolynomial<R>,
+    var: &str,
+) -> MultiPolynomial<R>
+where
+    R: IntegralDomain,
+{
+    let (a, b, k) = with_var(a, b, var);
+    let like = a.clone();
+    let res = resultant(&to_recursive(&a, k), &to_recursive(&b, k));
+    // the constants of the recursive representation have no variables
+    res.unify(like.with_terms(vec![])).0
+}
+
+/// The discriminant of `p` as a polynomial in `var`, see [`discriminant`] and
+/// [`multivariate_resultant`].
+pub fn multivariate_discriminant<R>(p: &MultiPolynomial<R>, var: &str) -> MultiPolynomial<R>
+where
+    R: IntegralDomain + FromUsize,
+{
+    let (p, _, k) = with_var(p, p, var);
+    let n = p.degree_in(k).unwrap_or(0);
+    let res = multivariate_resultant(&p, &p.derivative(k), var);
+    let lc = to_recursive(&p, k).leading_coefficient_cloned();
+    let res = IntegralDomain::exact_div(&res, &lc).expect("the leading coefficient divides");
+    if n % 4 < 2 {
+        res
+    } else {
+        -res
+    }
+}
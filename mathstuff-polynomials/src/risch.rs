@@ -0,0 +1,1075 @@
+//! The transcendental Risch algorithm for towers of `exp` and `log` extensions of `Q(x)`.
+//!
+//! Integrands live in `K_n = Q(x)(θ_1)...(θ_n)`, where every `θ_i` is `log(u)` or `exp(u)` for
+//! some `u ∈ K_{i-1}` and is a monomial over `K_{i-1}`: transcendental, with no new constants.
+//! [`Tower`] checks this with the structure theorem when a monomial is added. The algorithms are
+//! the ones from Bronstein's *Symbolic Integration I* and recurse on the coefficient field
+//! `K_{n-1}`: Hermite reduction with respect to the derivation of `K_n`, the residue criterion
+//! and the Lazard-Rioboo-Trager algorithm for the logarithmic part, limited integration in
+//! `K_{n-1}` for polynomials in a primitive monomial and the Risch differential equation for
+//! Laurent polynomials in a hyperexponential monomial, leaving an element of `K_{n-1}` to
+//! integrate. Each of them either finds the integral or proves that it is not elementary.
+//!
+//! The Risch differential equation is only solved over `Q(x)`, so integrands that need it for a
+//! hyperexponential monomial over `K_i` with `i >= 1`, like `exp(exp(x))`, are not decided and
+//! give [`RischError::Unsupported`].
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{BigInt, BigRational, One, ToPrimitive, Zero};
+
+use mathstuff_types::fraction::{Fraction, RationalFunction};
+use mathstuff_types::matrix::Matrix;
+use mathstuff_types::traits::{
+    CheckedInv, CommutativeRing, Field, FromUsize, GcdDomain, IntegralDomain,
+};
+use mathstuff_types::Polynomial;
+
+use crate::integration::{
+    hermite_reduce, hermite_reduce_with, integrate_rational_function, lazard_rioboo_trager_with,
+    HermiteReduction, LogTerm, RationalIntegral,
+};
+
+type Q = BigRational;
+
+type K = RationalFunction<Q>;
+
+/// An element of a tower `K_n`: a rational function of `x`, or a rational function of `θ_i` with
+/// coefficients in `K_{i-1}` that is not constant in `θ_i`. Every element has a single
+/// representation, at the lowest level it lies in, so that elements can be compared with `==`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Element {
+    Base(RationalFunction<Q>),
+    Extension(usize, RationalFunction<Element>),
+}
+
+impl Element {
+    pub fn constant(c: Q) -> Self {
+        Element::Base(K::constant(c))
+    }
+
+    /// The element `x`.
+    pub fn x() -> Self {
+        Element::Base(K::x())
+    }
+
+    /// The least `i` such that the element lies in `K_i`.
+    pub fn level(&self) -> usize {
+        match self {
+            Element::Base(_) => 0,
+            Element::Extension(i, _) => *i,
+        }
+    }
+
+    /// The value of the element if it is a constant.
+    pub fn to_constant(&self) -> Option<Q> {
+        match self {
+            Element::Base(f) => f.to_constant(),
+            Element::Extension(..) => None,
+        }
+    }
+
+    /// The element as a rational function of `x`.
+    ///
+    /// # Panics
+    ///
+    /// If the element does not lie in `Q(x)`.
+    fn base(&self) -> &K {
+        match self {
+            Element::Base(f) => f,
+            Element::Extension(..) => panic!("not an element of Q(x)"),
+        }
+    }
+
+    /// The element as a rational function of `θ_level`, for `level >= max(self.level(), 1)`.
+    fn lift(&self, level: usize) -> RationalFunction<Element> {
+        match self {
+            Element::Extension(i, f) if *i == level => f.clone(),
+            _ => RationalFunction::constant(self.clone()),
+        }
+    }
+
+    /// The element `f ∈ K_{level-1}(θ_level)`, at the level it lies in.
+    fn from_fraction(level: usize, f: RationalFunction<Element>) -> Self {
+        match f.to_constant() {
+            Some(c) => c,
+            None => Element::Extension(level, f),
+        }
+    }
+
+    /// Applies an operation of the field at the level of the higher operand.
+    fn combine(
+        self,
+        other: Self,
+        base: impl FnOnce(K, K) -> K,
+        extension: impl FnOnce(
+            RationalFunction<Element>,
+            RationalFunction<Element>,
+        ) -> RationalFunction<Element>,
+    ) -> Self {
+        match (self, other) {
+            (Element::Base(a), Element::Base(b)) => Element::Base(base(a, b)),
+            (a, b) => {
+                let level = a.level().max(b.level());
+                Element::from_fraction(level, extension(a.lift(level), b.lift(level)))
+            }
+        }
+    }
+}
+
+impl Add for Element {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.combine(other, |a, b| a + b, |a, b| a + b)
+    }
+}
+
+impl Sub for Element {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.combine(other, |a, b| a - b, |a, b| a - b)
+    }
+}
+
+impl Mul for Element {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.combine(other, |a, b| a * b, |a, b| a * b)
+    }
+}
+
+impl Div for Element {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        self.combine(other, |a, b| a / b, |a, b| a / b)
+    }
+}
+
+impl Neg for Element {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            Element::Base(f) => Element::Base(-f),
+            Element::Extension(i, f) => Element::Extension(i, -f),
+        }
+    }
+}
+
+impl Zero for Element {
+    fn zero() -> Self {
+        Element::Base(K::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        matches!(self, Element::Base(f) if f.is_zero())
+    }
+}
+
+impl One for Element {
+    fn one() -> Self {
+        Element::Base(K::one())
+    }
+}
+
+impl CheckedInv for Element {
+    fn checked_inv(&self) -> Option<Self> {
+        match self {
+            Element::Base(f) => f.checked_inv().map(Element::Base),
+            Element::Extension(i, f) => f.checked_inv().map(|f| Element::Extension(*i, f)),
+        }
+    }
+}
+
+impl FromUsize for Element {
+    fn from_usize(n: usize) -> Self {
+        Element::Base(K::from_usize(n))
+    }
+}
+
+impl CommutativeRing for Element {}
+
+impl Field for Element {}
+
+/// The monomial `θ_i` over `K_{i-1}`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Monomial {
+    /// `θ = log(u)`, given by `θ' = η = u' / u`.
+    Primitive(Element),
+    /// `θ = exp(u)`, given by `θ' / θ = η = u'`.
+    Hyperexponential(Element),
+}
+
+impl Monomial {
+    fn eta(&self) -> &Element {
+        match self {
+            Monomial::Primitive(eta) | Monomial::Hyperexponential(eta) => eta,
+        }
+    }
+}
+
+/// A tower of monomials `Q(x)(θ_1)...(θ_n)` and its derivation, `d/dx` on `Q(x)`.
+#[derive(Clone, Default, Debug)]
+pub struct Tower {
+    monomials: Vec<Monomial>,
+}
+
+impl Tower {
+    /// The field `Q(x)`, without monomials.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number `n` of monomials, so that the elements lie in `K_n`.
+    pub fn height(&self) -> usize {
+        self.monomials.len()
+    }
+
+    fn monomial(&self, level: usize) -> &Monomial {
+        &self.monomials[level - 1]
+    }
+
+    /// Adds the monomial `log(u)` for some `u ∈ K_n`, and returns it as an element of the tower.
+    /// `None` if `log(u)` is not a monomial over `K_n`.
+    pub fn push_log(&mut self, u: &Element) -> Option<Element> {
+        if u.is_zero() {
+            return None;
+        }
+        let eta = self.derive(u) / u.clone();
+        self.push(Monomial::Primitive(eta))
+    }
+
+    /// Adds the monomial `exp(u)` for some `u ∈ K_n`, and returns it as an element of the tower.
+    /// `None` if `exp(u)` is not a monomial over `K_n`.
+    pub fn push_exp(&mut self, u: &Element) -> Option<Element> {
+        let eta = self.derive(u);
+        self.push(Monomial::Hyperexponential(eta))
+    }
+
+    /// By the structure theorem, `log(u)` and `exp(u)` are monomials over `K_n` unless their `η`
+    /// is a linear combination of the `η` of `θ_1, ..., θ_n` with rational coefficients, in which
+    /// case they differ from the same combination of the `θ_i` by a constant.
+    fn push(&mut self, monomial: Monomial) -> Option<Element> {
+        let mut etas: Vec<_> = self.monomials.iter().map(|m| m.eta().clone()).collect();
+        etas.push(monomial.eta().clone());
+        if relations(&etas)
+            .iter()
+            .any(|c| !c[etas.len() - 1].is_zero())
+        {
+            return None;
+        }
+        self.monomials.push(monomial);
+        Some(Element::Extension(self.height(), RationalFunction::x()))
+    }
+
+    /// The derivative of an element of the tower.
+    pub fn derive(&self, f: &Element) -> Element {
+        match f {
+            Element::Base(f) => Element::Base(f.derivative()),
+            Element::Extension(i, f) => {
+                let (n, d) = (f.numerator(), f.denominator());
+                let numerator = self.derive_polynomial(*i, n) * d.clone()
+                    - n.clone() * self.derive_polynomial(*i, d);
+                Element::from_fraction(*i, RationalFunction::new(numerator, d.clone() * d.clone()))
+            }
+        }
+    }
+
+    /// Differentiates a polynomial in `θ_level` with coefficients in `K_{level-1}`.
+    pub fn derive_polynomial(&self, level: usize, p: &Polynomial<Element>) -> Polynomial<Element> {
+        let coeffs: Vec<_> = p.clone().into_iter().collect();
+        let derivative = coeffs.iter().map(|c| self.derive(c));
+        match self.monomial(level) {
+            // (Σ c_i θ^i)' = Σ (c_i' + (i + 1) c_{i+1} θ') θ^i
+            Monomial::Primitive(eta) => Polynomial::new_trim_zeroes(
+                derivative
+                    .enumerate()
+                    .map(|(i, c)| match coeffs.get(i + 1) {
+                        Some(next) => c + Element::from_usize(i + 1) * next.clone() * eta.clone(),
+                        None => c,
+                    })
+                    .collect(),
+            ),
+            // (Σ c_i θ^i)' = Σ (c_i' + i c_i η) θ^i
+            Monomial::Hyperexponential(eta) => Polynomial::new_trim_zeroes(
+                derivative
+                    .zip(&coeffs)
+                    .enumerate()
+                    .map(|(i, (dc, c))| dc + Element::from_usize(i) * c.clone() * eta.clone())
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Why [`risch_integrate`] found no antiderivative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RischError {
+    /// The integral was proven not to be elementary.
+    NonElementary,
+    /// The integral needs the Risch differential equation over `K_i` with `i >= 1`, which is not
+    /// implemented.
+    Unsupported,
+}
+
+/// An antiderivative of an element of `K_n`, `Σ rational + Σ logs + base`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscendentalIntegral {
+    /// Rational functions of the monomials, one for each level the integration went through,
+    /// from the highest.
+    pub rational: Vec<Element>,
+    /// Logarithms of polynomials in `θ_i` for the level `i` of each term. The polynomials
+    /// `roots_of` have constant coefficients, and every `argument` is monic in `θ_i` for each
+    /// root.
+    pub logs: Vec<(usize, LogTerm<Element>)>,
+    /// The integral of the part of the integrand that is left in `Q(x)`.
+    pub base: RationalIntegral<Q>,
+}
+
+/// The integral of an element of `K_{i-1}(θ_i)`, up to the integral of `rest ∈ K_{i-1}`.
+struct PartialIntegral {
+    rational: Element,
+    logs: Vec<LogTerm<Element>>,
+    rest: Element,
+}
+
+/// The coefficient of `t^i` in `p`.
+fn coefficient<R: CommutativeRing>(p: &Polynomial<R>, i: usize) -> R {
+    p.clone().into_iter().nth(i).unwrap_or_else(R::zero)
+}
+
+/// The coefficients of the numerators of `fs` over a common denominator, one row for each power
+/// of the variable.
+fn common_numerators<F: Field + PartialEq>(fs: &[RationalFunction<F>]) -> Vec<Vec<F>> {
+    let d = fs.iter().fold(Polynomial::one(), |acc, f| {
+        GcdDomain::lcm(&acc, f.denominator())
+    });
+    let numerators: Vec<_> = fs
+        .iter()
+        .map(|f| {
+            let cofactor = IntegralDomain::exact_div(&d, f.denominator())
+                .expect("the denominator divides the lcm");
+            f.numerator().clone() * cofactor
+        })
+        .collect();
+    let rows = numerators
+        .iter()
+        .filter_map(Polynomial::degree)
+        .max()
+        .map_or(0, |n| n + 1);
+    (0..rows)
+        .map(|i| numerators.iter().map(|p| coefficient(p, i)).collect())
+        .collect()
+}
+
+/// Linear equations for the constants `c` with `Σ c_l e_l = 0`. The monomials are
+/// transcendental, so the coefficients of every power of `θ_i` have to vanish.
+fn linear_equations(elements: &[Element]) -> Vec<Vec<Q>> {
+    let level = elements.iter().map(Element::level).max().unwrap_or(0);
+    if level == 0 {
+        let fs: Vec<_> = elements.iter().map(|e| e.base().clone()).collect();
+        return common_numerators(&fs);
+    }
+    let fs: Vec<_> = elements.iter().map(|e| e.lift(level)).collect();
+    common_numerators(&fs)
+        .into_iter()
+        .flat_map(|row| linear_equations(&row))
+        .collect()
+}
+
+/// A basis of the vectors `c ∈ Q^k` with `Σ c_l e_l = 0`, for elements `e_1, ..., e_k`.
+fn relations(elements: &[Element]) -> Vec<Vec<Q>> {
+    let rows = linear_equations(elements);
+    Matrix::from_fn(rows.len(), elements.len(), |i, j| rows[i][j].clone()).nullspace()
+}
+
+/// `Σ c_l e_l`.
+fn linear_combination(c: &[Q], elements: &[Element]) -> Element {
+    c.iter()
+        .zip(elements)
+        .filter(|(c, _)| !c.is_zero())
+        .fold(Element::zero(), |acc, (c, e)| {
+            acc + Element::constant(c.clone()) * e.clone()
+        })
+}
+
+/// Splits `q` so that `s` can be made monic modulo each factor, where `s` is a polynomial in `θ`
+/// with coefficients in `K[t]` and `q` is a polynomial in `t`.
+fn make_monic(q: Polynomial<Element>, s: Polynomial<Polynomial<Element>>) -> Vec<LogTerm<Element>> {
+    let s = Polynomial::new_trim_zeroes(s.into_iter().map(|c| c.div_rem(q.clone()).1).collect());
+    let lc = s.leading_coefficient_cloned();
+    let (g, inv, _) = lc.gcd_ext(q.clone());
+
+    if g.is_one() {
+        let argument = s
+            .into_iter()
+            .map(|c| (c * inv.clone()).div_rem(q.clone()).1)
+            .collect();
+        return vec![LogTerm {
+            roots_of: q,
+            argument,
+        }];
+    }
+
+    // lc(s) vanishes at the roots of g, so s has a lower degree there
+    let q = IntegralDomain::exact_div(&q, &g).expect("g divides q");
+    let mut rest = make_monic(q, s.clone());
+    let mut lower: Vec<_> = s.into_iter().collect();
+    lower.pop();
+    rest.extend(make_monic(g, Polynomial::new(lower)));
+    rest
+}
+
+/// The logarithmic part of `∫ a / d` where `a / d ∈ K_{level-1}(θ_level)`, `deg a < deg d` and
+/// `d` is square-free and normal.
+fn residue_reduce(
+    a: Polynomial<Element>,
+    d: Polynomial<Element>,
+    tower: &Tower,
+    level: usize,
+) -> Result<Vec<LogTerm<Element>>, RischError> {
+    let (a, d) = Fraction::new(a, d).into_parts();
+    let dd = tower.derive_polynomial(level, &d);
+
+    let mut logs = vec![];
+    for LogTerm { roots_of, argument } in lazard_rioboo_trager_with(a, d, dd) {
+        // residue criterion: the integral is elementary iff all the residues are constant
+        let roots_of = roots_of.normalize();
+        if roots_of
+            .clone()
+            .into_iter()
+            .any(|c| c.to_constant().is_none())
+        {
+            return Err(RischError::NonElementary);
+        }
+        logs.extend(make_monic(roots_of, argument));
+    }
+    Ok(logs)
+}
+
+/// `∫ p` for a polynomial `p ∈ Q[x]`.
+fn integrate_polynomial(p: Polynomial<Q>) -> Polynomial<Q> {
+    let mut coeffs = vec![Q::zero()];
+    coeffs.extend(
+        p.into_iter()
+            .enumerate()
+            .map(|(i, c)| c / Q::from_usize(i + 1)),
+    );
+    Polynomial::new_trim_zeroes(coeffs)
+}
+
+/// Constants `c` and an element `b` of the tower that solve a parametric problem.
+type Solution = (Vec<Q>, Element);
+
+/// Constants `c` and the coefficients `y` of a solution that is being built up.
+type PartialSolution = (Vec<Q>, Vec<Element>);
+
+/// The linear combination `Σ λ_v v` of the vectors `v`, of which there is at least one.
+fn combine(vectors: &[PartialSolution], lambda: &[Q]) -> PartialSolution {
+    let (c, y) = &vectors[0];
+    let mut c = vec![Q::zero(); c.len()];
+    let mut y = vec![Element::zero(); y.len()];
+    for ((cv, yv), l) in vectors.iter().zip(lambda).filter(|(_, l)| !l.is_zero()) {
+        for (a, b) in c.iter_mut().zip(cv) {
+            *a += l * b;
+        }
+        for (a, b) in y.iter_mut().zip(yv) {
+            let old = std::mem::replace(a, Element::zero());
+            *a = old + Element::constant(l.clone()) * b.clone();
+        }
+    }
+    (c, y)
+}
+
+/// Drops the solutions whose constants are a linear combination of the constants of the previous
+/// ones. They only differ from the same combination of the previous solutions by a constant.
+fn independent(solutions: Vec<Solution>) -> Vec<Solution> {
+    let mut basis: Vec<Solution> = vec![];
+    for solution in solutions {
+        let rows = basis
+            .iter()
+            .map(|(c, _)| c.clone())
+            .chain([solution.0.clone()])
+            .collect();
+        if Matrix::from_rows(rows).rank() > basis.len() {
+            basis.push(solution);
+        }
+    }
+    basis
+}
+
+/// The solution with `c_0 = 1` from a basis, as the other constants and the element.
+fn particular(solutions: Vec<Solution>) -> Option<(Vec<Q>, Element)> {
+    let (c, b) = solutions.into_iter().find(|(c, _)| !c[0].is_zero())?;
+    let inv = c[0].recip();
+    let rest = c[1..].iter().map(|c| c * &inv).collect();
+    Some((rest, Element::constant(inv) * b))
+}
+
+/// Parametric integration in `K_level`: a basis of the solutions `(c, b)` with `c ∈ Q^k` and
+/// `b ∈ K_level` of `b' = Σ c_l f_l`. The other solutions are linear combinations of the basis,
+/// up to a constant added to `b`.
+fn parametric_integrate(
+    fs: &[Element],
+    tower: &Tower,
+    level: usize,
+) -> Result<Vec<Solution>, RischError> {
+    if level == 0 {
+        return Ok(parametric_integrate_base(fs));
+    }
+
+    let fractions = fs.iter().map(|f| f.lift(level).into_parts()).collect();
+    match tower.monomial(level) {
+        Monomial::Primitive(eta) => parametric_integrate_primitive(fractions, eta, tower, level),
+        Monomial::Hyperexponential(eta) => {
+            parametric_integrate_hyperexponential(fractions, eta, tower, level)
+        }
+    }
+}
+
+fn parametric_integrate_base(fs: &[Element]) -> Vec<Solution> {
+    let mut rational = vec![];
+    let mut simple = vec![];
+    for f in fs {
+        let f = f.base();
+        let (p, r) = f.numerator().clone().div_rem(f.denominator().clone());
+        let HermiteReduction {
+            g_num,
+            g_den,
+            h_num,
+            h_den,
+        } = hermite_reduce(r, f.denominator().clone());
+        rational.push(Element::Base(
+            K::new(g_num, g_den) + K::from(integrate_polynomial(p)),
+        ));
+        simple.push(Element::Base(K::new(h_num, h_den)));
+    }
+
+    // the derivatives in Q(x) are the rational functions whose simple part vanishes
+    relations(&simple)
+        .into_iter()
+        .map(|c| {
+            let b = linear_combination(&c, &rational);
+            (c, b)
+        })
+        .collect()
+}
+
+fn parametric_integrate_primitive(
+    fractions: Vec<(Polynomial<Element>, Polynomial<Element>)>,
+    eta: &Element,
+    tower: &Tower,
+    level: usize,
+) -> Result<Vec<Solution>, RischError> {
+    let k = fractions.len();
+    let mut polynomials = vec![];
+    let mut rational = vec![];
+    let mut simple = vec![];
+    for (a, d) in fractions {
+        let (p, r) = a.div_rem(d.clone());
+        let HermiteReduction {
+            g_num,
+            g_den,
+            h_num,
+            h_den,
+        } = hermite_reduce_with(r, d, |x| tower.derive_polynomial(level, x));
+        polynomials.push(p);
+        rational.push(Element::from_fraction(
+            level,
+            RationalFunction::new(g_num, g_den),
+        ));
+        simple.push(Element::from_fraction(
+            level,
+            RationalFunction::new(h_num, h_den),
+        ));
+    }
+
+    // the polynomial part q of b has a degree of at most m + 1, where m is the degree of the
+    // polynomial part of Σ c_l f_l, with a constant leading coefficient
+    let top = polynomials
+        .iter()
+        .filter_map(Polynomial::degree)
+        .max()
+        .map_or(0, |m| m + 1);
+    let constant = |i: usize| {
+        let mut q = vec![Element::zero(); top + 1];
+        q[i] = Element::one();
+        (vec![Q::zero(); k], q)
+    };
+
+    let mut basis: Vec<PartialSolution> = relations(&simple)
+        .into_iter()
+        .map(|c| (c, vec![Element::zero(); top + 1]))
+        .collect();
+    basis.push(constant(top));
+
+    // the coefficients of q' = Σ c_l p_l from the highest: q_i' = P_i - (i + 1) q_{i+1} η
+    for i in (0..top).rev() {
+        let family: Vec<_> = basis
+            .iter()
+            .map(|(c, q)| {
+                let p: Vec<_> = polynomials.iter().map(|p| coefficient(p, i)).collect();
+                linear_combination(c, &p)
+                    - Element::from_usize(i + 1) * q[i + 1].clone() * eta.clone()
+            })
+            .collect();
+        let mut next: Vec<_> = parametric_integrate(&family, tower, level - 1)?
+            .into_iter()
+            .map(|(lambda, b)| {
+                let (c, mut q) = combine(&basis, &lambda);
+                q[i] = b;
+                (c, q)
+            })
+            .collect();
+        next.push(constant(i));
+        basis = next;
+    }
+
+    Ok(independent(
+        basis
+            .into_iter()
+            .map(|(c, q)| {
+                let q = Element::from_fraction(level, Polynomial::new_trim_zeroes(q).into());
+                let b = linear_combination(&c, &rational) + q;
+                (c, b)
+            })
+            .collect(),
+    ))
+}
+
+fn parametric_integrate_hyperexponential(
+    fractions: Vec<(Polynomial<Element>, Polynomial<Element>)>,
+    eta: &Element,
+    tower: &Tower,
+    level: usize,
+) -> Result<Vec<Solution>, RischError> {
+    let mut laurents = vec![];
+    let mut rational = vec![];
+    let mut simple = vec![];
+    for (a, d) in fractions {
+        let (laurent, reduction) = split_hyperexponential(a, d, tower, level);
+        let HermiteReduction {
+            g_num,
+            g_den,
+            h_num,
+            h_den,
+        } = reduction;
+        laurents.push(laurent);
+        rational.push(Element::from_fraction(
+            level,
+            RationalFunction::new(g_num, g_den),
+        ));
+        simple.push(Element::from_fraction(
+            level,
+            RationalFunction::new(h_num, h_den),
+        ));
+    }
+
+    // the Laurent polynomial part Σ y_i θ^i of b has the powers of the integrand
+    let low = -laurents.iter().map(|l| l.shift as i64).max().unwrap_or(0);
+    let high = laurents
+        .iter()
+        .map(|l| l.coeffs.len() as i64 - l.shift as i64)
+        .max()
+        .unwrap_or(0);
+    let powers = usize::try_from(high - low).unwrap_or(0);
+
+    let mut basis: Vec<PartialSolution> = relations(&simple)
+        .into_iter()
+        .map(|c| (c, vec![Element::zero(); powers]))
+        .collect();
+
+    // y_i' + i η y_i = Σ c_l P_i for each power
+    for i in low..high {
+        let family: Vec<_> = basis
+            .iter()
+            .map(|(c, _)| {
+                let p: Vec<_> = laurents.iter().map(|l| l.coeff(i)).collect();
+                linear_combination(c, &p)
+            })
+            .collect();
+        let solutions = if i == 0 {
+            parametric_integrate(&family, tower, level - 1)?
+        } else {
+            let g = Element::constant(BigInt::from(i).into()) * eta.clone();
+            parametric_risch_de(&g, &family, level - 1)?
+        };
+        basis = solutions
+            .into_iter()
+            .map(|(lambda, b)| {
+                let (c, mut y) = combine(&basis, &lambda);
+                y[(i - low) as usize] = b;
+                (c, y)
+            })
+            .collect();
+    }
+
+    // Σ y_i θ^i = (Σ y_i θ^(i - low)) / θ^(-low)
+    let theta_shift =
+        Polynomial::from_elem_with_degree(Element::one(), low.unsigned_abs() as usize);
+    Ok(independent(
+        basis
+            .into_iter()
+            .map(|(c, y)| {
+                let y = RationalFunction::new(Polynomial::new_trim_zeroes(y), theta_shift.clone());
+                let b = linear_combination(&c, &rational) + Element::from_fraction(level, y);
+                (c, b)
+            })
+            .collect(),
+    ))
+}
+
+/// Limited integration in `K_level`: finds `b ∈ K_level` and a constant `c` with
+/// `a = b' + c η`. `None` if there are none.
+fn limited_integrate(
+    a: &Element,
+    eta: &Element,
+    tower: &Tower,
+    level: usize,
+) -> Result<Option<(Element, Q)>, RischError> {
+    let solutions = parametric_integrate(&[a.clone(), -eta.clone()], tower, level)?;
+    Ok(particular(solutions).map(|(c, b)| (b, c[0].clone())))
+}
+
+/// Integrates a polynomial `p` in a primitive monomial `θ_level` with `θ' = η`. Returns `q` such
+/// that `p - q'` lies in `K_{level-1}`, together with `p - q'`.
+fn integrate_primitive_polynomial(
+    mut p: Polynomial<Element>,
+    eta: &Element,
+    tower: &Tower,
+    level: usize,
+) -> Result<(Polynomial<Element>, Element), RischError> {
+    let mut q = Polynomial::zero();
+
+    while let Some(m) = p.degree().filter(|&m| m > 0) {
+        let (b, c) = limited_integrate(&p.leading_coefficient_cloned(), eta, tower, level - 1)?
+            .ok_or(RischError::NonElementary)?;
+        let c = Element::constant(c / Q::from_usize(m + 1));
+        let q0 =
+            Polynomial::from_elem_with_degree(c, m + 1) + Polynomial::from_elem_with_degree(b, m);
+        p = p - tower.derive_polynomial(level, &q0);
+        q += q0;
+    }
+
+    let rest = p.into_iter().next().unwrap_or_else(Element::zero);
+    Ok((q, rest))
+}
+
+/// A basis of the solutions `(c, q)` of `a q' + b q = Σ c_l cs_l`, where `a`, `b` and `cs_l` are
+/// polynomials.
+fn parametric_polynomial_rde(
+    a: &Polynomial<Q>,
+    b: &Polynomial<Q>,
+    cs: &[Polynomial<Q>],
+) -> Vec<(Vec<Q>, Polynomial<Q>)> {
+    let da = a.degree().expect("a is nonzero") as i64;
+    let n = cs
+        .iter()
+        .filter_map(Polynomial::degree)
+        .max()
+        .and_then(|dc| {
+            let dc = dc as i64;
+            let n = match b.degree().map(|db| db as i64) {
+                None => dc - da + 1,
+                Some(db) if db != da - 1 => dc - db.max(da - 1),
+                Some(db) => {
+                    // the degree of the solution can also be -lc(b) / lc(a) if that is a positive
+                    // integer
+                    let m = -b.leading_coefficient_cloned() / a.leading_coefficient_cloned();
+                    let m = m.is_integer().then(|| m.to_integer().to_i64()).flatten();
+                    (dc - db).max(m.unwrap_or(0))
+                }
+            };
+            usize::try_from(n).ok()
+        });
+    let unknowns = n.map_or(0, |n| n + 1);
+
+    // the coefficients of a q' + b q for q = x^j and of -cs_l form the columns of the system
+    let columns: Vec<_> = (0..unknowns)
+        .map(|j| {
+            let xj = Polynomial::from_elem_with_degree(Q::one(), j);
+            a.clone() * xj.clone().derivative() + b.clone() * xj
+        })
+        .chain(cs.iter().map(|c| -c.clone()))
+        .collect();
+    let rows = columns
+        .iter()
+        .filter_map(Polynomial::degree)
+        .max()
+        .map_or(0, |d| d + 1);
+
+    let m = Matrix::from_fn(rows, columns.len(), |i, j| coefficient(&columns[j], i));
+    m.nullspace()
+        .into_iter()
+        .map(|v| {
+            let (q, c) = v.split_at(unknowns);
+            (c.to_vec(), Polynomial::new_trim_zeroes(q.to_vec()))
+        })
+        .collect()
+}
+
+/// The parametric Risch differential equation in `Q(x)`: a basis of the solutions `(c, y)` of
+/// `y' + g y = Σ c_l f_l`, where `g` is the derivative of an element of `Q(x)`, so that it has
+/// no simple poles.
+fn parametric_risch_de_base(g: &K, fs: &[K]) -> Vec<(Vec<Q>, K)> {
+    // the denominator of y divides gcd(d, d') / gcd(p, p') where d is the common denominator of
+    // the f_l and p = gcd(d, e) for the denominator e of g
+    let dn = fs.iter().fold(Polynomial::one(), |acc, f| {
+        GcdDomain::lcm(&acc, f.denominator())
+    });
+    let en = g.denominator().clone();
+    let p = dn.clone().gcd(en.clone());
+    let h = IntegralDomain::exact_div(
+        &dn.clone().gcd(dn.clone().derivative()),
+        &p.clone().gcd(p.derivative()),
+    )
+    .expect("gcd(p, p') divides gcd(d, d')");
+
+    // with y = q / h, h q' + (g h - h') q = f h^2. Clearing denominators gives polynomials.
+    let l = K::from(GcdDomain::lcm(&en, &dn));
+    let hk = K::from(h.clone());
+    let a = l.clone() * hk.clone();
+    let b = l.clone() * (g.clone() * hk.clone() - K::from(h.clone().derivative()));
+    let cs: Vec<_> = fs
+        .iter()
+        .map(|f| l.clone() * f.clone() * hk.clone() * hk.clone())
+        .collect();
+    debug_assert!([&a, &b]
+        .into_iter()
+        .chain(&cs)
+        .all(|x| x.denominator().is_one()));
+
+    let cs: Vec<_> = cs.iter().map(|c| c.numerator().clone()).collect();
+    parametric_polynomial_rde(a.numerator(), b.numerator(), &cs)
+        .into_iter()
+        .map(|(c, q)| (c, K::new(q, h.clone())))
+        .collect()
+}
+
+/// The parametric Risch differential equation `y' + g y = Σ c_l f_l` in `K_level`, with the
+/// solutions of [`parametric_risch_de_base`].
+fn parametric_risch_de(
+    g: &Element,
+    fs: &[Element],
+    level: usize,
+) -> Result<Vec<Solution>, RischError> {
+    // y' + g y = 0 only has the solution y = 0, as g is the logarithmic derivative of a monomial
+    if fs.iter().all(Zero::is_zero) {
+        return Ok((0..fs.len())
+            .map(|l| {
+                let mut c = vec![Q::zero(); fs.len()];
+                c[l] = Q::one();
+                (c, Element::zero())
+            })
+            .collect());
+    }
+    if level > 0 {
+        return Err(RischError::Unsupported);
+    }
+
+    let fs: Vec<_> = fs.iter().map(|f| f.base().clone()).collect();
+    Ok(parametric_risch_de_base(g.base(), &fs)
+        .into_iter()
+        .map(|(c, y)| (c, Element::Base(y)))
+        .collect())
+}
+
+/// Integrates `f ∈ K_n`, or proves that the integral is not elementary.
+pub fn risch_integrate(f: &Element, tower: &Tower) -> Result<TranscendentalIntegral, RischError> {
+    let level = f.level();
+    if level == 0 {
+        let (numerator, denominator) = f.base().clone().into_parts();
+        return Ok(TranscendentalIntegral {
+            rational: vec![],
+            logs: vec![],
+            base: integrate_rational_function(numerator, denominator),
+        });
+    }
+
+    let (a, d) = f.lift(level).into_parts();
+    let PartialIntegral {
+        rational,
+        logs,
+        rest,
+    } = match tower.monomial(level) {
+        Monomial::Primitive(eta) => integrate_primitive(a, d, eta, tower, level)?,
+        Monomial::Hyperexponential(eta) => integrate_hyperexponential(a, d, eta, tower, level)?,
+    };
+
+    let mut integral = risch_integrate(&rest, tower)?;
+    integral.rational.insert(0, rational);
+    integral
+        .logs
+        .splice(0..0, logs.into_iter().map(|log| (level, log)));
+    Ok(integral)
+}
+
+fn integrate_primitive(
+    a: Polynomial<Element>,
+    d: Polynomial<Element>,
+    eta: &Element,
+    tower: &Tower,
+    level: usize,
+) -> Result<PartialIntegral, RischError> {
+    let (p, r) = a.div_rem(d.clone());
+    let HermiteReduction {
+        g_num,
+        g_den,
+        h_num,
+        h_den,
+    } = hermite_reduce_with(r, d, |x| tower.derive_polynomial(level, x));
+
+    let logs = residue_reduce(h_num, h_den, tower, level)?;
+    let (q, rest) = integrate_primitive_polynomial(p, eta, tower, level)?;
+
+    let rational = RationalFunction::new(g_num + q * g_den.clone(), g_den);
+    Ok(PartialIntegral {
+        rational: Element::from_fraction(level, rational),
+        logs,
+        rest,
+    })
+}
+
+/// A Laurent polynomial `Σ c_i θ^i`, for `i` from `-shift`.
+struct Laurent {
+    coeffs: Vec<Element>,
+    shift: usize,
+}
+
+impl Laurent {
+    fn add(&mut self, i: i64, c: Element) {
+        let index = usize::try_from(i + self.shift as i64).expect("the power is in range");
+        if index >= self.coeffs.len() {
+            self.coeffs.resize_with(index + 1, Element::zero);
+        }
+        let old = std::mem::replace(&mut self.coeffs[index], Element::zero());
+        self.coeffs[index] = old + c;
+    }
+
+    /// The coefficient of `θ^i`.
+    fn coeff(&self, i: i64) -> Element {
+        usize::try_from(i + self.shift as i64)
+            .ok()
+            .and_then(|index| self.coeffs.get(index).cloned())
+            .unwrap_or_else(Element::zero)
+    }
+}
+
+/// Splits `a / d ∈ K_{level-1}(θ_level)` for a hyperexponential `θ_level` into a Laurent
+/// polynomial in `θ_level` and the Hermite reduction `(g_num / g_den)' + h_num / h_den` of the
+/// rest, where `h_num / h_den` is proper and the denominators are coprime to `θ_level`.
+fn split_hyperexponential(
+    a: Polynomial<Element>,
+    d: Polynomial<Element>,
+    tower: &Tower,
+    level: usize,
+) -> (Laurent, HermiteReduction<Element>) {
+    // d = θ^m d_n with d_n normal
+    let coeffs: Vec<_> = d.into_iter().collect();
+    let m = coeffs.iter().take_while(|c| c.is_zero()).count();
+    let dn = Polynomial::new(coeffs[m..].to_vec());
+    let theta_m = Polynomial::from_elem_with_degree(Element::one(), m);
+
+    // a / d = p + s / θ^m + t / d_n
+    let (p, r) = a.div_rem(theta_m.clone() * dn.clone());
+    let (s, t) = if m == 0 {
+        (Polynomial::zero(), r)
+    } else {
+        let (g, u, v) = dn.clone().gcd_ext(theta_m.clone());
+        debug_assert!(g.is_one());
+        let (q, s) = (u * r.clone()).div_rem(theta_m);
+        (s, v * r + q * dn.clone())
+    };
+
+    let mut laurent = Laurent {
+        coeffs: vec![],
+        shift: m,
+    };
+    for (i, c) in p.into_iter().enumerate() {
+        laurent.add(i as i64, c);
+    }
+    for (i, c) in s.into_iter().enumerate() {
+        laurent.add(i as i64 - m as i64, c);
+    }
+
+    let HermiteReduction {
+        g_num,
+        g_den,
+        h_num,
+        h_den,
+    } = hermite_reduce_with(t, dn, |x| tower.derive_polynomial(level, x));
+    let (p, h_num) = h_num.div_rem(h_den.clone());
+    for (i, c) in p.into_iter().enumerate() {
+        laurent.add(i as i64, c);
+    }
+
+    let reduction = HermiteReduction {
+        g_num,
+        g_den,
+        h_num,
+        h_den,
+    };
+    (laurent, reduction)
+}
+
+fn integrate_hyperexponential(
+    a: Polynomial<Element>,
+    d: Polynomial<Element>,
+    eta: &Element,
+    tower: &Tower,
+    level: usize,
+) -> Result<PartialIntegral, RischError> {
+    let (
+        mut laurent,
+        HermiteReduction {
+            g_num,
+            g_den,
+            h_num,
+            h_den,
+        },
+    ) = split_hyperexponential(a, d, tower, level);
+
+    let logs = residue_reduce(h_num, h_den, tower, level)?;
+    // (log s)' = deg(s) η + a proper fraction for monic s, so the derivative of Σ α log s over
+    // all the roots α has an extra η Σ α deg(s) in K_{level-1}, which is left to integrate.
+    for log in &logs {
+        let n = log.roots_of.degree().unwrap_or(0);
+        let trace = -log.roots_of.coeff_at(n - 1).clone();
+        let degree = Element::from_usize(log.argument.degree().unwrap_or(0));
+        laurent.add(0, -(trace * degree * eta.clone()));
+    }
+
+    let Laurent { coeffs, shift } = laurent;
+    let mut rest = Element::zero();
+    let mut y = vec![];
+    for (index, c) in coeffs.into_iter().enumerate() {
+        let i = index as i64 - shift as i64;
+        if i == 0 {
+            rest = c;
+            y.push(Element::zero());
+        } else {
+            let g = Element::constant(BigInt::from(i).into()) * eta.clone();
+            let (_, b) = particular(parametric_risch_de(&g, &[c], level - 1)?)
+                .ok_or(RischError::NonElementary)?;
+            y.push(b);
+        }
+    }
+
+    // Σ y_i θ^i = (Σ y_i θ^(i + shift)) / θ^shift
+    let theta_shift = Polynomial::from_elem_with_degree(Element::one(), shift);
+    let rational = Fraction::new(
+        g_num * theta_shift.clone() + Polynomial::new_trim_zeroes(y) * g_den.clone(),
+        g_den * theta_shift,
+    );
+
+    Ok(PartialIntegral {
+        rational: Element::from_fraction(level, rational),
+        logs,
+        rest,
+    })
+}
@@ -0,0 +1,85 @@
+//! Numeric approximations of the complex roots of polynomials.
+
+use std::cmp::Ordering;
+
+use num::complex::Complex64;
+use num::{BigRational, ToPrimitive, Zero};
+
+use mathstuff_types::Polynomial;
+
+fn eval(coeffs: &[f64], z: Complex64) -> Complex64 {
+    coeffs
+        .iter()
+        .rev()
+        .fold(Complex64::zero(), |acc, c| acc * z + c)
+}
+
+/// Sorts roots with the real ones first by increasing value, and then the others by increasing
+/// real and imaginary part.
+pub fn sort_roots(roots: &mut [Complex64]) {
+    let key = |z: &Complex64| (z.im != 0.0, z.re, z.im);
+    roots.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal));
+}
+
+/// Approximates the complex roots of the square-free polynomial `p` with the Durand-Kerner
+/// method, sorted with [`sort_roots`]. Roots whose imaginary part vanishes to working precision
+/// are made real.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_polynomials::roots::complex_roots;
+/// # use mathstuff_types::Polynomial;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// // x^3 - x = x (x - 1) (x + 1)
+/// let roots = complex_roots(&Polynomial::new(vec![n(0), n(-1), n(0), n(1)]));
+/// let re = roots.iter().map(|z| z.re).collect::<Vec<_>>();
+/// assert!(roots.iter().all(|z| z.im == 0.0));
+/// assert!((re[0] + 1.0).abs() < 1e-12 && re[1].abs() < 1e-12 && (re[2] - 1.0).abs() < 1e-12);
+/// ```
+pub fn complex_roots(p: &Polynomial<BigRational>) -> Vec<Complex64> {
+    let Some(n) = p.degree().filter(|n| *n > 0) else {
+        return vec![];
+    };
+    let lc = p.leading_coefficient_cloned();
+    let coeffs = p
+        .clone()
+        .into_iter()
+        .map(|c| (c / &lc).to_f64().unwrap())
+        .collect::<Vec<_>>();
+
+    // all the roots lie in the disk of radius 1 + max |c_i| (Cauchy's bound)
+    let radius = 1.0 + coeffs[..n].iter().fold(0.0_f64, |acc, c| acc.max(c.abs()));
+    let seed = Complex64::new(0.4, 0.9);
+    let mut roots = (0..n)
+        .map(|i| seed.powu(i as u32 + 1) * radius / seed.norm().powi(i as i32 + 1))
+        .collect::<Vec<_>>();
+
+    for _ in 0..1000 {
+        let mut change = 0.0_f64;
+        for i in 0..n {
+            let z = roots[i];
+            let denom = (0..n)
+                .filter(|&j| j != i)
+                .fold(Complex64::new(1.0, 0.0), |acc, j| acc * (z - roots[j]));
+            let step = eval(&coeffs, z) / denom;
+            if step.is_finite() {
+                roots[i] = z - step;
+                change = change.max(step.norm() / z.norm().max(1.0));
+            }
+        }
+        if change < 1e-15 {
+            break;
+        }
+    }
+
+    for z in &mut roots {
+        // the real roots of real polynomials have conjugates that converge to the same point
+        if z.im.abs() <= 1e-12 * z.norm().max(1.0) {
+            z.im = 0.0;
+        }
+    }
+    sort_roots(&mut roots);
+    roots
+}
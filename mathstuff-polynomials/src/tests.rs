@@ -1,6 +1,24 @@
-use mathstuff_types::{Polynomial, print::DisplayWithVar};
+use mathstuff_types::arith::{mul_karatsuba, mul_ntt, mul_schoolbook};
+use mathstuff_types::finite_field::Fp;
+use mathstuff_types::fraction::RationalFunction;
+use mathstuff_types::groebner::GroebnerBasis;
+use mathstuff_types::{
+    DivisionError, MonomialOrder, MultiPolynomial, Polynomial, poly, print::DisplayWithVar,
+};
+use num::{BigInt, One, Zero};
 
-use crate::factorization::SquareFreeFactorization;
+use crate::factorization::{Kronecker, SquareFreeFactorization};
+use crate::multivariate_gcd::{modular_gcd, rational_gcd, subresultant_gcd};
+use crate::integration::{
+    hermite_reduce, integrate_rational_function, log_to_atan, log_to_atan_sqrt, HermiteReduction,
+    LogTerm,
+};
+use crate::partial_fractions::{apart, apart_square_free, PartialFractions};
+use crate::resultant::{
+    discriminant, multivariate_discriminant, multivariate_resultant, resultant, sylvester_matrix,
+    sylvester_resultant,
+};
+use crate::risch::{risch_integrate, Element, RischError, Tower};
 
 macro_rules! v {
     ($($elem:expr),*) => {
@@ -10,23 +28,6 @@ macro_rules! v {
     };
 }
 
-/* macro_rules! p {
-    (@handle_rest[$v:ident]($($lit:literal)? x $($tt:tt)*)) => {
-        v[1] += num::BigRational::from_integer(num::BigInt::from(1 $(- 1 + $lit)?));
-        p!(@handle_rest[$v]( $($tt)* ));
-    };
-    (@handle_rest[$v:ident]()) => {{}};
-    ($($lit:literal)? x^$exp:literal)$( $(+)? $($lits:literal)? x^$exps:literal )* $($tt:tt)*) => {{
-        let mut v = vec![ <num::BigRational as num::traits::Zero>::zero(); [$exp $(, $exps)*].into_iter().max().unwrap() + 1];
-        v[1 $(- 1 + $exp)?] = num::BigRational::from_integer(num::BigInt::from(1 $(- 1 + $lit)?));
-        $(
-            v[$exps] += num::BigRational::from_integer(num::BigInt::from(1 $(- 1 + $lits)?));
-        )*
-        p!(@handle_rest[v]($($tt)*));
-        Polynomial::new(v)
-    }};
-} */
-
 #[test]
 pub fn test_div() {
     // 3x^4 - 5x^2 + 3 / x + 2
@@ -42,6 +43,49 @@ pub fn test_div() {
     let (q, r) = a.div_rem(b);
     assert_eq!(q, Polynomial::new(vec![60, 9, 1]));
     assert_eq!(r, Polynomial::new(vec![424]));
+
+    // 2x^2 + 6x + 4 / 2, the remainder becomes zero with a constant divisor
+    let a = Polynomial::new(vec![4, 6, 2]);
+    let b = Polynomial::new(vec![2]);
+    let (q, r) = a.div_rem(b);
+    assert_eq!(q, Polynomial::new(vec![2, 3, 1]));
+    assert_eq!(r.degree(), None);
+}
+
+#[test]
+pub fn test_checked_div() {
+    // 6x^3 + 7x^2 - 1 = (2x + 1)(3x^2 + 2x - 1) over the integers
+    let a = Polynomial::new(vec![-1, 0, 7, 6]);
+    let b = Polynomial::new(vec![1, 2]);
+    let q = Polynomial::new(vec![-1, 2, 3]);
+    assert_eq!(
+        a.clone().checked_div_rem(b.clone()),
+        Ok((q.clone(), Polynomial::zero()))
+    );
+    assert_eq!(a.clone().exact_div(b.clone()), Some(q));
+    assert_eq!(a.clone().exact_div(Polynomial::new(vec![-1, 1])), None);
+    assert_eq!(a.clone().exact_div(Polynomial::zero()), None);
+    assert_eq!(
+        (a.clone() + Polynomial::one()).checked_div_rem(b.clone()),
+        Ok((Polynomial::new(vec![-1, 2, 3]), Polynomial::one()))
+    );
+    assert_eq!(
+        a.clone().checked_div_rem(Polynomial::new(vec![1, 4])),
+        Err(DivisionError::NotDivisible)
+    );
+    assert_eq!(
+        a.clone().checked_div_rem(Polynomial::zero()),
+        Err(DivisionError::DivisionByZero)
+    );
+
+    // 4^3 (6x^3 + 7x^2 - 1) = (4x + 1) q + r
+    let b = Polynomial::new(vec![1, 4]);
+    let (q, r) = a.clone().pseudo_div_rem(b.clone());
+    assert_eq!(r.degree(), Some(0));
+    assert_eq!(a.scalar_mul(64), q * b.clone() + r);
+
+    let (q, r) = b.clone().pseudo_div_rem(Polynomial::new(vec![1, 0, 1]));
+    assert_eq!((q, r), (Polynomial::zero(), b));
 }
 
 #[test]
@@ -51,6 +95,316 @@ pub fn test_gcd() {
     assert_eq!(Polynomial::new(v![1, 1]), u.gcd(v));
 }
 
+#[test]
+pub fn test_gcd_ext() {
+    let a = Polynomial::new(v![-1, 0, 0, 1]);
+    let b = Polynomial::new(v![-1, 0, 1]);
+    let (g, s, t) = a.clone().gcd_ext(b.clone());
+    assert_eq!(g, Polynomial::new(v![-1, 1]));
+    assert_eq!(s * a.clone() + t * b.clone(), g);
+
+    // x^2 + x + 1 is coprime to x^2 + 1, and (x + 1)(x^2 + x + 1) = x^3 + 2x^2 + 2x + 1 ≡ x
+    let m = Polynomial::new(v![1, 0, 1]);
+    let inv = Polynomial::new(v![1, 1, 1]).inverse_mod(&m).unwrap();
+    assert_eq!(inv.degree(), Some(1));
+    let (_, r) = (inv * Polynomial::new(v![1, 1, 1]) - Polynomial::one()).div_rem(m.clone());
+    assert!(r.is_zero());
+    assert_eq!(a.clone().inverse_mod(&b), None);
+    assert_eq!(a.clone().inverse_mod(&Polynomial::zero()), None);
+
+    // p ≡ x (mod x^2 + 1) and p ≡ 2 (mod x - 1)
+    let p = Polynomial::chinese_remainder(&[
+        (Polynomial::new(v![0, 1]), m.clone()),
+        (Polynomial::new(v![2]), Polynomial::new(v![-1, 1])),
+    ])
+    .unwrap();
+    let half = num::BigRational::new(1.into(), 2.into());
+    assert_eq!(p, Polynomial::new(vec![half.clone(), num::BigRational::one(), half]));
+    assert_eq!(
+        Polynomial::chinese_remainder(&[(a.clone(), a), (b.clone(), b)]),
+        None
+    );
+    assert_eq!(
+        Polynomial::<num::BigRational>::chinese_remainder(&[]),
+        Some(Polynomial::zero())
+    );
+}
+
+#[test]
+pub fn test_integer_gcd() {
+    let p = |coeffs: &[i64]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    let a = p(&[6, 0, -4]);
+    assert_eq!(a.content(), BigInt::from(-2));
+    assert_eq!(a.primitive_part(), p(&[-3, 0, 2]));
+    assert_eq!(p(&[]).content(), BigInt::zero());
+
+    let a = p(&[-5, 2, 8, -3, -3, 0, 1, 0, 1]);
+    let b = p(&[21, -9, -4, 0, 5, 0, 3]);
+    assert_eq!(
+        a.clone().primitive_prs(b.clone()),
+        [
+            a.clone(),
+            b.clone(),
+            p(&[3, 0, -1, 0, 5]),
+            p(&[-49, 25, 13]),
+            p(&[-6150, 4663]),
+            p(&[1]),
+        ]
+    );
+    let euclidean = a.clone().euclidean_prs(b.clone());
+    assert_eq!(euclidean.len(), 6);
+    assert_eq!(euclidean[2], p(&[-9, 0, 3, 0, -15]));
+    assert_eq!(
+        euclidean[5].clone().primitive_part(),
+        p(&[1]),
+        "the Euclidean remainders only differ by a constant"
+    );
+    assert_eq!(a.clone().integer_gcd(b.clone()), p(&[1]));
+
+    // (2x + 3)(x - 1)^2 and 4 (2x + 3)(x + 5)
+    let g = p(&[3, 2]);
+    let a = g.clone() * p(&[1, -2, 1]);
+    let b = (g.clone() * p(&[5, 1])).scalar_mul(BigInt::from(4));
+    assert_eq!(a.clone().integer_gcd(b.clone()), g);
+    assert_eq!(
+        b.clone().integer_gcd(a.scalar_mul(BigInt::from(-6))),
+        g.scalar_mul(BigInt::from(2))
+    );
+    assert_eq!(
+        b.clone().integer_gcd(p(&[])),
+        b.clone().primitive_part().scalar_mul(BigInt::from(4))
+    );
+    assert_eq!(b.integer_gcd(p(&[6])), p(&[2]));
+    assert_eq!(p(&[]).integer_gcd(p(&[])), p(&[]));
+}
+
+#[test]
+pub fn test_resultant() {
+    use mathstuff_types::matrix::Matrix;
+
+    // small pseudorandom polynomials, with degrees that cover all the parities and differences
+    let mut seed = 7u64;
+    let mut poly = |degree: usize| {
+        let mut coeffs = (0..=degree)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                BigInt::from((seed >> 33) % 19) - BigInt::from(9)
+            })
+            .collect::<Vec<BigInt>>();
+        if coeffs[degree].is_zero() {
+            coeffs[degree] = BigInt::one();
+        }
+        Polynomial::new(coeffs)
+    };
+    for (m, n) in [(1, 1), (2, 1), (3, 3), (4, 1), (5, 2), (2, 6), (6, 3), (0, 4), (3, 0)] {
+        let (a, b) = (poly(m), poly(n));
+        let res = resultant(&a, &b);
+        assert_eq!(res, sylvester_resultant(&a, &b), "{a:?} {b:?}");
+        let sign = if m * n % 2 == 1 { -BigInt::one() } else { BigInt::one() };
+        assert_eq!(resultant(&b, &a), res * sign);
+    }
+
+    let p = |coeffs: &[i64]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    // (x - 1)^2 (x + 2) has a repeated root
+    assert_eq!(discriminant(&p(&[2, -3, 0, 1])), BigInt::zero());
+    // x^4 + 1 has the discriminant 256
+    assert_eq!(discriminant(&p(&[1, 0, 0, 0, 1])), BigInt::from(256));
+    assert_eq!(resultant(&p(&[1, 1]), &p(&[])), BigInt::zero());
+    assert_eq!(resultant(&p(&[3]), &p(&[1, 0, 1])), BigInt::from(9));
+    assert_eq!(
+        sylvester_matrix(&p(&[1, 2, 3]), &p(&[4, 5])),
+        Matrix::from_rows(
+            [[3, 2, 1], [5, 4, 0], [0, 5, 4]]
+                .map(|row| row.map(BigInt::from).to_vec())
+                .to_vec()
+        )
+    );
+
+    let (a, b) = (Polynomial::new(v![1, 2, 0, 3]), Polynomial::new(v![-1, 0, 2]));
+    assert_eq!(resultant(&a, &b), sylvester_resultant(&a, &b));
+
+    // over Z[y], which has exact division but no `/`: res_x(x^2 + y, x - y) = y^2 + y
+    let q = |coeffs: &[&[i64]]| Polynomial::new(coeffs.iter().map(|&c| p(c)).collect());
+    let (a, b) = (q(&[&[0, 1], &[], &[1]]), q(&[&[0, -1], &[1]]));
+    assert_eq!(resultant(&a, &b), p(&[0, 1, 1]));
+    assert_eq!(resultant(&a, &b), sylvester_resultant(&a, &b));
+
+    // the discriminant of a x^2 + b x + c is b^2 - 4ac
+    let [x, a, b, c] = MultiPolynomial::generators(&["x", "a", "b", "c"], MonomialOrder::Lex);
+    let quadratic = a.clone() * x.clone() * x.clone() + b.clone() * x.clone() + c.clone();
+    let four = MultiPolynomial::constant(BigInt::from(4));
+    let expected = b.clone() * b - four * a.clone() * c.clone();
+    assert_eq!(multivariate_discriminant(&quadratic, "x"), expected);
+    // eliminating x from a x - 1 and c x^2 - 1 leaves c - a^2
+    let linear = a.clone() * x.clone() - MultiPolynomial::constant(BigInt::one());
+    let square = c.clone() * x.clone() * x - MultiPolynomial::constant(BigInt::one());
+    assert_eq!(multivariate_resultant(&linear, &square, "x"), c - a.clone() * a.clone());
+    assert_eq!(
+        multivariate_resultant(&a, &a, "y"),
+        MultiPolynomial::constant(BigInt::one())
+    );
+}
+
+#[test]
+pub fn test_partial_fractions() {
+    let p = |coeffs: Vec<num::BigRational>| Polynomial::new(coeffs);
+    let sum = |apart: &PartialFractions<num::BigRational>, d: &Polynomial<num::BigRational>| {
+        apart.terms.iter().fold(apart.polynomial.clone() * d.clone(), |acc, t| {
+            let denominator = num::pow(t.factor.clone(), t.power.get());
+            acc + (d.clone().div_rem(denominator).0 * t.numerator.clone())
+        })
+    };
+
+    // (x^4 + 1) / (x^3 - x^2) = x + 1 - 1 / x - 1 / x^2 + 2 / (x - 1)
+    let (n, d) = (p(v![1, 0, 0, 0, 1]), p(v![0, 0, -1, 1]));
+    let decomposition = apart(n.clone(), d.clone());
+    assert_eq!(decomposition.polynomial, p(v![1, 1]));
+    let terms = decomposition
+        .terms
+        .iter()
+        .map(|t| (t.numerator.clone(), t.factor.clone(), t.power.get()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        terms,
+        [
+            (p(v![2]), p(v![-1, 1]), 1),
+            (p(v![-1]), p(v![0, 1]), 1),
+            (p(v![-1]), p(v![0, 1]), 2),
+        ]
+    );
+    assert_eq!(sum(&decomposition, &d), n);
+
+    // the irreducible quadratic x^2 + 1 stays a single factor
+    let (n, d) = (p(v![3, 2, 1]), p(v![0, 1, 0, 1]));
+    let decomposition = apart(n.clone(), d.clone());
+    assert!(decomposition.polynomial.is_zero());
+    assert!(decomposition.terms.iter().any(|t| t.factor == p(v![1, 0, 1])));
+    assert_eq!(sum(&decomposition, &d), n);
+
+    // a squared quadratic factor and a scaled denominator 3 (x - 2) (x^2 + 1)^2
+    let n = p(v![5, 0, 0, 0, 0, 1]);
+    let d = p(v![-6, 3]) * p(v![1, 0, 1]) * p(v![1, 0, 1]);
+    let decomposition = apart(n.clone(), d.clone());
+    assert_eq!(sum(&decomposition, &d), n);
+    let square_free = apart_square_free(n.clone(), d.clone());
+    assert_eq!(sum(&square_free, &d), n);
+    assert!(square_free.terms.iter().all(|t| t.numerator.degree() < t.factor.degree()));
+
+    // two coprime irreducible quadratics: x / ((x^2 + 1) (x^2 + 2)) = x / (x^2 + 1) - x / (x^2 + 2)
+    let (n, d) = (p(v![0, 1]), p(v![1, 0, 1]) * p(v![2, 0, 1]));
+    let decomposition = apart(n.clone(), d.clone());
+    assert!(decomposition.polynomial.is_zero());
+    let terms = decomposition
+        .terms
+        .iter()
+        .map(|t| (t.numerator.clone(), t.factor.clone(), t.power.get()))
+        .collect::<Vec<_>>();
+    assert_eq!(terms.len(), 2);
+    assert!(terms.contains(&(p(v![0, 1]), p(v![1, 0, 1]), 1)));
+    assert!(terms.contains(&(p(v![0, -1]), p(v![2, 0, 1]), 1)));
+    assert_eq!(sum(&decomposition, &d), n);
+}
+
+#[test]
+pub fn test_fast_multiplication() {
+    use mathstuff_types::traits::EuclideanDomain;
+
+    let mut seed = 11u64;
+    let mut random = |len: usize, bits: u32| {
+        (0..len)
+            .map(|_| {
+                let mut c = BigInt::zero();
+                for _ in 0..bits.div_ceil(32) {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    c = (c << 32) + (seed >> 32);
+                }
+                c >>= bits.div_ceil(32) * 32 - bits;
+                if seed & 1 == 1 {
+                    -c
+                } else {
+                    c
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // lengths around the thresholds, unbalanced lengths and big coefficients
+    for (m, n, bits) in [(1, 1, 8), (40, 33, 8), (70, 70, 8), (200, 65, 100), (150, 70, 300)] {
+        let (a, b) = (random(m, bits), random(n, bits));
+        let expected = mul_schoolbook(&a, &b);
+        assert_eq!(mul_karatsuba(&a, &b), expected, "{m} {n}");
+        assert_eq!(mul_ntt(&a, &b), expected, "{m} {n}");
+        assert_eq!(mul_ntt(&b, &a), expected, "{m} {n}");
+    }
+
+    let rationals = |x: Vec<BigInt>| {
+        x.into_iter()
+            .enumerate()
+            .map(|(i, c)| num::BigRational::new(c, BigInt::from(i % 7 + 1)))
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (rationals(random(100, 30)), rationals(random(80, 30)));
+    assert_eq!(
+        Polynomial::new(a.clone()) * Polynomial::new(b.clone()),
+        Polynomial::new(mul_schoolbook(&a, &b))
+    );
+
+    type F7 = Fp<7>;
+    let a = (0..50).map(|i| F7::new(i * i + 1)).collect::<Vec<_>>();
+    let b = (0..45).map(|i| F7::new(3 * i - 2)).collect::<Vec<_>>();
+    assert_eq!(mul_karatsuba(&a, &b), mul_schoolbook(&a, &b));
+
+    // Newton division agrees with the schoolbook division, with a monic divisor so that the
+    // coefficients of the quotient stay small
+    let a = Polynomial::new(rationals(random(300, 20)));
+    let mut b = random(120, 2);
+    b.push(BigInt::one());
+    let b = Polynomial::new(b.into_iter().map(num::BigRational::from_integer).collect());
+    let (q, r) = a.clone().fast_div_rem(b.clone());
+    assert_eq!((q.clone(), r.clone()), a.clone().div_rem(b.clone()));
+    assert!(r.degree() < b.degree());
+    assert_eq!(a.clone() % b.clone(), r);
+    assert_eq!(EuclideanDomain::div_rem(&a, &b), (q.clone(), r.clone()));
+    assert_eq!(q * b + r, a);
+}
+
+#[test]
+pub fn test_evaluation() {
+    type Q = num::BigRational;
+    let n = |x: i64| Q::from_integer(x.into());
+    // a polynomial of degree 100 with small coefficients, above all the thresholds
+    let p = Polynomial::new((0..=100).map(|i| n((i * 37 % 11) - 5)).collect::<Vec<_>>());
+    let horner = |p: &Polynomial<Q>, x: &Q| {
+        (0..=p.degree().unwrap()).rev().fold(n(0), |acc, i| acc * x + p.coeff_at(i))
+    };
+
+    let points = (-20..20).map(n).collect::<Vec<_>>();
+    let values = points.iter().map(|x| horner(&p, x)).collect::<Vec<_>>();
+    assert_eq!(points.iter().map(|x| p.eval(x)).collect::<Vec<_>>(), values);
+    assert_eq!(p.eval_many(&points), values);
+    assert_eq!(p.eval_many(&points[..5]), values[..5]);
+
+    // p(x + 3) and p(x^2 - x + 2) agree with p at the translated points
+    let shifted = p.taylor_shift(&n(3));
+    let inner = Polynomial::new(v![2, -1, 1]);
+    let composed = p.compose(&inner);
+    assert_eq!(composed.degree(), Some(200));
+    for x in points.iter().step_by(5) {
+        assert_eq!(shifted.eval(x), horner(&p, &(x + n(3))));
+        assert_eq!(composed.eval(x), horner(&p, &horner(&inner, x)));
+    }
+    let small = Polynomial::new(v![1, 2, 3]);
+    assert_eq!(small.taylor_shift(&n(-1)), Polynomial::new(v![2, -4, 3]));
+    assert_eq!(small.compose(&Polynomial::zero()), Polynomial::new(v![1]));
+    assert_eq!(Polynomial::<Q>::zero().taylor_shift(&n(2)), Polynomial::zero());
+
+    // integer polynomials evaluated at rationals and at polynomials
+    let p = Polynomial::new(vec![BigInt::from(-2), BigInt::zero(), BigInt::one()]);
+    assert_eq!(p.eval(&Q::new(3.into(), 2.into())), Q::new(1.into(), 4.into()));
+    let x_plus_one = Polynomial::new(vec![BigInt::one(), BigInt::one()]);
+    assert_eq!(p.eval(&x_plus_one), p.taylor_shift(&BigInt::one()));
+}
+
 #[test]
 pub fn test_square_free_factorization() {
     let u = Polynomial::new(v![-16, -24, -4, 10, 6, 1]);
@@ -77,3 +431,681 @@ pub fn test_square_free_factorization() {
         sqf.print_with_var("x").to_string().as_str()
     );
 }
+
+#[test]
+pub fn test_kronecker_factorization() {
+    let p = |coeffs: &[i64]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    // x^6 - 1 = (x - 1)(x + 1)(x^2 - x + 1)(x^2 + x + 1)
+    let factors = Kronecker::<BigInt>::new(p(&[-1, 0, 0, 0, 0, 0, 1])).into_factors();
+    assert_eq!(factors.len(), 4);
+    assert_eq!(factors.iter().cloned().product::<Polynomial<_>>(), p(&[-1, 0, 0, 0, 0, 0, 1]));
+    assert!(factors[..2].iter().all(|f| f.degree() == Some(1)));
+    assert!(factors.contains(&p(&[1, -1, 1])) && factors.contains(&p(&[1, 1, 1])));
+
+    // irreducible polynomials and constants are their own factorization, up to the content
+    let factors = Kronecker::<BigInt>::new(p(&[1, 0, 0, 0, 1]));
+    assert_eq!(factors.factors(), [p(&[1, 0, 0, 0, 1])]);
+    let factors = Kronecker::<BigInt>::new(p(&[-3, 0, -3]));
+    assert_eq!(factors.factors(), [p(&[-3]), p(&[1, 0, 1])]);
+    assert_eq!(Kronecker::<BigInt>::new(p(&[6])).factors(), [p(&[6])]);
+
+    // over Q the factors are monic
+    let factors = Kronecker::<num::BigRational>::new(poly!(1/2x^3 - 1/2x)).into_factors();
+    assert_eq!(factors, [poly!(1/2), poly!(x + 1), poly!(x), poly!(x - 1)]);
+}
+
+#[test]
+pub fn test_hermite_reduce() {
+    let a = Polynomial::new(v![-8, 8, -4, 0, -24, 0, 0, 1]);
+    let d = Polynomial::new(v![0, 0, 8, 0, 12, 0, 6, 0, 1]);
+    let (_, a) = a.div_rem(d.clone());
+
+    let HermiteReduction { g_num, g_den, h_num, h_den } = hermite_reduce(a.clone(), d.clone());
+
+    // a / d = (g_num / g_den)' + h_num / h_den
+    let dg = g_num.clone().derivative() * g_den.clone() - g_num * g_den.clone().derivative();
+    assert_eq!(
+        (dg * h_den.clone() + h_num.clone() * g_den.clone() * g_den.clone()) * d,
+        a * g_den.clone() * g_den * h_den.clone()
+    );
+    assert_eq!(h_num, Polynomial::new(v![1]));
+    assert_eq!(h_den, Polynomial::new(v![0, 1]));
+}
+
+#[test]
+pub fn test_integrate_rational_function() {
+    // ∫ (x^7 - 24x^4 - 4x^2 + 8x - 8) / (x^8 + 6x^6 + 12x^4 + 8x^2)
+    //     = (3x^3 + 8x^2 + 6x + 4) / (x^5 + 4x^3 + 4x) + log(x)
+    let r = integrate_rational_function(
+        Polynomial::new(v![-8, 8, -4, 0, -24, 0, 0, 1]),
+        Polynomial::new(v![0, 0, 8, 0, 12, 0, 6, 0, 1]),
+    );
+    assert_eq!(r.numerator, Polynomial::new(v![4, 6, 8, 3]));
+    assert_eq!(r.denominator, Polynomial::new(v![0, 4, 0, 4, 0, 1]));
+    assert_eq!(
+        r.logs,
+        vec![LogTerm {
+            roots_of: Polynomial::new(v![-1, 1]),
+            argument: Polynomial::new(vec![Polynomial::zero(), Polynomial::new(v![1])]),
+        }]
+    );
+
+    // ∫ 1 / (x^2 - 1) = Σ_{4t^2 - 1 = 0} t log(1 - 2tx)
+    let r = integrate_rational_function(Polynomial::new(v![1]), Polynomial::new(v![-1, 0, 1]));
+    assert!(r.numerator.is_zero());
+    assert_eq!(
+        r.logs,
+        vec![LogTerm {
+            roots_of: Polynomial::new(vec![
+                num::BigRational::new((-1).into(), 4.into()),
+                num::BigRational::zero(),
+                num::BigRational::one(),
+            ]),
+            argument: Polynomial::new(vec![Polynomial::new(v![1]), Polynomial::new(v![0, -2])]),
+        }]
+    );
+
+    // the polynomial part: ∫ 3x^2 + 2x + 1 / x = x^3 + x^2 + log(x)
+    let r = integrate_rational_function(Polynomial::new(v![1, 0, 2, 3]), Polynomial::new(v![0, 1]));
+    assert_eq!(r.numerator, Polynomial::new(v![0, 0, 1, 1]));
+    assert_eq!(r.denominator, Polynomial::new(v![1]));
+    assert_eq!(r.logs.len(), 1);
+}
+
+#[test]
+pub fn test_log_to_atan() {
+    // i log((x^3 - 3x + i(x^2 - 2)) / (x^3 - 3x - i(x^2 - 2)))
+    //     = 2 atan((x^5 - 3x^3 + x) / 2) + 2 atan(x^3) + 2 atan(x)
+    let half = |x: i32| num::BigRational::new(x.into(), 2.into());
+    assert_eq!(
+        log_to_atan(Polynomial::new(v![0, -3, 0, 1]), Polynomial::new(v![-2, 0, 1])),
+        vec![
+            Polynomial::new(vec![half(0), half(1), half(0), half(-3), half(0), half(1)]),
+            Polynomial::new(v![0, 0, 0, 1]),
+            Polynomial::new(v![0, 1]),
+        ]
+    );
+
+    // with k = 4, (x^2 - 2) / 2 √k is x^2 - 2 again
+    let n = |x: i32| num::BigRational::from_integer(x.into());
+    let sqrt = log_to_atan_sqrt(
+        (Polynomial::new(v![0, -3, 0, 1]), false),
+        (Polynomial::new(vec![n(-1), n(0), half(1)]), true),
+        &n(4),
+    );
+    let sqrt: Vec<_> = sqrt
+        .into_iter()
+        .map(|(p, odd)| if odd { p.scalar_mul(n(2)) } else { p })
+        .collect();
+    assert_eq!(
+        sqrt,
+        log_to_atan(Polynomial::new(v![0, -3, 0, 1]), Polynomial::new(v![-2, 0, 1]))
+    );
+
+    // atan(1 / (-x / √2)) ~ atan(x / √2)
+    assert_eq!(
+        log_to_atan_sqrt(
+            (Polynomial::new(v![1]), false),
+            (Polynomial::new(vec![n(0), half(-1)]), true),
+            &n(2)
+        ),
+        vec![(Polynomial::new(vec![n(0), half(1)]), true)]
+    );
+}
+
+#[test]
+pub fn test_risch_integrate() {
+    let q = |x: i32| num::BigRational::from_integer(x.into());
+    let k = |x: i32| Element::constant(q(x));
+    let x = Element::x();
+    let poly = |c: Vec<Element>| Polynomial::new(c);
+    let integrate = |f: &Element, tower: &Tower| risch_integrate(f, tower);
+
+    // ∫ log(x) = x log(x) - x
+    let mut tower = Tower::new();
+    let log_x = tower.push_log(&x).unwrap();
+    let res = integrate(&log_x, &tower).unwrap();
+    assert_eq!(res.rational, vec![x.clone() * log_x.clone()]);
+    assert_eq!(res.base.numerator, Polynomial::new(v![0, -1]));
+
+    // ∫ 1 / (x log(x)) = log(log(x))
+    let res = integrate(&(k(1) / (x.clone() * log_x.clone())), &tower).unwrap();
+    assert_eq!(res.rational, vec![k(0)]);
+    assert_eq!(
+        res.logs,
+        vec![(
+            1,
+            LogTerm {
+                roots_of: poly(vec![k(-1), k(1)]),
+                argument: Polynomial::new(vec![Polynomial::zero(), Polynomial::one()]),
+            }
+        )]
+    );
+
+    // ∫ x exp(x) = (x - 1) exp(x)
+    let mut tower = Tower::new();
+    let exp_x = tower.push_exp(&x).unwrap();
+    let res = integrate(&(x.clone() * exp_x.clone()), &tower).unwrap();
+    assert_eq!(res.rational, vec![(x.clone() - k(1)) * exp_x.clone()]);
+
+    // ∫ exp(-x) = -1 / exp(x)
+    let res = integrate(&(k(1) / exp_x.clone()), &tower).unwrap();
+    assert_eq!(res.rational, vec![k(-1) / exp_x.clone()]);
+
+    // ∫ 1 / (exp(x) + 1) = x - log(exp(x) + 1)
+    let res = integrate(&(k(1) / (exp_x.clone() + k(1))), &tower).unwrap();
+    assert_eq!(res.base.numerator, Polynomial::new(v![0, 1]));
+    assert_eq!(res.logs[0].1.roots_of, poly(vec![k(1), k(1)]));
+
+    // ∫ exp(x) / (exp(2x) + 1) = Σ t log(exp(x) + 2t) over t^2 + 1/4 = 0, i.e. atan(exp(x))
+    let f = exp_x.clone() / (exp_x.clone() * exp_x.clone() + k(1));
+    let res = integrate(&f, &tower).unwrap();
+    let quarter = Element::constant(num::BigRational::new(1.into(), 4.into()));
+    assert_eq!(
+        res.logs,
+        vec![(
+            1,
+            LogTerm {
+                roots_of: poly(vec![quarter, k(0), k(1)]),
+                argument: Polynomial::new(vec![poly(vec![k(0), k(2)]), Polynomial::one()]),
+            }
+        )]
+    );
+
+    // the logarithmic integral, the exponential integral and the error function
+    let mut tower = Tower::new();
+    let log_x = tower.push_log(&x).unwrap();
+    assert_eq!(
+        integrate(&(k(1) / log_x.clone()), &tower),
+        Err(RischError::NonElementary)
+    );
+    let mut tower = Tower::new();
+    let exp_x = tower.push_exp(&x).unwrap();
+    assert_eq!(
+        integrate(&(exp_x.clone() / x.clone()), &tower),
+        Err(RischError::NonElementary)
+    );
+    let mut tower = Tower::new();
+    let exp_x2 = tower.push_exp(&(x.clone() * x.clone())).unwrap();
+    assert_eq!(integrate(&exp_x2, &tower), Err(RischError::NonElementary));
+
+    // log(x^2) and exp(2x) are not monomials over Q(x)(log(x), exp(x))
+    let mut tower = Tower::new();
+    let exp_x = tower.push_exp(&x).unwrap();
+    let log_x = tower.push_log(&x).unwrap();
+    assert_eq!(tower.push_log(&(x.clone() * x.clone())), None);
+    assert_eq!(tower.push_exp(&(k(2) * x.clone())), None);
+    assert_eq!(tower.push_log(&exp_x), None);
+
+    // towers: ∫ exp(x) / x + exp(x) log(x) = exp(x) log(x), while ∫ exp(x) log(x) and
+    // ∫ log(log(x)) are not elementary
+    let f = exp_x.clone() / x.clone() + exp_x.clone() * log_x.clone();
+    let res = integrate(&f, &tower).unwrap();
+    assert_eq!(res.rational, vec![exp_x.clone() * log_x.clone()]);
+    assert!(res.logs.is_empty());
+    assert_eq!(
+        integrate(&(exp_x.clone() * log_x.clone()), &tower),
+        Err(RischError::NonElementary)
+    );
+
+    let mut tower = Tower::new();
+    let log_x = tower.push_log(&x).unwrap();
+    let log_log_x = tower.push_log(&log_x).unwrap();
+    assert_eq!(
+        integrate(&log_log_x, &tower),
+        Err(RischError::NonElementary)
+    );
+
+    // ∫ log(log(x)) / (x log(x)) = log(log(x))^2 / 2
+    let res = integrate(&(log_log_x.clone() / (x.clone() * log_x)), &tower).unwrap();
+    let half = Element::constant(num::BigRational::new(1.into(), 2.into()));
+    assert_eq!(res.rational, vec![half * log_log_x.clone() * log_log_x]);
+
+    // exp(exp(x)) needs the Risch differential equation over Q(x)(exp(x))
+    let mut tower = Tower::new();
+    let exp_x = tower.push_exp(&x).unwrap();
+    let exp_exp_x = tower.push_exp(&exp_x).unwrap();
+    assert_eq!(integrate(&exp_exp_x, &tower), Err(RischError::Unsupported));
+}
+
+#[test]
+pub fn test_multi_polynomial() {
+    use mathstuff_types::traits::IntegralDomain;
+
+    let [x, y, z] = MultiPolynomial::<i64>::generators(&["x", "y", "z"], MonomialOrder::Lex);
+    let c = MultiPolynomial::constant;
+
+    // x y^2 z + 2 x^2 z^2 - 3 z^3 + y^4
+    let p = x.clone() * y.clone().pow(2) * z.clone()
+        + c(2) * x.clone().pow(2) * z.clone().pow(2)
+        - c(3) * z.clone().pow(3)
+        + y.clone().pow(4);
+    assert_eq!(p.num_terms(), 4);
+    assert_eq!(p.to_string(), "2x^2*z^2 + x*y^2*z + y^4 - 3z^3");
+    assert_eq!(p.leading_term(), Some((&[2, 0, 2][..], &2)));
+
+    let p = p.with_order(MonomialOrder::GrLex);
+    assert_eq!(p.to_string(), "2x^2*z^2 + x*y^2*z + y^4 - 3z^3");
+    let p = p.with_order(MonomialOrder::GRevLex);
+    assert_eq!(p.to_string(), "y^4 + x*y^2*z + 2x^2*z^2 - 3z^3");
+    assert_eq!(p.leading_monomial(), Some(&[0, 4, 0][..]));
+    assert_eq!(p.total_degree(), Some(4));
+    assert_eq!(p.degree_in(0), Some(2));
+    assert_eq!(p.coefficient(&[0, 0, 3]), -3);
+
+    // the order of the terms and variables does not matter for equality
+    assert_eq!(x.clone() + y.clone(), y.clone() + x.clone());
+    let [w] = MultiPolynomial::generators(&["w"], MonomialOrder::Lex);
+    assert_eq!((x.clone() + w.clone()).vars(), ["x", "y", "z", "w"]);
+    assert_eq!(x.clone() + w.clone() - w, x);
+    assert!((p.clone() - p.clone()).is_zero());
+    assert_eq!(p.clone().with_order(MonomialOrder::Lex), p);
+    assert_eq!(p.clone().with_order(MonomialOrder::GrLex), p);
+
+    // exact division
+    let q = x.clone() - c(2) * z.clone();
+    assert_eq!(IntegralDomain::exact_div(&(p.clone() * q.clone()), &q), Some(p.clone()));
+    assert_eq!(IntegralDomain::exact_div(&p, &q), None);
+    assert_eq!(IntegralDomain::exact_div(&(p.clone() * c(3)), &c(3)), Some(p.clone()));
+    assert_eq!(IntegralDomain::exact_div(&p, &c(2)), None);
+
+    // evaluation and derivatives
+    assert_eq!(p.eval(&[1, 2, 3]), 16 + 12 + 18 - 81);
+    let q = p.eval_var(1, &2);
+    assert_eq!(q.to_string(), "2x^2*z^2 - 3z^3 + 4x*z + 16");
+    assert_eq!(p.derivative(2).to_string(), "x*y^2 + 4x^2*z - 9z^2");
+    assert!(p.derivative(0).derivative(0).derivative(0).is_zero());
+
+    // as a coefficient ring
+    let [a] = MultiPolynomial::<i64>::generators(&["a"], MonomialOrder::Lex);
+    let f = Polynomial::new(vec![a.clone(), MultiPolynomial::one()]);
+    let g = Polynomial::new(vec![-a.clone(), MultiPolynomial::one()]);
+    assert_eq!(
+        f * g,
+        Polynomial::new(vec![-a.clone().pow(2), MultiPolynomial::zero(), MultiPolynomial::one()])
+    );
+}
+
+#[test]
+pub fn test_multivariate_gcd() {
+    type P = MultiPolynomial<num::BigInt>;
+    let [x, y, z] = P::generators(&["x", "y", "z"], MonomialOrder::Lex);
+    let c = |n: i64| P::constant(n.into());
+
+    let cases = [
+        // (gcd, cofactor of a, cofactor of b)
+        (
+            x.clone() * y.clone() - c(2) * z.clone() + c(1),
+            x.clone().pow(2) + y.clone(),
+            x.clone() - y.clone() * z.clone(),
+        ),
+        (
+            (c(3) * x.clone().pow(2) * z.clone() - y.clone().pow(3) + c(5)).pow(2),
+            c(4) * (y.clone() + z.clone()),
+            c(9) * (x.clone() + c(1)) * (z.clone() - c(1)),
+        ),
+        (c(7), x.clone() + y.clone(), x.clone() - y.clone()),
+        (y.clone() * z.clone(), z.clone(), y.clone()),
+        (c(1), x.clone() + z.clone(), x.clone() * z.clone() - c(1)),
+    ];
+    for (g, u, v) in cases {
+        let (a, b) = (g.clone() * u, g.clone() * v);
+        assert_eq!(subresultant_gcd(&a, &b), g, "{a} {b}");
+        assert_eq!(modular_gcd(&a, &b), g, "{a} {b}");
+        assert_eq!(modular_gcd(&-a.clone(), &b), g, "{a} {b}");
+    }
+
+    // the gcd of an integer multiple of the gcd and zero
+    let a = c(-2) * x.clone() * y.clone();
+    assert_eq!(modular_gcd(&a, &P::zero()), c(2) * x.clone() * y.clone());
+    assert!(subresultant_gcd(&P::zero(), &P::zero()).is_zero());
+
+    // a gcd with coefficients too big for the primes of the modular algorithm
+    let big = P::constant(num::pow(num::BigInt::from(3), 700));
+    let g = x.clone() * y.clone() + big;
+    let (a, b) = (g.clone() * (x.clone() + c(1)), g.clone() * (y.clone() - c(1)));
+    assert_eq!(modular_gcd(&a, &b), g);
+
+    // over Q
+    let [s, t] = MultiPolynomial::<num::BigRational>::generators(&["s", "t"], MonomialOrder::GrLex);
+    let q = |n: i64, d: i64| MultiPolynomial::constant(num::BigRational::new(n.into(), d.into()));
+    let g = s.clone() * t.clone() - q(1, 2);
+    let a = g.clone() * (s.clone() + q(2, 3)) * q(3, 1);
+    let b = g.clone() * (t.clone().pow(2) - s.clone()) * q(1, 5);
+    assert_eq!(rational_gcd(&a, &b), g);
+}
+
+#[test]
+pub fn test_groebner_basis() {
+    type Q = num::BigRational;
+    let q = |n: i64, d: i64| MultiPolynomial::constant(Q::new(n.into(), d.into()));
+
+    // Cox, Little, O'Shea, Ideals, Varieties and Algorithms, §2.7
+    let [x, y] = MultiPolynomial::<Q>::generators(&["x", "y"], MonomialOrder::GrLex);
+    let f = [
+        x.clone().pow(3) - q(2, 1) * x.clone() * y.clone(),
+        x.clone().pow(2) * y.clone() - q(2, 1) * y.clone().pow(2) + x.clone(),
+    ];
+    let expected = [
+        x.clone().pow(2),
+        x.clone() * y.clone(),
+        y.clone().pow(2) - q(1, 2) * x.clone(),
+    ];
+    let basis = GroebnerBasis::buchberger(&f);
+    assert_eq!(basis.polynomials(), expected);
+    assert_eq!(GroebnerBasis::f4(&f), basis);
+    for p in &f {
+        assert!(basis.contains(p));
+    }
+    assert!(!basis.contains(&y));
+    assert_eq!(
+        basis.normal_form(&(y.clone().pow(3) + x.clone() + y.clone())),
+        x.clone() + y.clone()
+    );
+    assert!(!basis.is_unit_ideal());
+
+    // the same ideal over GF(7), where -1/2 = 3
+    type F7 = Fp<7>;
+    let c = |n: i64| MultiPolynomial::constant(F7::new(n));
+    let [x, y] = MultiPolynomial::<F7>::generators(&["x", "y"], MonomialOrder::GrLex);
+    let f = [
+        x.clone().pow(3) - c(2) * x.clone() * y.clone(),
+        x.clone().pow(2) * y.clone() - c(2) * y.clone().pow(2) + x.clone(),
+    ];
+    let basis = GroebnerBasis::f4(&f);
+    assert_eq!(
+        basis.polynomials(),
+        [
+            x.clone().pow(2),
+            x.clone() * y.clone(),
+            y.clone().pow(2) + c(3) * x.clone()
+        ]
+    );
+    assert_eq!(GroebnerBasis::buchberger(&f), basis);
+
+    // cyclic 3, whose lex basis is triangular
+    let [x, y, z] = MultiPolynomial::<Q>::generators(&["x", "y", "z"], MonomialOrder::Lex);
+    let f = [
+        x.clone() + y.clone() + z.clone(),
+        x.clone() * y.clone() + y.clone() * z.clone() + z.clone() * x.clone(),
+        x.clone() * y.clone() * z.clone() - q(1, 1),
+    ];
+    let expected = [
+        x.clone() + y.clone() + z.clone(),
+        y.clone().pow(2) + y.clone() * z.clone() + z.clone().pow(2),
+        z.clone().pow(3) - q(1, 1),
+    ];
+    assert_eq!(GroebnerBasis::buchberger(&f).polynomials(), expected);
+    assert_eq!(GroebnerBasis::f4(&f).polynomials(), expected);
+
+    // divisors in different variables are rewritten in the union of all the variables
+    let [_, y2] = MultiPolynomial::<Q>::generators(&["x", "y"], MonomialOrder::Lex);
+    let [_, z2] = MultiPolynomial::<Q>::generators(&["x", "z"], MonomialOrder::Lex);
+    let (quotients, remainder) = y2.clone().pow(2).div_rem_many(&[z2, y2]);
+    assert_eq!(quotients, [MultiPolynomial::zero(), y.clone()]);
+    assert!(remainder.is_zero());
+    assert!(quotients.iter().chain([&remainder]).all(|p| p.vars() == ["x", "y", "z"]));
+
+    // katsura 3 in all the orders
+    for order in [MonomialOrder::Lex, MonomialOrder::GrLex, MonomialOrder::GRevLex] {
+        let [u0, u1, u2] = MultiPolynomial::<Q>::generators(&["u0", "u1", "u2"], order);
+        let two = q(2, 1);
+        let f = [
+            u0.clone() + two.clone() * u1.clone() + two.clone() * u2.clone() - q(1, 1),
+            u0.clone().pow(2) + two.clone() * u1.clone().pow(2) + two.clone() * u2.clone().pow(2)
+                - u0.clone(),
+            two.clone() * u0.clone() * u1.clone() + two.clone() * u1.clone() * u2.clone()
+                - u1.clone(),
+        ];
+        let basis = GroebnerBasis::buchberger(&f);
+        assert_eq!(GroebnerBasis::f4(&f), basis, "{order:?}");
+        assert!(f.iter().all(|p| basis.contains(p)));
+    }
+
+    // inconsistent equations generate the unit ideal
+    let [x, y] = MultiPolynomial::<Q>::generators(&["x", "y"], MonomialOrder::GRevLex);
+    let f = [x.clone() * y.clone() - q(1, 1), x.clone(), y.clone() + q(3, 1)];
+    assert_eq!(GroebnerBasis::f4(&f).polynomials(), [q(1, 1)]);
+    assert!(GroebnerBasis::buchberger(&f).is_unit_ideal());
+}
+
+#[test]
+pub fn test_algebraic_structures() {
+    use mathstuff_types::traits::{Characteristic, EuclideanDomain, GcdDomain, IntegralDomain};
+
+    let n = |x: i64| BigInt::from(x);
+    assert_eq!(GcdDomain::gcd(&n(-12), &n(18)), n(6));
+    assert_eq!(GcdDomain::lcm(&n(-4), &n(6)), n(12));
+    assert_eq!(n(-7).unit(), n(-1));
+    assert_eq!(IntegralDomain::exact_div(&n(12), &n(-4)), Some(n(-3)));
+    assert_eq!(IntegralDomain::exact_div(&n(12), &n(5)), None);
+    let (g, s, t) = EuclideanDomain::gcd_ext(&n(240), &n(-46));
+    assert_eq!(g, n(2));
+    assert_eq!(s * n(240) + t * n(-46), g);
+    assert_eq!(IntegralDomain::exact_div(&i64::MIN, &-1), None);
+    assert_eq!(GcdDomain::normalize(&(i64::MIN + 1)), i64::MAX);
+    assert_eq!(EuclideanDomain::div_rem(&-7i64, &2), (-3, -1));
+
+    // gcds over Z[x] keep the gcd of the contents and have a positive leading coefficient
+    let p = |coeffs: &[i64]| Polynomial::new(coeffs.iter().map(|&c| n(c)).collect());
+    let (a, b) = (p(&[-6, 0, 6]), p(&[-4, -8, -4]));
+    assert_eq!(a.content(), n(6));
+    assert_eq!(b.content(), n(-4));
+    assert_eq!(a.clone().gcd(b.clone()), p(&[2, 2]));
+    assert_eq!(GcdDomain::lcm(&a, &b), p(&[-12, -12, 12, 12]));
+    assert_eq!(IntegralDomain::exact_div(&a, &p(&[2, 2])), Some(p(&[-3, 3])));
+    assert_eq!(IntegralDomain::exact_div(&a, &p(&[1, 4])), None);
+    assert_eq!(GcdDomain::normalize(&p(&[3, -2])), p(&[-3, 2]));
+
+    // over Z[x][y], with the contents in Z[x]
+    let q = |coeffs: &[&[i64]]| Polynomial::new(coeffs.iter().map(|&c| p(c)).collect());
+    let a = q(&[&[0, 2], &[2]]);
+    let b = q(&[&[0, 0, 4], &[0, 4]]);
+    assert_eq!(a.gcd(b), q(&[&[0, 2], &[2]]));
+
+    // gcds over fields are monic
+    let u = Polynomial::new(v![2, 4, 2]);
+    assert_eq!(u.clone().gcd(Polynomial::new(v![-3, 3, 6])), Polynomial::new(v![1, 1]));
+    assert_eq!(GcdDomain::gcd(&u, &Polynomial::zero()), Polynomial::new(v![1, 2, 1]));
+    let (g, s, t) = EuclideanDomain::gcd_ext(&u, &Polynomial::new(v![-1, 1]));
+    assert!(g.is_one());
+    assert_eq!(s * u + t * Polynomial::new(v![-1, 1]), g);
+
+    assert_eq!(Fp::<7>::characteristic(), 7);
+    assert_eq!(Polynomial::<Fp<7>>::characteristic(), 7);
+    assert_eq!(num::BigRational::characteristic(), 0);
+
+    // square-free factorizations over Z split off the content
+    let sqf = SquareFreeFactorization::factor_primitive(p(&[24, 132, 90, -525, -750]));
+    assert_eq!(
+        "-3(2x - 1)(5x + 2)^3",
+        sqf.print_with_var("x").to_string().as_str()
+    );
+
+    // over any field, like F_7, the factors are monic
+    let f7 = |coeffs: &[i64]| Polynomial::new(coeffs.iter().map(|&c| Fp::<7>::new(c)).collect());
+    let sqf = SquareFreeFactorization::factor_polynomial(f7(&[4, 10, 8, 2]));
+    assert_eq!(sqf.leading_coeff, Fp::new(2));
+    let k = |k: usize| std::num::NonZeroUsize::new(k).unwrap();
+    assert_eq!(sqf.factors, [(f7(&[2, 1]), k(1)), (f7(&[1, 1]), k(2))]);
+}
+
+#[test]
+pub fn test_fractions() {
+    use mathstuff_types::fraction::Fraction;
+
+    let n = |x: i64| BigInt::from(x);
+    let f = Fraction::new(n(6), n(-4));
+    assert_eq!((f.numerator(), f.denominator()), (&n(-3), &n(2)));
+    assert_eq!(f.clone() + Fraction::from(n(2)), Fraction::new(n(1), n(2)));
+    assert_eq!(f.pow(3), Fraction::new(n(-27), n(8)));
+    assert!((f.clone() - f).is_zero());
+
+    // Q(a), with a monic denominator
+    type Q = num::BigRational;
+    let q = |x: i64| Q::from_integer(x.into());
+    let a = RationalFunction::<Q>::x();
+    let k = |x: i64| RationalFunction::constant(q(x));
+    let f = (a.clone() * a.clone() - k(1)) / (k(2) * a.clone() - k(2));
+    assert_eq!(f, RationalFunction::new(Polynomial::new(v![1, 1]), Polynomial::new(v![2])));
+    assert_eq!(f.eval(&q(3)), Some(q(2)));
+    assert_eq!(k(1) / a.clone() - k(1) / a.clone(), k(0));
+    let g = RationalFunction::new(Polynomial::new(v![1]), Polynomial::new(v![-1, 1]));
+    assert_eq!(g.eval(&q(1)), None);
+    assert_eq!(
+        g.derivative(),
+        RationalFunction::new(Polynomial::new(v![-1]), Polynomial::new(v![1, -2, 1]))
+    );
+
+    // polynomials in x with coefficients in Q(a): gcd((x - a)(x - 1), (x - a)(a x + 1)) = x - a
+    let p = |c: Vec<RationalFunction<Q>>| Polynomial::new(c);
+    let u = p(vec![-a.clone(), k(1)]) * p(vec![k(-1), k(1)]);
+    let v = p(vec![-a.clone(), k(1)]) * p(vec![k(1), a.clone()]);
+    assert_eq!(u.gcd(v), p(vec![-a, k(1)]));
+}
+
+#[test]
+pub fn test_matrices() {
+    use mathstuff_types::matrix::Matrix;
+
+    let z = |rows: &[&[i64]]| {
+        let row = |r: &[i64]| r.iter().map(|&x| BigInt::from(x)).collect();
+        Matrix::from_rows(rows.iter().map(|r| row(r)).collect())
+    };
+    let m = z(&[&[2, -1, 0], &[1, 3, 4], &[0, 5, -2]]);
+    assert_eq!(m.determinant(), BigInt::from(-54));
+    assert_eq!(m.transpose().determinant(), BigInt::from(-54));
+    assert_eq!(m.rank(), 3);
+    assert_eq!((m.clone() * m.clone()).determinant(), BigInt::from(54 * 54));
+    assert_eq!(m.clone() * Matrix::identity(3), m);
+    assert_eq!(m.clone() - m.clone(), Matrix::zeros(3, 3));
+    // a zero column, a row swap and a dependent row
+    let singular = z(&[&[0, 0, 1, 2], &[0, 2, 3, 4], &[0, 4, 7, 10], &[0, 1, 1, 0]]);
+    assert_eq!(singular.rank(), 3);
+    assert_eq!(z(&[&[0, 1], &[1, 0]]).determinant(), BigInt::from(-1));
+    assert_eq!(
+        z(&[&[3, 1, 4, 1], &[5, 9, 2, 6], &[5, 3, 5, 8], &[9, 7, 9, 3]]).determinant(),
+        BigInt::from(98)
+    );
+
+    let q = |rows: &[&[i64]]| {
+        let row = |r: &[i64]| r.iter().map(|&x| num::BigRational::from_integer(x.into())).collect();
+        Matrix::from_rows(rows.iter().map(|r| row(r)).collect())
+    };
+    let m = q(&[&[1, 2, 1], &[2, 4, 0], &[3, 6, 1]]);
+    let (rref, pivots) = m.rref();
+    assert_eq!(rref, q(&[&[1, 2, 0], &[0, 0, 1], &[0, 0, 0]]));
+    assert_eq!(pivots, [0, 2]);
+    assert_eq!(m.inverse(), None);
+    assert_eq!(m.nullspace(), [v![-2, 1, 0]]);
+    let (consistent, inconsistent) = (v![2, 4, 6], v![1, 1, 1]);
+    assert_eq!(m.solve(&consistent), Some(v![2, 0, 0]));
+    assert_eq!(m.solve(&inconsistent), None);
+
+    let m = q(&[&[2, 1], &[7, 4]]);
+    let inv = m.inverse().unwrap();
+    assert_eq!(inv, q(&[&[4, -1], &[-7, 2]]));
+    assert_eq!(m * inv, Matrix::identity(2));
+
+    // over Q[x]: det(x I - A) is the characteristic polynomial
+    let x = Polynomial::new(v![0, 1]);
+    let c = |n: i64| Polynomial::new(v![n]);
+    let m = Matrix::from_rows(vec![
+        vec![x.clone() - c(2), c(-1)],
+        vec![c(-1), x.clone() - c(2)],
+    ]);
+    assert_eq!(m.determinant(), Polynomial::new(v![3, -4, 1]));
+}
+
+#[test]
+pub fn test_integer_normal_forms() {
+    use mathstuff_types::matrix::Matrix;
+
+    let z = |rows: &[&[i64]]| {
+        let row = |r: &[i64]| r.iter().map(|&x| BigInt::from(x)).collect();
+        Matrix::from_rows(rows.iter().map(|r| row(r)).collect())
+    };
+    let unimodular = |u: &Matrix<BigInt>| num::Signed::abs(&u.determinant()).is_one();
+
+    // the characteristic polynomial agrees with det(x I - A) over Z[x]
+    let m = z(&[&[3, 1, 4, 1], &[5, 9, 2, 6], &[5, 3, 5, 8], &[9, 7, 9, 3]]);
+    let x = Polynomial::new(vec![BigInt::zero(), BigInt::one()]);
+    let x_minus_m = Matrix::from_fn(4, 4, |i, j| {
+        let entry = Polynomial::from(m[(i, j)].clone());
+        if i == j {
+            x.clone() - entry
+        } else {
+            -entry
+        }
+    });
+    assert_eq!(m.charpoly(), x_minus_m.determinant());
+    assert_eq!(m.charpoly().coeff_at(0), &BigInt::from(98));
+    assert_eq!(z(&[]).charpoly(), Polynomial::one());
+
+    // a zero column and a dependent row
+    let m = z(&[&[0, 4, -6, 2], &[0, 6, 3, 9], &[0, 2, -15, -5]]);
+    let (h, u) = m.hermite_normal_form();
+    assert_eq!(h, z(&[&[0, 2, 9, 7], &[0, 0, 24, 12], &[0, 0, 0, 0]]));
+    assert!(unimodular(&u));
+    assert_eq!(u * m.clone(), h);
+
+    let (s, u, v) = m.smith_normal_form();
+    assert_eq!(s, z(&[&[1, 0, 0, 0], &[0, 12, 0, 0], &[0, 0, 0, 0]]));
+    assert!(unimodular(&u) && unimodular(&v));
+    assert_eq!(u * m.clone() * v, s);
+
+    let b = [0, 18, -18].map(BigInt::from);
+    let (x, kernel) = m.solve_diophantine(&b).unwrap();
+    assert_eq!(m.mul_vec(&x), b);
+    assert_eq!(kernel.len(), 2);
+    for k in &kernel {
+        assert!(m.mul_vec(k).iter().all(Zero::is_zero));
+    }
+    // solvable over Q but not over Z
+    assert_eq!(m.solve_diophantine(&[0, 9, -9].map(BigInt::from)), None);
+    // inconsistent
+    assert_eq!(m.solve_diophantine(&[0, 0, 1].map(BigInt::from)), None);
+}
+
+#[test]
+pub fn test_parse_polynomials() {
+    use mathstuff_types::parse::ParsePolynomialError;
+
+    type Q = num::BigRational;
+    let parse = |s: &str| Polynomial::<Q>::from_str_with_var(s, "x");
+    let p = Polynomial::new(v![24, 132, 90, -525, -750]);
+    assert_eq!(parse(&p.print_with_var("x").to_string()), Ok(p));
+    let half = |n: i32| Q::new(n.into(), 2.into());
+    assert_eq!(
+        parse(" - 1/2 * x^2+x -3x^2 + 2/4"),
+        Ok(Polynomial::new(vec![half(1), Q::one(), half(-7)]))
+    );
+    assert_eq!(parse("x^2 - x^2"), Ok(Polynomial::zero()));
+    assert_eq!(parse("0"), Ok(Polynomial::zero()));
+    assert_eq!(
+        Polynomial::<Q>::from_str_with_var("2tt^3 + tt", "tt"),
+        Ok(Polynomial::new(v![0, 1, 0, 2]))
+    );
+    assert_eq!(poly!(t; 2t^3 + t), Polynomial::new(v![0, 1, 0, 2]));
+    assert_eq!(poly!(3/2 x - 1/2), Polynomial::new(vec![half(-1), half(3)]));
+
+    assert_eq!(parse(""), Err(ParsePolynomialError::UnexpectedEnd));
+    assert_eq!(parse("x^2 +"), Err(ParsePolynomialError::UnexpectedEnd));
+    assert_eq!(parse("x^"), Err(ParsePolynomialError::UnexpectedEnd));
+    assert_eq!(parse("3y"), Err(ParsePolynomialError::Unexpected(1)));
+    assert_eq!(parse("x + * x"), Err(ParsePolynomialError::Unexpected(4)));
+    assert_eq!(parse("2 * 3"), Err(ParsePolynomialError::Unexpected(4)));
+    assert_eq!(parse("1/0"), Err(ParsePolynomialError::InvalidCoefficient(0)));
+    assert_eq!(
+        parse("x^99999999999999999999999"),
+        Err(ParsePolynomialError::InvalidExponent(2))
+    );
+    assert_eq!(
+        Polynomial::<BigInt>::from_str_with_var("x + 1/2", "x"),
+        Err(ParsePolynomialError::InvalidCoefficient(4))
+    );
+    assert_eq!(
+        Polynomial::<BigInt>::from_str_with_var("2x^2 - 7", "x"),
+        Ok(Polynomial::new(vec![BigInt::from(-7), BigInt::zero(), BigInt::from(2)]))
+    );
+}
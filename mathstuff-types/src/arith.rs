@@ -0,0 +1,433 @@
+//! Fast multiplication and division of dense polynomials.
+//!
+//! Products of short polynomials are computed with the schoolbook method, and longer ones with
+//! Karatsuba's algorithm, which takes `O(n^1.59)` ring operations. Polynomials with integer or
+//! rational coefficients are multiplied with number theoretic transforms instead: the product is
+//! computed modulo enough word-sized primes `p` with `2^k | p - 1` to determine its coefficients,
+//! which are then reconstructed with the Chinese remainder theorem.
+//!
+//! Division over a field uses Newton iteration to invert the reversed divisor as a power series,
+//! so that it costs a constant number of multiplications.
+
+use std::ops::Rem;
+use std::sync::{Mutex, OnceLock};
+
+use num::{BigInt, BigRational, Integer, One, ToPrimitive, Zero};
+
+use crate::traits::{CommutativeRing, Field};
+use crate::Polynomial;
+
+/// Below this length, Karatsuba's algorithm falls back to the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Below this length, integer polynomials are not multiplied with number theoretic transforms.
+const NTT_THRESHOLD: usize = 64;
+
+/// Below this degree of the divisor or quotient, division does not use Newton iteration.
+const NEWTON_THRESHOLD: usize = 64;
+
+fn add_assign<R: CommutativeRing>(x: &mut R, y: R) {
+    let old = std::mem::replace(x, R::zero());
+    *x = old + y;
+}
+
+fn sub_assign<R: CommutativeRing>(x: &mut R, y: R) {
+    let old = std::mem::replace(x, R::zero());
+    *x = CommutativeRing::sub(old, y);
+}
+
+/// Adds the product of `a` and `b` to `out`, which must be at least `a.len() + b.len() - 1`
+/// long.
+fn schoolbook_into<R: CommutativeRing>(a: &[R], b: &[R], out: &mut [R]) {
+    for (i, x) in a.iter().enumerate() {
+        if x.is_zero() {
+            continue;
+        }
+        for (y, z) in b.iter().zip(&mut out[i..]) {
+            add_assign(z, x.clone() * y.clone());
+        }
+    }
+}
+
+/// Adds the product of `a` and `b` to `out`, which must be at least `a.len() + b.len() - 1`
+/// long.
+fn karatsuba_into<R: CommutativeRing>(a: &[R], b: &[R], out: &mut [R]) {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    if b.len() < KARATSUBA_THRESHOLD {
+        return schoolbook_into(a, b, out);
+    }
+    if a.len() > b.len() {
+        // multiply b with the pieces of a of the same length
+        for (k, piece) in a.chunks(b.len()).enumerate() {
+            karatsuba_into(piece, b, &mut out[k * b.len()..]);
+        }
+        return;
+    }
+
+    // (a0 + a1 x^m)(b0 + b1 x^m) = z0 + z1 x^m + z2 x^2m where
+    // z1 = (a0 + a1)(b0 + b1) - z0 - z2
+    let m = a.len() / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let sum = |lo: &[R], hi: &[R]| {
+        let mut sum = hi.to_vec();
+        for (x, y) in sum.iter_mut().zip(lo) {
+            add_assign(x, y.clone());
+        }
+        sum
+    };
+    let mut z0 = vec![R::zero(); 2 * m - 1];
+    karatsuba_into(a0, b0, &mut z0);
+    let mut z2 = vec![R::zero(); 2 * a1.len() - 1];
+    karatsuba_into(a1, b1, &mut z2);
+    let mut z1 = vec![R::zero(); 2 * a1.len() - 1];
+    karatsuba_into(&sum(a0, a1), &sum(b0, b1), &mut z1);
+
+    for (i, c) in z0.into_iter().enumerate() {
+        sub_assign(&mut z1[i], c.clone());
+        add_assign(&mut out[i], c);
+    }
+    for (i, c) in z2.into_iter().enumerate() {
+        sub_assign(&mut z1[i], c.clone());
+        add_assign(&mut out[2 * m + i], c);
+    }
+    for (i, c) in z1.into_iter().enumerate() {
+        add_assign(&mut out[m + i], c);
+    }
+}
+
+/// The coefficients of the product of the polynomials with coefficients `a` and `b`, with the
+/// schoolbook method.
+pub fn mul_schoolbook<R: CommutativeRing>(a: &[R], b: &[R]) -> Vec<R> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut out = vec![R::zero(); a.len() + b.len() - 1];
+    schoolbook_into(a, b, &mut out);
+    out
+}
+
+/// The coefficients of the product of the polynomials with coefficients `a` and `b`, with
+/// Karatsuba's algorithm.
+pub fn mul_karatsuba<R: CommutativeRing>(a: &[R], b: &[R]) -> Vec<R> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut out = vec![R::zero(); a.len() + b.len() - 1];
+    karatsuba_into(a, b, &mut out);
+    out
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(p)) as u64
+}
+
+fn pow_mod(mut a: u64, mut e: u64, p: u64) -> u64 {
+    let mut result = 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, p);
+        }
+        a = mul_mod(a, a, p);
+        e >>= 1;
+    }
+    result
+}
+
+/// Deterministic Miller-Rabin test, whose bases are enough for all `n < 2^64`.
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&b) = BASES.iter().find(|&&b| n.is_multiple_of(b)) {
+        return n == b;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// A prime `p` between `2^61` and `2^62` with `2^k | p - 1`, and an element `root` of order
+/// `2^k`.
+#[derive(Clone, Copy)]
+struct NttPrime {
+    p: u64,
+    root: u64,
+}
+
+/// The first `count` primes `p = c 2^k + 1` below `2^62`, by decreasing `c`. Searching for them
+/// takes longer than most transforms, so the primes found are kept for each `k`.
+fn ntt_primes(k: u32, count: usize) -> Vec<NttPrime> {
+    static PRIMES: [OnceLock<Mutex<Vec<NttPrime>>>; 62] = [const { OnceLock::new() }; 62];
+    let mut primes = PRIMES[k as usize]
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap();
+    if primes.len() < count {
+        // continue the search below the last prime found
+        let end = primes.last().map_or(1u64 << (62 - k), |q| (q.p - 1) >> k);
+        let missing = count - primes.len();
+        primes.extend(search_ntt_primes(k, end).take(missing));
+    }
+    primes.iter().take(count).copied().collect()
+}
+
+/// The primes `p = c 2^k + 1` with `2^(61 - k) <= c < end`, by decreasing `c`.
+fn search_ntt_primes(k: u32, end: u64) -> impl Iterator<Item = NttPrime> {
+    (1u64 << (61 - k)..end)
+        .rev()
+        .map(move |c| (c << k) + 1)
+        .filter(|&p| is_prime(p))
+        .map(move |p| {
+            // a quadratic nonresidue z has order divisible by 2^s where 2^s || p - 1
+            let z = (2..)
+                .find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)
+                .unwrap();
+            let s = (p - 1).trailing_zeros();
+            let root = pow_mod(pow_mod(z, (p - 1) >> s, p), 1 << (s - k), p);
+            NttPrime { p, root }
+        })
+}
+
+/// The number theoretic transform of `a`, whose length is a power of two, with the root of unity
+/// `root` of order `a.len()`.
+fn ntt(a: &mut [u64], root: u64, p: u64) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let w = pow_mod(root, (n / len) as u64, p);
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wk = 1;
+            for (x, y) in lo.iter_mut().zip(hi) {
+                let (u, v) = (*x, mul_mod(*y, wk, p));
+                *x = (u + v) % p;
+                *y = (u + p - v) % p;
+                wk = mul_mod(wk, w, p);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// The coefficients of the product of the polynomials with coefficients `a` and `b`, with number
+/// theoretic transforms.
+pub fn mul_ntt(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let k = n.trailing_zeros();
+
+    // |c_i| ≤ min(m, n) max |a_i| max |b_j|, and the primes must determine c_i and its sign
+    let max_bits = |x: &[BigInt]| x.iter().map(BigInt::bits).max().unwrap_or(0);
+    let bits = max_bits(a) + max_bits(b) + u64::from(a.len().min(b.len()).ilog2()) + 3;
+    let primes = ntt_primes(k, bits.div_ceil(61) as usize);
+
+    let residues = primes
+        .iter()
+        .map(|&NttPrime { p, root }| {
+            let big_p = BigInt::from(p);
+            let transform = |x: &[BigInt]| {
+                let mut t = x
+                    .iter()
+                    .map(|c| c.mod_floor(&big_p).to_u64().unwrap())
+                    .collect::<Vec<_>>();
+                t.resize(n, 0);
+                ntt(&mut t, root, p);
+                t
+            };
+            let mut c = transform(a);
+            for (x, y) in c.iter_mut().zip(transform(b)) {
+                *x = mul_mod(*x, y, p);
+            }
+            ntt(&mut c, pow_mod(root, p - 2, p), p);
+            let n_inv = pow_mod(n as u64, p - 2, p);
+            c.truncate(len);
+            c.iter_mut().for_each(|x| *x = mul_mod(*x, n_inv, p));
+            c
+        })
+        .collect::<Vec<_>>();
+
+    // Garner's algorithm: c = v_0 + v_1 p_0 + v_2 p_0 p_1 + ..., where inverses[i] is the inverse
+    // of p_0 ... p_(i - 1) modulo p_i
+    let inverses = primes
+        .iter()
+        .enumerate()
+        .map(|(i, &NttPrime { p, .. })| {
+            let product = primes[..i]
+                .iter()
+                .fold(1, |acc, q| mul_mod(acc, q.p % p, p));
+            pow_mod(product, p - 2, p)
+        })
+        .collect::<Vec<_>>();
+    let modulus = primes.iter().fold(BigInt::one(), |acc, q| acc * q.p);
+    let half = &modulus >> 1;
+    (0..len)
+        .map(|j| {
+            let mut digits = Vec::with_capacity(primes.len());
+            for (i, &NttPrime { p, .. }) in primes.iter().enumerate() {
+                let partial = digits
+                    .iter()
+                    .zip(&primes)
+                    .rev()
+                    .fold(0, |acc, (&v, q)| (mul_mod(acc, q.p % p, p) + v % p) % p);
+                let v = mul_mod((residues[i][j] + p - partial) % p, inverses[i], p);
+                digits.push(v);
+            }
+            let c = digits
+                .iter()
+                .zip(&primes)
+                .rev()
+                .fold(BigInt::zero(), |acc, (&v, q)| acc * q.p + v);
+            if c > half {
+                c - &modulus
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// The product of integer polynomials, see [`CommutativeRing::mul_slices`].
+pub(crate) fn mul_integers(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    if a.len().min(b.len()) < NTT_THRESHOLD {
+        mul_karatsuba(a, b)
+    } else {
+        mul_ntt(a, b)
+    }
+}
+
+/// The product of rational polynomials, see [`CommutativeRing::mul_slices`]. Long polynomials
+/// are scaled to integer polynomials and multiplied with [`mul_ntt`].
+pub(crate) fn mul_rationals(a: &[BigRational], b: &[BigRational]) -> Vec<BigRational> {
+    if a.len().min(b.len()) < NTT_THRESHOLD {
+        return mul_karatsuba(a, b);
+    }
+    let integers = |x: &[BigRational]| {
+        let lcm = x.iter().fold(BigInt::one(), |acc, c| acc.lcm(c.denom()));
+        let numerators = x
+            .iter()
+            .map(|c| c.numer() * (&lcm / c.denom()))
+            .collect::<Vec<_>>();
+        (numerators, lcm)
+    };
+    let (a, da) = integers(a);
+    let (b, db) = integers(b);
+    let denominator = da * db;
+    mul_ntt(&a, &b)
+        .into_iter()
+        .map(|c| BigRational::new(c, denominator.clone()))
+        .collect()
+}
+
+impl<F: Field> Polynomial<F> {
+    /// The first `n` coefficients of the power series `1 / self`, computed with Newton iteration.
+    /// `None` if the constant coefficient is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigRational;
+    /// let p = |coeffs: &[i32]| {
+    ///     Polynomial::new(coeffs.iter().map(|&c| BigRational::from_integer(c.into())).collect())
+    /// };
+    /// // 1 / (1 - x) = 1 + x + x^2 + ...
+    /// assert_eq!(p(&[1, -1]).inverse_series(4), Some(p(&[1, 1, 1, 1])));
+    /// assert_eq!(p(&[0, 1]).inverse_series(4), None);
+    /// ```
+    pub fn inverse_series(&self, n: usize) -> Option<Self> {
+        let inverse = self.coeffs.first()?.checked_inv()?;
+        let mut g = Polynomial::new(vec![inverse]);
+        let two = Polynomial::new(vec![F::one() + F::one()]);
+        let mut len = 1;
+        while len < n {
+            // g (2 - f g) is correct to twice as many terms as g
+            len = (2 * len).min(n);
+            let fg = (self.clone().truncated(len) * g.clone()).truncated(len);
+            g = (g * (two.clone() - fg)).truncated(len);
+        }
+        Some(g.truncated(n))
+    }
+
+    /// `self` modulo `x^n`.
+    fn truncated(mut self, n: usize) -> Self {
+        self.coeffs.truncate(n);
+        self.trim_zeros();
+        self
+    }
+
+    /// The polynomial with the coefficients of `self` in reverse order, as a polynomial of degree
+    /// `degree`.
+    fn reversed(&self, degree: usize) -> Self {
+        let mut coeffs = self.coeffs.clone();
+        coeffs.resize_with(degree + 1, F::zero);
+        coeffs.reverse();
+        Polynomial::new_trim_zeroes(coeffs)
+    }
+
+    /// Performs polynomial division like [`Self::div_rem`], computing the quotient with
+    /// [`Self::inverse_series`] when both the divisor and quotient are long. For a divisor of
+    /// degree `n` and a dividend of degree `m`, the quotient `q` satisfies
+    /// `rev(q) = rev(self) / rev(other) mod x^(m - n + 1)`, where `rev` reverses the coefficients.
+    ///
+    /// # Panics
+    ///
+    /// If `other` is zero.
+    pub fn fast_div_rem(self, other: Self) -> (Self, Self) {
+        let n = other.degree().expect("division by the zero polynomial");
+        let Some(m) = self.degree().filter(|&m| m >= n) else {
+            return (Polynomial::zero(), self);
+        };
+        if n < NEWTON_THRESHOLD || m - n < NEWTON_THRESHOLD {
+            return self.div_rem(other);
+        }
+
+        let k = m - n + 1;
+        let inverse = other
+            .reversed(n)
+            .inverse_series(k)
+            .expect("the leading coefficient is nonzero");
+        let quotient = (self.reversed(m).truncated(k) * inverse)
+            .truncated(k)
+            .reversed(k - 1);
+        let remainder = self - other * quotient.clone();
+        (quotient, remainder)
+    }
+}
+
+/// The remainder of [`Polynomial::fast_div_rem`].
+impl<F: Field> Rem for Polynomial<F> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        self.fast_div_rem(rhs).1
+    }
+}
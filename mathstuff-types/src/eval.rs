@@ -0,0 +1,178 @@
+//! Evaluation, composition and translation of polynomials.
+//!
+//! Single points are evaluated with Horner's scheme, and many points at once by reducing the
+//! polynomial modulo the products `Π (x - x_i)` of a subproduct tree. Compositions `p(q(x))` and
+//! Taylor shifts `p(x + a)` split `p = p_0 + x^h p_1` and recurse on both halves with the powers
+//! `q^(2^i)`, so that they take `O(log n)` fast multiplications of each size.
+
+use std::ops::Mul;
+
+use num::Zero;
+
+use crate::traits::{CommutativeRing, Field};
+use crate::Polynomial;
+
+/// Below this degree, Taylor shifts use the quadratic algorithm, and compositions and
+/// multipoint evaluation use Horner's scheme.
+const HORNER_THRESHOLD: usize = 32;
+
+impl<Ring: CommutativeRing> Polynomial<Ring> {
+    /// Evaluates the polynomial at `x` with Horner's scheme. `x` can belong to any ring the
+    /// coefficients embed into, like a field of fractions or a polynomial ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::{BigInt, BigRational};
+    /// let p = Polynomial::new(vec![BigInt::from(1), BigInt::from(0), BigInt::from(2)]);
+    /// assert_eq!(p.eval(&BigInt::from(3)), BigInt::from(19));
+    /// // the integers embed into the rationals
+    /// let half = BigRational::new(1.into(), 2.into());
+    /// assert_eq!(p.eval(&half), BigRational::new(3.into(), 2.into()));
+    /// ```
+    pub fn eval<S>(&self, x: &S) -> S
+    where
+        S: Zero + Mul<Output = S> + Clone + From<Ring>,
+    {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(S::zero(), |acc, c| acc * x.clone() + S::from(c.clone()))
+    }
+
+    /// The composition `self(inner(x))`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigInt;
+    /// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    /// // (x^2 + 1)^2 + 1 = x^4 + 2x^2 + 2
+    /// assert_eq!(p(&[1, 0, 1]).compose(&p(&[1, 0, 1])), p(&[2, 0, 2, 0, 1]));
+    /// ```
+    pub fn compose(&self, inner: &Self) -> Self {
+        if self.coeffs.len() <= HORNER_THRESHOLD {
+            return self.eval(inner);
+        }
+        // powers[i] = inner^(2^i) for all the splits of self in compose_rec
+        let mut powers = vec![inner.clone()];
+        while powers.len() <= (self.coeffs.len() - 1).ilog2() as usize {
+            let last = powers.last().unwrap().clone();
+            powers.push(last.clone() * last);
+        }
+        compose_rec(&self.coeffs, inner, &powers)
+    }
+
+    /// The Taylor shift `self(x + a)`, whose coefficients are the Taylor coefficients of `self`
+    /// at `a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigInt;
+    /// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    /// // (x + 1)^3 = x^3 + 3x^2 + 3x + 1
+    /// assert_eq!(p(&[0, 0, 0, 1]).taylor_shift(&BigInt::from(1)), p(&[1, 3, 3, 1]));
+    /// ```
+    pub fn taylor_shift(&self, a: &Ring) -> Self {
+        if self.coeffs.len() > HORNER_THRESHOLD {
+            return self.compose(&Polynomial::new_trim_zeroes(vec![a.clone(), Ring::one()]));
+        }
+        // c_j += a c_(j + 1) from the top, once for every coefficient
+        let mut coeffs = self.coeffs.clone();
+        let n = coeffs.len();
+        for i in 0..n {
+            for j in (i..n - 1).rev() {
+                let old = std::mem::replace(&mut coeffs[j], Ring::zero());
+                coeffs[j] = old + a.clone() * coeffs[j + 1].clone();
+            }
+        }
+        Polynomial::new_trim_zeroes(coeffs)
+    }
+}
+
+/// `p(inner)` for the polynomial `p` with the coefficients `coeffs`, given
+/// `powers[i] = inner^(2^i)` for the halves of `coeffs` longer than [`HORNER_THRESHOLD`].
+fn compose_rec<Ring: CommutativeRing>(
+    coeffs: &[Ring],
+    inner: &Polynomial<Ring>,
+    powers: &[Polynomial<Ring>],
+) -> Polynomial<Ring> {
+    if coeffs.len() <= HORNER_THRESHOLD {
+        return coeffs.iter().rev().fold(Polynomial::zero(), |acc, c| {
+            acc * inner.clone() + Polynomial::from(c.clone())
+        });
+    }
+    // p = p_0 + x^h p_1 with h the largest power of two below the length
+    let k = (coeffs.len() - 1).ilog2() as usize;
+    let (lo, hi) = coeffs.split_at(1 << k);
+    compose_rec(lo, inner, powers) + compose_rec(hi, inner, powers) * powers[k].clone()
+}
+
+impl<F: Field> Polynomial<F> {
+    /// Evaluates the polynomial at all the `points`. Many points are evaluated at once by
+    /// reducing the polynomial modulo a subproduct tree of the `x - x_i`, with
+    /// [`Polynomial::fast_div_rem`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// let p = Polynomial::new(vec![n(-1), n(0), n(1)]);
+    /// assert_eq!(p.eval_many(&[n(0), n(1), n(2)]), [n(-1), n(0), n(3)]);
+    /// ```
+    pub fn eval_many(&self, points: &[F]) -> Vec<F> {
+        if points.len() <= HORNER_THRESHOLD {
+            return points.iter().map(|x| self.eval(x)).collect();
+        }
+
+        // tree[k][j] is the product of the x - x_i for the points of the j-th block of 2^k points
+        let leaves = points
+            .iter()
+            .map(|x| Polynomial::new(vec![-x.clone(), F::one()]))
+            .collect::<Vec<_>>();
+        let mut tree = vec![leaves];
+        while tree.last().unwrap().len() > 1 {
+            let level = tree
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| pair.iter().cloned().reduce(|a, b| a * b).unwrap())
+                .collect();
+            tree.push(level);
+        }
+
+        let mut values = Vec::with_capacity(points.len());
+        let root = self.clone().fast_div_rem(tree.last().unwrap()[0].clone()).1;
+        eval_tree(root, &tree, tree.len() - 1, 0, points, &mut values);
+        values
+    }
+}
+
+/// Pushes the values of `p` at the points of the `j`-th block of `2^k` points to `values`, given
+/// `p` modulo `tree[k][j]`.
+fn eval_tree<F: Field>(
+    p: Polynomial<F>,
+    tree: &[Vec<Polynomial<F>>],
+    k: usize,
+    j: usize,
+    points: &[F],
+    values: &mut Vec<F>,
+) {
+    if 1 << k <= HORNER_THRESHOLD {
+        let block = &points[j << k..((j + 1) << k).min(points.len())];
+        values.extend(block.iter().map(|x| p.eval(x)));
+        return;
+    }
+    for child in [2 * j, 2 * j + 1] {
+        if let Some(m) = tree[k - 1].get(child) {
+            let r = p.clone().fast_div_rem(m.clone()).1;
+            eval_tree(r, tree, k - 1, child, points, values);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Prime fields.
+
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::traits::{Characteristic, CheckedInv, CommutativeRing, Field, FromUsize};
+
+/// The field of integers modulo the prime `P` (`GF(p)`), which must be less than `2^32`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Fp<const P: u64>(u64);
+
+impl<const P: u64> Fp<P> {
+    pub fn new(n: i64) -> Self {
+        Self(n.rem_euclid(P as i64) as u64)
+    }
+
+    /// The representative in `[0, P)`.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self((self.0 + rhs.0) % P)
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self((self.0 + P - rhs.0) % P)
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0 % P)
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self((P - self.0) % P)
+    }
+}
+
+impl<const P: u64> Div for Fp<P> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let inv = rhs.checked_inv().expect("division by zero");
+        Self(self.0 * inv.0 % P)
+    }
+}
+
+impl<const P: u64> Zero for Fp<P> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> One for Fp<P> {
+    fn one() -> Self {
+        Self(1 % P)
+    }
+}
+
+impl<const P: u64> CheckedInv for Fp<P> {
+    fn checked_inv(&self) -> Option<Self> {
+        // Fermat's little theorem
+        (!self.is_zero()).then(|| num::pow(*self, (P - 2) as usize))
+    }
+}
+
+impl<const P: u64> FromUsize for Fp<P> {
+    fn from_usize(n: usize) -> Self {
+        Self((n as u64) % P)
+    }
+}
+
+impl<const P: u64> Display for Fp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The ring of integers modulo `P`
+impl<const P: u64> CommutativeRing for Fp<P> {}
+
+/// The field of integers modulo `P`
+impl<const P: u64> Field for Fp<P> {}
+
+impl<const P: u64> Characteristic for Fp<P> {
+    fn characteristic() -> u64 {
+        P
+    }
+}
@@ -0,0 +1,228 @@
+//! Fields of fractions of Euclidean domains, like the rational functions over a field.
+//!
+//! A [`Fraction`] is kept in lowest terms with a normalized denominator (see
+//! [`GcdDomain::normalize`]), so that equal fractions have equal representations and can be
+//! compared with `==`. Every operation reduces its result with a gcd.
+//!
+//! [`GcdDomain::normalize`]: crate::traits::GcdDomain::normalize
+
+use std::fmt::{self, Debug};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::traits::{
+    Characteristic, CheckedInv, CommutativeRing, EuclideanDomain, Field, FromUsize,
+};
+use crate::Polynomial;
+
+/// The fraction `numerator / denominator` of elements of a Euclidean domain `R`, in lowest
+/// terms with a normalized denominator: positive for the integers, monic for polynomials over a
+/// field.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Fraction<R> {
+    numerator: R,
+    denominator: R,
+}
+
+/// A quotient of polynomials over `F` (`F(x)`), with a monic denominator.
+pub type RationalFunction<F> = Fraction<Polynomial<F>>;
+
+impl<R: EuclideanDomain> Fraction<R> {
+    /// `numerator / denominator` in lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// If `denominator` is zero.
+    pub fn new(numerator: R, denominator: R) -> Self {
+        assert!(!denominator.is_zero(), "zero denominator");
+        let divide = |x: R, by: &R| x.exact_div(by).expect("the gcd divides both elements");
+        let g = numerator.gcd(&denominator);
+        let (numerator, denominator) = (divide(numerator, &g), divide(denominator, &g));
+        let unit = denominator.unit();
+        Self {
+            numerator: divide(numerator, &unit),
+            denominator: divide(denominator, &unit),
+        }
+    }
+
+    pub fn numerator(&self) -> &R {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &R {
+        &self.denominator
+    }
+
+    /// The numerator and the denominator.
+    pub fn into_parts(self) -> (R, R) {
+        (self.numerator, self.denominator)
+    }
+
+    /// `self^k`. The powers of coprime elements are coprime, so this needs no gcd.
+    pub fn pow(&self, k: usize) -> Self {
+        Self {
+            numerator: num::pow(self.numerator.clone(), k),
+            denominator: num::pow(self.denominator.clone(), k),
+        }
+    }
+}
+
+impl<F: Field> RationalFunction<F> {
+    /// The rational function `x`.
+    pub fn x() -> Self {
+        Self::from(Polynomial::new(vec![F::zero(), F::one()]))
+    }
+
+    pub fn constant(c: F) -> Self {
+        Self::from(Polynomial::from(c))
+    }
+
+    /// The value of the function if it is a constant.
+    pub fn to_constant(&self) -> Option<F> {
+        match (self.numerator.degree(), self.denominator.degree()) {
+            (None, _) => Some(F::zero()),
+            (Some(0), Some(0)) => Some(self.numerator.coeff_at(0).clone()),
+            _ => None,
+        }
+    }
+
+    /// The exact value of the function at `a`, `None` if `a` is a pole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::fraction::RationalFunction;
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigRational;
+    /// let q = |n: i32, d: i32| BigRational::new(n.into(), d.into());
+    /// // (x + 1) / (x^2 - 4)
+    /// let f = RationalFunction::new(
+    ///     Polynomial::new(vec![q(1, 1), q(1, 1)]),
+    ///     Polynomial::new(vec![q(-4, 1), q(0, 1), q(1, 1)]),
+    /// );
+    /// assert_eq!(f.eval(&q(1, 2)), Some(q(-2, 5)));
+    /// assert_eq!(f.eval(&q(2, 1)), None);
+    /// ```
+    pub fn eval(&self, a: &F) -> Option<F> {
+        let denominator = self.denominator.eval(a).checked_inv()?;
+        Some(self.numerator.eval(a) * denominator)
+    }
+
+    /// The derivative with respect to `x`.
+    pub fn derivative(&self) -> Self
+    where
+        F: FromUsize,
+    {
+        let (n, d) = (self.numerator.clone(), self.denominator.clone());
+        Self::new(
+            n.clone().derivative() * d.clone() - n * d.clone().derivative(),
+            d.clone() * d,
+        )
+    }
+}
+
+impl<R: Debug> Debug for Fraction<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({:?}) / ({:?})", self.numerator, self.denominator)
+    }
+}
+
+/// The fraction `x / 1`.
+impl<R: EuclideanDomain> From<R> for Fraction<R> {
+    fn from(x: R) -> Self {
+        Self {
+            numerator: x,
+            denominator: R::one(),
+        }
+    }
+}
+
+impl<R: EuclideanDomain> Add for Fraction<R> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator.clone() + other.numerator * self.denominator.clone(),
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl<R: EuclideanDomain> Sub for Fraction<R> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        self + other.neg()
+    }
+}
+
+impl<R: EuclideanDomain> Mul for Fraction<R> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl<R: EuclideanDomain> Div for Fraction<R> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.checked_inv().expect("division by zero")
+    }
+}
+
+impl<R: EuclideanDomain> Neg for Fraction<R> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<R: EuclideanDomain> Zero for Fraction<R> {
+    fn zero() -> Self {
+        Self::from(R::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+}
+
+impl<R: EuclideanDomain> One for Fraction<R> {
+    fn one() -> Self {
+        Self::from(R::one())
+    }
+}
+
+impl<R: EuclideanDomain> CheckedInv for Fraction<R> {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Self::new(self.denominator.clone(), self.numerator.clone()))
+        }
+    }
+}
+
+impl<R: EuclideanDomain + FromUsize> FromUsize for Fraction<R> {
+    fn from_usize(n: usize) -> Self {
+        Self::from(R::from_usize(n))
+    }
+}
+
+impl<R: EuclideanDomain> CommutativeRing for Fraction<R> {}
+
+/// The field of fractions of a Euclidean domain
+impl<R: EuclideanDomain> Field for Fraction<R> {}
+
+impl<R: Characteristic> Characteristic for Fraction<R> {
+    fn characteristic() -> u64 {
+        R::characteristic()
+    }
+}
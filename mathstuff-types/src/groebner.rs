@@ -0,0 +1,361 @@
+//! Gröbner bases of ideals of multivariate polynomials over a field.
+//!
+//! [`GroebnerBasis::buchberger`] is Buchberger's algorithm with the product and chain criteria,
+//! and [`GroebnerBasis::f4`] is Faugère's F4 algorithm, which reduces all the S-polynomials of
+//! the same degree at once by Gaussian elimination. Both return the reduced Gröbner basis, which
+//! only depends on the ideal and the monomial order.
+
+use std::collections::{HashMap, HashSet};
+
+use num::{One, Zero};
+
+use crate::multi::MultiPolynomial;
+use crate::traits::Field;
+
+fn divides(a: &[usize], b: &[usize]) -> bool {
+    a.iter().zip(b).all(|(i, j)| i <= j)
+}
+
+fn lcm(a: &[usize], b: &[usize]) -> Vec<usize> {
+    a.iter().zip(b).map(|(i, j)| *i.max(j)).collect()
+}
+
+/// `b / a` for monomials where `a` divides `b`.
+fn quotient(b: &[usize], a: &[usize]) -> Vec<usize> {
+    b.iter().zip(a).map(|(i, j)| i - j).collect()
+}
+
+impl<F: Field + PartialEq> MultiPolynomial<F> {
+    /// `self` divided by its leading coefficient.
+    #[must_use]
+    pub fn monic(self) -> Self {
+        match self.leading_coefficient().and_then(F::checked_inv) {
+            Some(lc) => self.scalar_mul(lc),
+            None => self,
+        }
+    }
+
+    /// Divides `self` by `divisors` with the multivariate division algorithm. Returns the
+    /// quotients `q_i` and the remainder `r` such that `self = Σ q_i d_i + r`, where no term of
+    /// `r` is divisible by the leading monomial of any `d_i`.
+    pub fn div_rem_many(&self, divisors: &[Self]) -> (Vec<Self>, Self) {
+        // the union of the variables of `self` and all the divisors, in the order of `self`
+        let template = divisors
+            .iter()
+            .fold(self.with_terms(vec![]), |t, d| t.unify(d.with_terms(vec![])).0)
+            .with_order(self.order());
+        let mut p = template.clone().unify(self.clone()).1;
+        let divisors = divisors
+            .iter()
+            .map(|d| template.clone().unify(d.clone()).1)
+            .collect::<Vec<_>>();
+
+        let mut quotients = vec![p.with_terms(vec![]); divisors.len()];
+        let mut remainder = vec![];
+        while let Some((lm, lc)) = p.leading_term() {
+            let lm = lm.to_vec();
+            match divisors
+                .iter()
+                .enumerate()
+                .find(|(_, d)| d.leading_monomial().is_some_and(|d| divides(d, &lm)))
+            {
+                Some((i, d)) => {
+                    let (dm, dc) = d.leading_term().unwrap();
+                    let m = quotient(&lm, dm);
+                    let c = lc.clone() / dc.clone();
+                    quotients[i] += p.with_terms(vec![(m.clone(), c.clone())]);
+                    p = p - d.clone().mul_term(&m, c);
+                }
+                None => remainder.push(p.terms.remove(0)),
+            }
+        }
+        (quotients, p.with_terms(remainder))
+    }
+
+    /// The remainder of `self` by `divisors`, see [`MultiPolynomial::div_rem_many`].
+    pub fn normal_form(&self, divisors: &[Self]) -> Self {
+        self.div_rem_many(divisors).1
+    }
+
+    /// The S-polynomial of the nonzero `self` and `other`, the combination of them that
+    /// cancels their leading terms.
+    pub fn s_polynomial(&self, other: &Self) -> Self {
+        let (a, b) = self.clone().unify(other.clone());
+        let (am, ac) = a.leading_term().unwrap();
+        let (bm, bc) = b.leading_term().unwrap();
+        let l = lcm(am, bm);
+        let (am, bm) = (quotient(&l, am), quotient(&l, bm));
+        let (ac, bc) = (ac.checked_inv().unwrap(), bc.checked_inv().unwrap());
+        a.clone().mul_term(&am, ac) - b.clone().mul_term(&bm, bc)
+    }
+}
+
+/// A reduced Gröbner basis of an ideal, sorted by decreasing leading monomial.
+#[derive(Clone, Debug)]
+pub struct GroebnerBasis<F> {
+    polys: Vec<MultiPolynomial<F>>,
+}
+
+impl<F: Field + PartialEq> PartialEq for GroebnerBasis<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.polys == other.polys
+    }
+}
+
+/// The nonzero `generators` as monic polynomials in the union of their variables.
+fn prepare<F: Field + PartialEq>(generators: &[MultiPolynomial<F>]) -> Vec<MultiPolynomial<F>> {
+    let template = generators.iter().fold(MultiPolynomial::zero(), |t, g| {
+        t.unify(g.with_terms(vec![])).0
+    });
+    generators
+        .iter()
+        .filter(|g| !g.is_zero())
+        .map(|g| template.clone().unify(g.clone()).1.monic())
+        .collect()
+}
+
+/// Pairs of indices of basis elements, the smaller index first.
+type Pair = (usize, usize);
+
+/// Whether the S-polynomial of `(i, j)` need not be reduced: either the leading monomials are
+/// coprime, or some other element's leading monomial divides their lcm and the pairs with it
+/// are not `pending`.
+fn skip_pair<F: Field>(
+    (i, j): Pair,
+    basis: &[MultiPolynomial<F>],
+    pending: impl Fn(Pair) -> bool,
+) -> bool {
+    let lm = |k: usize| basis[k].leading_monomial().unwrap();
+    let (a, b) = (lm(i), lm(j));
+    if a.iter().zip(b).all(|(x, y)| *x == 0 || *y == 0) {
+        return true;
+    }
+    let l = lcm(a, b);
+    let pair = |x: usize, y: usize| (x.min(y), x.max(y));
+    (0..basis.len()).any(|k| {
+        k != i && k != j && divides(lm(k), &l) && !pending(pair(i, k)) && !pending(pair(j, k))
+    })
+}
+
+impl<F: Field + PartialEq> GroebnerBasis<F> {
+    /// Computes the reduced Gröbner basis of the ideal generated by `generators` with
+    /// Buchberger's algorithm, with respect to their monomial order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mathstuff_types::groebner::GroebnerBasis;
+    /// use mathstuff_types::{MonomialOrder, MultiPolynomial};
+    /// use num::BigRational;
+    ///
+    /// let [x, y] = MultiPolynomial::<BigRational>::generators(&["x", "y"], MonomialOrder::Lex);
+    /// let one = MultiPolynomial::constant(BigRational::from_integer(1.into()));
+    /// let half = MultiPolynomial::constant(BigRational::new(1.into(), 2.into()));
+    ///
+    /// // the circle intersected with the diagonal
+    /// let basis = GroebnerBasis::buchberger(&[
+    ///     x.clone().pow(2) + y.clone().pow(2) - one,
+    ///     x.clone() - y.clone(),
+    /// ]);
+    /// assert_eq!(basis.polynomials(), [x.clone() - y.clone(), y.clone().pow(2) - half]);
+    /// assert!(basis.contains(&(x.clone() * y.clone() - y.clone().pow(2))));
+    /// assert!(!basis.contains(&x));
+    /// ```
+    pub fn buchberger(generators: &[MultiPolynomial<F>]) -> Self {
+        let mut basis = prepare(generators);
+        let mut pairs = (0..basis.len())
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .collect::<Vec<Pair>>();
+
+        let lcm_of = |basis: &[MultiPolynomial<F>], (i, j): Pair| {
+            lcm(
+                basis[i].leading_monomial().unwrap(),
+                basis[j].leading_monomial().unwrap(),
+            )
+        };
+
+        // the normal strategy: the pair with the smallest lcm first
+        while let Some(next) = (0..pairs.len()).min_by(|&a, &b| {
+            let order = basis[0].order();
+            order.compare(&lcm_of(&basis, pairs[a]), &lcm_of(&basis, pairs[b]))
+        }) {
+            let pair = pairs.swap_remove(next);
+            if skip_pair(pair, &basis, |p| pairs.contains(&p)) {
+                continue;
+            }
+            let s = basis[pair.0]
+                .s_polynomial(&basis[pair.1])
+                .normal_form(&basis);
+            if !s.is_zero() {
+                let k = basis.len();
+                pairs.extend((0..k).map(|i| (i, k)));
+                basis.push(s.monic());
+            }
+        }
+
+        Self::reduce(basis)
+    }
+
+    /// Computes the reduced Gröbner basis of the ideal generated by `generators` with the F4
+    /// algorithm, with respect to their monomial order.
+    pub fn f4(generators: &[MultiPolynomial<F>]) -> Self {
+        let mut basis = prepare(generators);
+        let mut pairs = (0..basis.len())
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .collect::<Vec<Pair>>();
+
+        while !pairs.is_empty() {
+            let lcm_of = |(i, j): Pair| {
+                lcm(
+                    basis[i].leading_monomial().unwrap(),
+                    basis[j].leading_monomial().unwrap(),
+                )
+            };
+
+            // the pairs with the lcms of the smallest total degree
+            let degree = |p: Pair| lcm_of(p).iter().sum::<usize>();
+            let d = pairs.iter().map(|&p| degree(p)).min().unwrap();
+            let (selected, rest): (Vec<_>, Vec<_>) =
+                pairs.into_iter().partition(|&p| degree(p) == d);
+            pairs = rest;
+            let selected = selected
+                .iter()
+                .copied()
+                .filter(|&p| !skip_pair(p, &basis, |q| pairs.contains(&q) || selected.contains(&q)))
+                .collect::<Vec<_>>();
+
+            // the multiples of the basis elements whose leading terms cancel in the
+            // S-polynomials
+            let mut rows = HashSet::<(Vec<usize>, usize)>::new();
+            for (i, j) in selected {
+                let l = lcm_of((i, j));
+                for k in [i, j] {
+                    rows.insert((quotient(&l, basis[k].leading_monomial().unwrap()), k));
+                }
+            }
+            let mut rows = rows
+                .into_iter()
+                .map(|(m, k)| basis[k].clone().mul_term(&m, F::one()))
+                .collect::<Vec<_>>();
+
+            // symbolic preprocessing: add a reducer for each monomial divisible by a leading
+            // monomial of the basis
+            let mut done = rows
+                .iter()
+                .map(|r| r.leading_monomial().unwrap().to_vec())
+                .collect::<HashSet<_>>();
+            let mut i = 0;
+            while i < rows.len() {
+                let monomials = rows[i].terms().map(|(m, _)| m.to_vec()).collect::<Vec<_>>();
+                for m in monomials {
+                    if !done.insert(m.clone()) {
+                        continue;
+                    }
+                    if let Some(g) = basis
+                        .iter()
+                        .find(|g| divides(g.leading_monomial().unwrap(), &m))
+                    {
+                        let q = quotient(&m, g.leading_monomial().unwrap());
+                        rows.push(g.clone().mul_term(&q, F::one()));
+                    }
+                }
+                i += 1;
+            }
+
+            // Gaussian elimination into row echelon form, the rows with new leading monomials
+            // extend the basis
+            let leading_monomials = rows
+                .iter()
+                .map(|r| r.leading_monomial().unwrap().to_vec())
+                .collect::<HashSet<_>>();
+            let order = basis[0].order();
+            rows.sort_by(|a, b| {
+                order.compare(b.leading_monomial().unwrap(), a.leading_monomial().unwrap())
+            });
+            let mut pivots = HashMap::<Vec<usize>, MultiPolynomial<F>>::new();
+            let mut new = vec![];
+            for mut row in rows {
+                while let Some((lm, lc)) = row.leading_term() {
+                    let Some(pivot) = pivots.get(lm) else { break };
+                    let lc = lc.clone();
+                    row = row - pivot.clone().scalar_mul(lc);
+                }
+                let Some(lm) = row.leading_monomial().map(<[usize]>::to_vec) else {
+                    continue;
+                };
+                let row = row.monic();
+                if !leading_monomials.contains(&lm) {
+                    new.push(row.clone());
+                }
+                pivots.insert(lm, row);
+            }
+
+            for h in new {
+                let k = basis.len();
+                pairs.extend((0..k).map(|i| (i, k)));
+                basis.push(h);
+            }
+        }
+
+        Self::reduce(basis)
+    }
+
+    /// Turns a Gröbner basis of monic polynomials into the reduced one.
+    fn reduce(basis: Vec<MultiPolynomial<F>>) -> Self {
+        let lm = |p: &MultiPolynomial<F>| p.leading_monomial().unwrap().to_vec();
+
+        // remove the elements whose leading monomial is a multiple of another's, keeping the
+        // first one of those with equal leading monomials
+        let mut polys = basis
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| {
+                !basis
+                    .iter()
+                    .enumerate()
+                    .any(|(j, q)| j != *i && divides(&lm(q), &lm(p)) && (lm(q) != lm(p) || j < *i))
+            })
+            .map(|(_, p)| p.clone())
+            .collect::<Vec<_>>();
+
+        for i in 0..polys.len() {
+            let others = polys
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, p)| p.clone())
+                .collect::<Vec<_>>();
+            polys[i] = polys[i].normal_form(&others).monic();
+        }
+
+        if let Some(order) = polys.first().map(MultiPolynomial::order) {
+            polys.sort_by(|a, b| order.compare(&lm(b), &lm(a)));
+        }
+        Self { polys }
+    }
+
+    /// The polynomials of the basis, by decreasing leading monomial.
+    pub fn polynomials(&self) -> &[MultiPolynomial<F>] {
+        &self.polys
+    }
+
+    pub fn into_polynomials(self) -> Vec<MultiPolynomial<F>> {
+        self.polys
+    }
+
+    /// The normal form of `p` modulo the ideal, which is zero exactly when `p` lies in it.
+    pub fn normal_form(&self, p: &MultiPolynomial<F>) -> MultiPolynomial<F> {
+        p.normal_form(&self.polys)
+    }
+
+    /// Whether `p` lies in the ideal.
+    pub fn contains(&self, p: &MultiPolynomial<F>) -> bool {
+        self.normal_form(p).is_zero()
+    }
+
+    /// Whether the ideal is the whole ring, that is whether the polynomials have no common
+    /// zero over the algebraic closure.
+    pub fn is_unit_ideal(&self) -> bool {
+        self.contains(&MultiPolynomial::one())
+    }
+}
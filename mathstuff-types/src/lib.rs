@@ -4,10 +4,33 @@ use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
 
 use num::{One, Zero};
 
+pub mod arith;
+pub mod eval;
 pub mod traits;
 pub mod print;
-
-use traits::{CommutativeRing, Field, FromUsize};
+pub mod parse;
+pub mod multi;
+pub mod groebner;
+pub mod finite_field;
+pub mod fraction;
+pub mod matrix;
+pub mod prs;
+
+pub use multi::{MonomialOrder, MultiPolynomial};
+
+use traits::{
+    Characteristic, CheckedInv, CommutativeRing, EuclideanDomain, Field, FromUsize, GcdDomain,
+    IntegralDomain, UniqueFactorizationDomain,
+};
+
+/// The reason a polynomial division failed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DivisionError {
+    DivisionByZero,
+    /// The leading coefficient of the divisor does not divide a leading coefficient of the
+    /// remainder, so there is no remainder of lower degree.
+    NotDivisible,
+}
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Polynomial<Ring> {
@@ -123,55 +146,233 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
     }
 
     /// Performs polynomial division, returns a (quotient, remainder) tuple.
+    ///
+    /// The coefficients are divided with `/`, so this is only correct over a field. See
+    /// [`Self::checked_div_rem`] and [`Self::pseudo_div_rem`] for other rings, and
+    /// [`Self::fast_div_rem`] for long polynomials over a field, which `%` and
+    /// [`EuclideanDomain::div_rem`] use.
+    ///
+    /// # Panics
+    ///
+    /// If `other` is zero.
     pub fn div_rem(self, other: Polynomial<Ring>) -> (Polynomial<Ring>, Polynomial<Ring>)
     where
         Ring: Div<Ring, Output = Ring>,
     {
-        let mut quotient = Polynomial::zero();
-        let mut remainder = self;
+        if self.is_zero() {
+            return (Polynomial::zero(), self);
+        }
+        let n = other.degree().expect("division by the zero polynomial");
+        let mut remainder = self.coeffs;
+        if remainder.len() <= n {
+            return (Polynomial::zero(), Polynomial::new(remainder));
+        }
 
-        let Some(mut m) = remainder.degree() else {
-            // remainder is zero, just return (0, 0).
-            return (quotient, remainder);
-        };
+        // eliminate the leading coefficients of the remainder in place, from the highest
+        let lcv = other.leading_coefficient_cloned();
+        let mut quotient = vec![Ring::zero(); remainder.len() - n];
+        for k in (0..quotient.len()).rev() {
+            let lcr = std::mem::replace(&mut remainder[k + n], Ring::zero());
+            if lcr.is_zero() {
+                continue;
+            }
+            let s = lcr / lcv.clone();
+            for (r, c) in remainder[k..k + n].iter_mut().zip(&other.coeffs) {
+                let old = std::mem::replace(r, Ring::zero());
+                *r = CommutativeRing::sub(old, s.clone() * c.clone());
+            }
+            quotient[k] = s;
+        }
+        remainder.truncate(n);
 
-        let n = other.degree().unwrap();
+        (
+            Polynomial::new_trim_zeroes(quotient),
+            Polynomial::new_trim_zeroes(remainder),
+        )
+    }
 
+    /// Performs polynomial division, returns a (quotient, remainder) tuple with the remainder of
+    /// lower degree than `other`.
+    ///
+    /// Fails if `other` is zero, or if a leading coefficient of the remainder is not a multiple
+    /// of the leading coefficient of `other`, like when dividing `x^2` by `2x + 1` over the
+    /// integers. Division by a polynomial whose leading coefficient is a unit never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::{DivisionError, Polynomial};
+    /// # use num::BigInt;
+    /// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    /// // x^2 + 3x + 5 = (x + 2)(x + 1) + 3
+    /// assert_eq!(p(&[5, 3, 1]).checked_div_rem(p(&[1, 1])), Ok((p(&[2, 1]), p(&[3]))));
+    /// assert_eq!(p(&[0, 0, 1]).checked_div_rem(p(&[1, 2])), Err(DivisionError::NotDivisible));
+    /// assert_eq!(p(&[1]).checked_div_rem(p(&[])), Err(DivisionError::DivisionByZero));
+    /// ```
+    pub fn checked_div_rem(self, other: Self) -> Result<(Self, Self), DivisionError>
+    where
+        Ring: Div<Ring, Output = Ring> + PartialEq,
+    {
+        let n = other.degree().ok_or(DivisionError::DivisionByZero)?;
         let lcv = other.leading_coefficient_cloned();
+        let mut quotient = Polynomial::zero();
+        let mut remainder = self;
 
-        while m >= n {
+        while let Some(m) = remainder.degree().filter(|m| *m >= n) {
             let lcr = remainder.leading_coefficient_cloned();
             let s = lcr.clone() / lcv.clone();
-            quotient += Polynomial::from_elem_with_degree(s.clone(), m - n);
-            let poly = (other.clone() - Polynomial::from_elem_with_degree(lcv.clone(), n))
-                .scalar_mul(s)
-                .raised_by(m - n);
-            remainder = (remainder - Polynomial::from_elem_with_degree(lcr, m)) - poly;
-            m = remainder.degree().unwrap_or(0);
+            if s.clone() * lcv.clone() != lcr {
+                return Err(DivisionError::NotDivisible);
+            }
+            let term = Polynomial::from_elem_with_degree(s, m - n);
+            remainder = remainder - term.clone() * other.clone();
+            quotient += term;
         }
 
-        (quotient, remainder)
+        Ok((quotient, remainder))
+    }
+
+    /// The quotient of `self` by `other`, if `other` divides `self`.
+    ///
+    /// `None` if `other` is zero or does not divide `self`.
+    pub fn exact_div(self, other: Self) -> Option<Self>
+    where
+        Ring: Div<Ring, Output = Ring> + PartialEq,
+    {
+        self.checked_div_rem(other)
+            .ok()
+            .and_then(|(q, r)| r.is_zero().then_some(q))
+    }
+
+    /// Pseudo-division, which works over any integral domain. Returns `(q, r)` such that
+    /// `lc^δ self = q other + r`, where `lc` is the leading coefficient of `other`, the remainder
+    /// `r` has lower degree than `other`, and `δ = max(deg self - deg other + 1, 0)`.
+    ///
+    /// # Panics
+    ///
+    /// If `other` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigInt;
+    /// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    /// // 2^2 (x^2 + 1) = (2x - 1)(2x + 1) + 5
+    /// assert_eq!(p(&[1, 0, 1]).pseudo_div_rem(p(&[1, 2])), (p(&[-1, 2]), p(&[5])));
+    /// ```
+    pub fn pseudo_div_rem(self, other: Self) -> (Self, Self) {
+        let n = other.degree().expect("division by the zero polynomial");
+        let lc = other.leading_coefficient_cloned();
+        let Some(mut e) = self.degree().and_then(|m| (m + 1).checked_sub(n)) else {
+            return (Polynomial::zero(), self);
+        };
+
+        let mut quotient = Polynomial::zero();
+        let mut remainder = self;
+        while let Some(m) = remainder.degree().filter(|m| *m >= n) {
+            let term =
+                Polynomial::from_elem_with_degree(remainder.leading_coefficient_cloned(), m - n);
+            quotient = quotient.scalar_mul(lc.clone()) + term.clone();
+            remainder = remainder.scalar_mul(lc.clone()) - term * other.clone();
+            e -= 1;
+        }
+
+        let scale = num::pow(lc, e);
+        (
+            quotient.scalar_mul(scale.clone()),
+            remainder.scalar_mul(scale),
+        )
+    }
+
+    /// The gcd of `self` and `other`, in the normal form of [`GcdDomain::normalize`]: *monic*
+    /// over a field, with a positive leading coefficient over the integers.
+    pub fn gcd(self, other: Self) -> Self
+    where
+        Ring: GcdDomain,
+    {
+        GcdDomain::gcd(&self, &other)
     }
 
-    /// Returns a *monic* polynomial that is a factor in both `self` and `other`.
-    pub fn gcd(mut self, mut other: Self) -> Self
+    /// The extended Euclidean algorithm. Returns `(g, s, t)` such that `s * self + t * other = g`,
+    /// where `g` is the *monic* gcd of `self` and `other`.
+    ///
+    /// If both polynomials are zero, all three returned polynomials are zero.
+    pub fn gcd_ext(self, other: Self) -> (Self, Self, Self)
     where
         Ring: Field,
     {
-        if self.is_zero() && other.is_zero() {
-            return Polynomial::zero();
+        let (mut r0, mut r1) = (self, other);
+        let (mut s0, mut s1) = (Polynomial::one(), Polynomial::zero());
+        let (mut t0, mut t1) = (Polynomial::zero(), Polynomial::one());
+
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(r1.clone());
+            r0 = std::mem::replace(&mut r1, r);
+            let s = s0 - q.clone() * s1.clone();
+            s0 = std::mem::replace(&mut s1, s);
+            let t = t0 - q * t1.clone();
+            t0 = std::mem::replace(&mut t1, t);
         }
-        while !other.is_zero() {
-            let r = self.clone().div_rem(other.clone()).1;
-            self = other;
-            other = r;
+
+        let Some(lc) = r0.leading_coefficient().and_then(CheckedInv::checked_inv) else {
+            return (Polynomial::zero(), Polynomial::zero(), Polynomial::zero());
+        };
+
+        (
+            r0.scalar_mul(lc.clone()),
+            s0.scalar_mul(lc.clone()),
+            t0.scalar_mul(lc),
+        )
+    }
+
+    /// The inverse of `self` modulo `modulus`, that is the polynomial `s` of lower degree than
+    /// `modulus` such that `s * self - 1` is a multiple of `modulus`.
+    ///
+    /// `None` if `modulus` is zero or `self` and `modulus` are not coprime.
+    pub fn inverse_mod(self, modulus: &Self) -> Option<Self>
+    where
+        Ring: Field + PartialEq,
+    {
+        if modulus.is_zero() {
+            return None;
         }
-        let lc = self
-            .leading_coefficient_cloned()
-            .clone()
-            .checked_inv()
-            .unwrap();
-        self.scalar_mul(lc)
+        let (g, s, _) = self.gcd_ext(modulus.clone());
+        g.is_one().then(|| s % modulus.clone())
+    }
+
+    /// The Chinese remainder theorem. Given pairs `(r_i, m_i)` of residues and pairwise coprime
+    /// moduli, returns the unique polynomial `p` of lower degree than `Π m_i` such that
+    /// `p - r_i` is a multiple of `m_i` for every `i`.
+    ///
+    /// `None` if a modulus is zero or two moduli are not coprime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigRational;
+    /// let p = |coeffs: &[i32]| {
+    ///     Polynomial::new(coeffs.iter().map(|&c| BigRational::from_integer(c.into())).collect())
+    /// };
+    /// // p(1) = 2, p(-1) = 0 and p(0) = 1
+    /// let residues = [(p(&[2]), p(&[-1, 1])), (p(&[]), p(&[1, 1])), (p(&[1]), p(&[0, 1]))];
+    /// assert_eq!(Polynomial::chinese_remainder(&residues), Some(p(&[1, 1])));
+    /// ```
+    pub fn chinese_remainder(residues: &[(Self, Self)]) -> Option<Self>
+    where
+        Ring: Field + PartialEq,
+    {
+        let mut result = Polynomial::zero();
+        let mut product = Polynomial::one();
+        for (r, m) in residues {
+            // result + product * k ≡ r (mod m)
+            let inv = product.clone().inverse_mod(m)?;
+            let k = (r.clone() - result.clone()) * inv % m.clone();
+            result += product.clone() * k;
+            product = product * m.clone();
+        }
+        Some(result)
     }
 }
 
@@ -181,6 +382,19 @@ impl<Ring: CommutativeRing> FromIterator<Ring> for Polynomial<Ring> {
     }
 }
 
+/// The constant polynomial.
+impl<Ring: CommutativeRing> From<Ring> for Polynomial<Ring> {
+    fn from(c: Ring) -> Self {
+        Self::new_trim_zeroes(vec![c])
+    }
+}
+
+impl<Ring: CommutativeRing + FromUsize> FromUsize for Polynomial<Ring> {
+    fn from_usize(n: usize) -> Self {
+        Self::from(Ring::from_usize(n))
+    }
+}
+
 impl<Ring: CommutativeRing> Zero for Polynomial<Ring> {
     fn is_zero(&self) -> bool {
         self.coeffs.is_empty()
@@ -236,20 +450,11 @@ impl<Ring: CommutativeRing> AddAssign for Polynomial<Ring> {
     }
 }
 
+/// Multiplies with [`CommutativeRing::mul_slices`].
 impl<Ring: CommutativeRing> Mul for Polynomial<Ring> {
     type Output = Polynomial<Ring>;
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut coeffs = Vec::new();
-        for (i, a) in self.coeffs.into_iter().enumerate() {
-            let mut new_coeffs = repeat_with(Ring::zero).take(i).collect::<Vec<_>>();
-            for b in rhs.coeffs.iter().cloned() {
-                new_coeffs.push(a.clone() * b);
-            }
-            coeffs.push(Polynomial::new(new_coeffs));
-        }
-        coeffs
-            .into_iter()
-            .fold(Polynomial::new(vec![]), |a, b| a + b)
+        Polynomial::new_trim_zeroes(Ring::mul_slices(&self.coeffs, &rhs.coeffs))
     }
 }
 
@@ -288,4 +493,45 @@ impl<Ring: CommutativeRing> Neg for Polynomial<Ring> {
 }
 
 /// The ring of polynomials over a ring (`R[x]`)
-impl<Ring: CommutativeRing> CommutativeRing for Polynomial<Ring> {}
\ No newline at end of file
+impl<Ring: CommutativeRing> CommutativeRing for Polynomial<Ring> {}
+/// Polynomials over an integral domain have no zero divisors either, since the product of the
+/// leading coefficients is the leading coefficient of the product.
+impl<Ring: IntegralDomain> IntegralDomain for Polynomial<Ring> {
+    fn exact_div(&self, other: &Self) -> Option<Self> {
+        let n = other.degree()?;
+        let lcv = other.leading_coefficient_cloned();
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![Ring::zero(); remainder.len().saturating_sub(n)];
+        for k in (0..quotient.len()).rev() {
+            let lcr = std::mem::replace(&mut remainder[k + n], Ring::zero());
+            if lcr.is_zero() {
+                continue;
+            }
+            let s = lcr.exact_div(&lcv)?;
+            for (r, c) in remainder[k..k + n].iter_mut().zip(&other.coeffs) {
+                let old = std::mem::replace(r, Ring::zero());
+                *r = CommutativeRing::sub(old, s.clone() * c.clone());
+            }
+            quotient[k] = s;
+        }
+        remainder
+            .iter()
+            .all(Zero::is_zero)
+            .then(|| Polynomial::new_trim_zeroes(quotient))
+    }
+}
+
+impl<Ring: UniqueFactorizationDomain> UniqueFactorizationDomain for Polynomial<Ring> {}
+
+/// The ring of polynomials over a field (`F[x]`), where the remainders have lower degree.
+impl<F: Field> EuclideanDomain for Polynomial<F> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        self.clone().fast_div_rem(other.clone())
+    }
+}
+
+impl<Ring: Characteristic> Characteristic for Polynomial<Ring> {
+    fn characteristic() -> u64 {
+        Ring::characteristic()
+    }
+}
@@ -0,0 +1,679 @@
+//! Dense matrices over commutative rings.
+//!
+//! Arithmetic, transposition and characteristic polynomials (with Berkowitz's division-free
+//! algorithm) work over any [`CommutativeRing`]. Determinants and ranks use Bareiss's
+//! fraction-free elimination, whose divisions are all exact, so that they only need an
+//! [`IntegralDomain`] and the entries stay as small as the minors of the matrix. Reduced row
+//! echelon forms, inverses, nullspaces and solutions of linear systems need a [`Field`].
+//!
+//! Integer matrices also have Hermite and Smith normal forms, computed with unimodular row and
+//! column operations built from extended gcds, which solve systems of linear Diophantine
+//! equations.
+
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use num::integer::ExtendedGcd;
+use num::{BigInt, Integer, One, Signed, Zero};
+
+use crate::traits::{CommutativeRing, Field, IntegralDomain};
+use crate::Polynomial;
+
+/// A `rows × cols` matrix, with its entries stored row by row.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Matrix<R> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<R>,
+}
+
+impl<R: CommutativeRing> Matrix<R> {
+    /// The `rows × cols` matrix with the given entries, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If there are not `rows * cols` entries.
+    pub fn new(rows: usize, cols: usize, entries: Vec<R>) -> Self {
+        assert_eq!(entries.len(), rows * cols, "wrong number of entries");
+        Self {
+            rows,
+            cols,
+            entries,
+        }
+    }
+
+    /// The matrix with the given rows.
+    ///
+    /// # Panics
+    ///
+    /// If the rows do not all have the same length.
+    pub fn from_rows(rows: Vec<Vec<R>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "rows of different lengths"
+        );
+        Self::new(rows.len(), cols, rows.into_iter().flatten().collect())
+    }
+
+    /// The `rows × cols` matrix with the entries `f(i, j)`.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> R) -> Self {
+        let entries = (0..rows)
+            .flat_map(|i| (0..cols).map(move |j| (i, j)))
+            .map(|(i, j)| f(i, j))
+            .collect();
+        Self::new(rows, cols, entries)
+    }
+
+    /// The `rows × cols` zero matrix.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::from_fn(rows, cols, |_, _| R::zero())
+    }
+
+    /// The `n × n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { R::one() } else { R::zero() })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// The entries of the `i`-th row.
+    pub fn row(&self, i: usize) -> &[R] {
+        &self.entries[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// The entries of the `j`-th column.
+    pub fn column(&self, j: usize) -> Vec<R> {
+        (0..self.rows).map(|i| self[(i, j)].clone()).collect()
+    }
+
+    pub fn into_rows(self) -> Vec<Vec<R>> {
+        if self.cols == 0 {
+            return vec![vec![]; self.rows];
+        }
+        let mut entries = self.entries.into_iter();
+        (0..self.rows)
+            .map(|_| entries.by_ref().take(self.cols).collect())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+
+    #[must_use]
+    pub fn scalar_mul(mut self, c: R) -> Self {
+        for x in &mut self.entries {
+            let old = std::mem::replace(x, R::zero());
+            *x = old * c.clone();
+        }
+        self
+    }
+
+    /// The product of `self` and the column vector `v`.
+    ///
+    /// # Panics
+    ///
+    /// If `v` does not have one entry per column.
+    pub fn mul_vec(&self, v: &[R]) -> Vec<R> {
+        assert_eq!(v.len(), self.cols, "dimension mismatch");
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(v)
+                    .fold(R::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+            })
+            .collect()
+    }
+
+    /// The characteristic polynomial `det(x I - self)`, computed with Berkowitz's algorithm,
+    /// which uses no divisions and takes `O(n^4)` ring operations.
+    ///
+    /// # Panics
+    ///
+    /// If the matrix is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::matrix::Matrix;
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigInt;
+    /// let n = |x: i32| BigInt::from(x);
+    /// let m = Matrix::from_rows(vec![vec![n(2), n(1)], vec![n(1), n(2)]]);
+    /// assert_eq!(m.charpoly(), Polynomial::new(vec![n(3), n(-4), n(1)]));
+    /// ```
+    pub fn charpoly(&self) -> Polynomial<R> {
+        assert!(
+            self.is_square(),
+            "the characteristic polynomial of a non-square matrix"
+        );
+        // the coefficients of the characteristic polynomial of the leading k×k submatrix, from the
+        // highest degree to the lowest
+        let mut charpoly = vec![R::one()];
+        for k in 0..self.rows {
+            // the first column of the Toeplitz matrix of the leading (k + 1)×(k + 1) submatrix is
+            // 1, -a_kk, -r c, -r M c, -r M^2 c, ... where M is the leading k×k submatrix, r the
+            // rest of row k and c the rest of column k
+            let mut toeplitz = vec![R::one(), -self[(k, k)].clone()];
+            let mut column = (0..k).map(|i| self[(i, k)].clone()).collect::<Vec<_>>();
+            for _ in 0..k {
+                let product = (0..k).fold(R::zero(), |acc, j| {
+                    acc + self[(k, j)].clone() * column[j].clone()
+                });
+                toeplitz.push(-product);
+                column = (0..k)
+                    .map(|i| {
+                        (0..k).fold(R::zero(), |acc, j| {
+                            acc + self[(i, j)].clone() * column[j].clone()
+                        })
+                    })
+                    .collect();
+            }
+            charpoly = (0..k + 2)
+                .map(|i| {
+                    (0..=i.min(k)).fold(R::zero(), |acc, j| {
+                        acc + toeplitz[i - j].clone() * charpoly[j].clone()
+                    })
+                })
+                .collect();
+        }
+        charpoly.reverse();
+        Polynomial::new(charpoly)
+    }
+
+    fn swap_rows(&mut self, i: usize, k: usize) {
+        for j in 0..self.cols {
+            self.entries.swap(i * self.cols + j, k * self.cols + j);
+        }
+    }
+
+    /// The matrix with the columns of `self` followed by the columns of `other`.
+    fn augment(&self, other: &Self) -> Self {
+        assert_eq!(self.rows, other.rows, "dimension mismatch");
+        Self::from_fn(self.rows, self.cols + other.cols, |i, j| {
+            match j.checked_sub(self.cols) {
+                Some(j) => other[(i, j)].clone(),
+                None => self[(i, j)].clone(),
+            }
+        })
+    }
+}
+
+impl<R: IntegralDomain> Matrix<R> {
+    /// Brings `self` to row echelon form with Bareiss's algorithm, in place. The entries are
+    /// minors of the original matrix, since the updates `(p a_ij - a_ic a_rj) / p'` are exact,
+    /// where `p` is the current pivot and `p'` the previous one.
+    ///
+    /// Returns the rank and whether an odd number of rows were swapped.
+    fn fraction_free_echelon(&mut self) -> (usize, bool) {
+        let mut previous = R::one();
+        let mut rank = 0;
+        let mut odd = false;
+        for c in 0..self.cols {
+            let Some(p) = (rank..self.rows).find(|&i| !self[(i, c)].is_zero()) else {
+                continue;
+            };
+            if p != rank {
+                self.swap_rows(p, rank);
+                odd = !odd;
+            }
+            let pivot = self[(rank, c)].clone();
+            for i in rank + 1..self.rows {
+                let factor = std::mem::replace(&mut self[(i, c)], R::zero());
+                for j in c + 1..self.cols {
+                    let old = std::mem::replace(&mut self[(i, j)], R::zero());
+                    let minor = CommutativeRing::sub(
+                        pivot.clone() * old,
+                        factor.clone() * self[(rank, j)].clone(),
+                    );
+                    self[(i, j)] = minor
+                        .exact_div(&previous)
+                        .expect("the divisions of Bareiss's algorithm are exact");
+                }
+            }
+            previous = pivot;
+            rank += 1;
+        }
+        (rank, odd)
+    }
+
+    /// The determinant, computed with Bareiss's fraction-free elimination.
+    ///
+    /// # Panics
+    ///
+    /// If the matrix is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::matrix::Matrix;
+    /// # use num::BigInt;
+    /// let m = Matrix::from_rows(vec![
+    ///     vec![BigInt::from(2), BigInt::from(-1), BigInt::from(0)],
+    ///     vec![BigInt::from(1), BigInt::from(3), BigInt::from(4)],
+    ///     vec![BigInt::from(0), BigInt::from(5), BigInt::from(-2)],
+    /// ]);
+    /// assert_eq!(m.determinant(), BigInt::from(-54));
+    /// ```
+    pub fn determinant(&self) -> R {
+        assert!(self.is_square(), "the determinant of a non-square matrix");
+        let n = self.rows;
+        if n == 0 {
+            return R::one();
+        }
+        let mut echelon = self.clone();
+        let (rank, odd) = echelon.fraction_free_echelon();
+        if rank < n {
+            return R::zero();
+        }
+        let det = echelon[(n - 1, n - 1)].clone();
+        if odd {
+            -det
+        } else {
+            det
+        }
+    }
+
+    /// The rank, the dimension of the space spanned by the rows over the field of fractions.
+    pub fn rank(&self) -> usize {
+        self.clone().fraction_free_echelon().0
+    }
+}
+
+impl<F: Field> Matrix<F> {
+    /// The reduced row echelon form of `self` and its pivot columns: the leading entry of each
+    /// nonzero row is one, and is the only nonzero entry of its column.
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = vec![];
+        for c in 0..m.cols {
+            let r = pivots.len();
+            let Some(p) = (r..m.rows).find(|&i| !m[(i, c)].is_zero()) else {
+                continue;
+            };
+            m.swap_rows(p, r);
+            let inv = m[(r, c)]
+                .checked_inv()
+                .expect("nonzero elements of a field are invertible");
+            for j in c..m.cols {
+                let old = std::mem::replace(&mut m[(r, j)], F::zero());
+                m[(r, j)] = old * inv.clone();
+            }
+            for i in (0..m.rows).filter(|&i| i != r) {
+                let factor = m[(i, c)].clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                for j in c..m.cols {
+                    let old = std::mem::replace(&mut m[(i, j)], F::zero());
+                    m[(i, j)] = CommutativeRing::sub(old, factor.clone() * m[(r, j)].clone());
+                }
+            }
+            pivots.push(c);
+        }
+        (m, pivots)
+    }
+
+    /// The inverse matrix, `None` if `self` is singular.
+    ///
+    /// # Panics
+    ///
+    /// If the matrix is not square.
+    pub fn inverse(&self) -> Option<Self> {
+        assert!(self.is_square(), "the inverse of a non-square matrix");
+        let n = self.rows;
+        let (m, pivots) = self.augment(&Self::identity(n)).rref();
+        if pivots.len() < n || pivots[n - 1] >= n {
+            return None;
+        }
+        Some(Self::from_fn(n, n, |i, j| m[(i, n + j)].clone()))
+    }
+
+    /// A basis of the nullspace, the vectors `v` such that `self v = 0`. There is one vector for
+    /// each non-pivot column of the [reduced row echelon form](Self::rref), with a one in that
+    /// column.
+    pub fn nullspace(&self) -> Vec<Vec<F>> {
+        let (m, pivots) = self.rref();
+        (0..self.cols)
+            .filter(|j| !pivots.contains(j))
+            .map(|free| {
+                let mut v = vec![F::zero(); self.cols];
+                v[free] = F::one();
+                for (i, &p) in pivots.iter().enumerate() {
+                    v[p] = -m[(i, free)].clone();
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// A solution `x` of `self x = b`, where the variables of the non-pivot columns are zero.
+    /// `None` if there is none. The other solutions differ by an element of the
+    /// [nullspace](Self::nullspace).
+    ///
+    /// # Panics
+    ///
+    /// If `b` does not have one entry per row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::matrix::Matrix;
+    /// # use num::BigRational;
+    /// let q = |n: i32| BigRational::from_integer(n.into());
+    /// // x + 2y = 5, 3x + 4y = 6
+    /// let m = Matrix::from_rows(vec![vec![q(1), q(2)], vec![q(3), q(4)]]);
+    /// assert_eq!(m.solve(&[q(5), q(6)]), Some(vec![q(-4), BigRational::new(9.into(), 2.into())]));
+    /// ```
+    pub fn solve(&self, b: &[F]) -> Option<Vec<F>> {
+        assert_eq!(b.len(), self.rows, "dimension mismatch");
+        let b = Self::from_fn(self.rows, 1, |i, _| b[i].clone());
+        let (m, pivots) = self.augment(&b).rref();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+        let mut x = vec![F::zero(); self.cols];
+        for (i, &p) in pivots.iter().enumerate() {
+            x[p] = m[(i, self.cols)].clone();
+        }
+        Some(x)
+    }
+}
+
+impl Matrix<BigInt> {
+    /// Replaces the rows `i` and `k` with `a row_i + b row_k` and `c row_i + d row_k`.
+    fn combine_rows(&mut self, i: usize, k: usize, [a, b, c, d]: &[BigInt; 4]) {
+        for j in 0..self.cols {
+            let (x, y) = (&self[(i, j)], &self[(k, j)]);
+            let (x, y) = (a * x + b * y, c * x + d * y);
+            (self[(i, j)], self[(k, j)]) = (x, y);
+        }
+    }
+
+    /// Replaces the columns `j` and `l` with `a col_j + b col_l` and `c col_j + d col_l`.
+    fn combine_columns(&mut self, j: usize, l: usize, [a, b, c, d]: &[BigInt; 4]) {
+        for i in 0..self.rows {
+            let (x, y) = (&self[(i, j)], &self[(i, l)]);
+            let (x, y) = (a * x + b * y, c * x + d * y);
+            (self[(i, j)], self[(i, l)]) = (x, y);
+        }
+    }
+
+    fn negate_row(&mut self, i: usize) {
+        for j in 0..self.cols {
+            let old = std::mem::take(&mut self[(i, j)]);
+            self[(i, j)] = -old;
+        }
+    }
+
+    /// Row operations `[a, b, c, d]` of determinant one that make the second entry of `(x, y)`
+    /// zero, and its first entry `gcd(x, y)` up to sign. When `x` divides `y`, `x` is kept.
+    fn eliminate(x: &BigInt, y: &BigInt) -> [BigInt; 4] {
+        if !x.is_zero() && y.is_multiple_of(x) {
+            return [BigInt::one(), BigInt::zero(), -(y / x), BigInt::one()];
+        }
+        let ExtendedGcd { gcd, x: s, y: t } = x.extended_gcd(y);
+        [s, t, -(y / &gcd), x / &gcd]
+    }
+
+    /// The Hermite normal form `H` of `self` and a unimodular matrix `U` with `U self = H`. `H`
+    /// is in row echelon form, its pivots are positive and the entries above each pivot are
+    /// nonnegative and smaller than it. It is unique, and its nonzero rows are a basis of the
+    /// lattice spanned by the rows of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::matrix::Matrix;
+    /// # use num::BigInt;
+    /// let z = |rows: &[[i32; 3]]| {
+    ///     Matrix::from_rows(rows.iter().map(|r| r.map(BigInt::from).to_vec()).collect())
+    /// };
+    /// let m = z(&[[2, 3, 6], [4, 7, 8]]);
+    /// let (h, u) = m.hermite_normal_form();
+    /// assert_eq!(h, z(&[[2, 0, 18], [0, 1, -4]]));
+    /// assert_eq!(u * m, h);
+    /// ```
+    pub fn hermite_normal_form(&self) -> (Self, Self) {
+        let mut h = self.clone();
+        let mut u = Self::identity(self.rows);
+        let mut r = 0;
+        for c in 0..self.cols {
+            if r == self.rows {
+                break;
+            }
+            for i in r + 1..self.rows {
+                if !h[(i, c)].is_zero() {
+                    let ops = Self::eliminate(&h[(r, c)], &h[(i, c)]);
+                    h.combine_rows(r, i, &ops);
+                    u.combine_rows(r, i, &ops);
+                }
+            }
+            if h[(r, c)].is_zero() {
+                continue;
+            }
+            if h[(r, c)].is_negative() {
+                h.negate_row(r);
+                u.negate_row(r);
+            }
+            for i in 0..r {
+                let q = h[(i, c)].div_floor(&h[(r, c)]);
+                if !q.is_zero() {
+                    let ops = [BigInt::one(), -q, BigInt::zero(), BigInt::one()];
+                    h.combine_rows(i, r, &ops);
+                    u.combine_rows(i, r, &ops);
+                }
+            }
+            r += 1;
+        }
+        (h, u)
+    }
+
+    /// The Smith normal form `S` of `self` and unimodular matrices `U` and `V` with
+    /// `U self V = S`. `S` is diagonal, and its diagonal entries `d_1, ..., d_r, 0, ..., 0` are
+    /// positive up to the rank `r`, with each `d_i` dividing `d_(i + 1)`. They are the invariant
+    /// factors of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::matrix::Matrix;
+    /// # use num::BigInt;
+    /// let z = |rows: &[[i32; 3]]| {
+    ///     Matrix::from_rows(rows.iter().map(|r| r.map(BigInt::from).to_vec()).collect())
+    /// };
+    /// let m = z(&[[2, 4, 4], [-6, 6, 12], [10, -4, -16]]);
+    /// let (s, u, v) = m.smith_normal_form();
+    /// assert_eq!(s, z(&[[2, 0, 0], [0, 6, 0], [0, 0, 12]]));
+    /// assert_eq!(u * m * v, s);
+    /// ```
+    pub fn smith_normal_form(&self) -> (Self, Self, Self) {
+        let mut s = self.clone();
+        let mut u = Self::identity(self.rows);
+        let mut v = Self::identity(self.cols);
+        for t in 0..self.rows.min(self.cols) {
+            // move the smallest nonzero entry of the remaining submatrix to (t, t)
+            let Some((p, q)) = (t..self.rows)
+                .flat_map(|i| (t..self.cols).map(move |j| (i, j)))
+                .filter(|&(i, j)| !s[(i, j)].is_zero())
+                .min_by_key(|&(i, j)| s[(i, j)].abs())
+            else {
+                break;
+            };
+            let swap = [BigInt::zero(), BigInt::one(), BigInt::one(), BigInt::zero()];
+            if p != t {
+                s.combine_rows(t, p, &swap);
+                u.combine_rows(t, p, &swap);
+            }
+            if q != t {
+                s.combine_columns(t, q, &swap);
+                v.combine_columns(t, q, &swap);
+            }
+            // every pass that does not finish strictly decreases |s_tt|
+            loop {
+                for i in t + 1..self.rows {
+                    if !s[(i, t)].is_zero() {
+                        let ops = Self::eliminate(&s[(t, t)], &s[(i, t)]);
+                        s.combine_rows(t, i, &ops);
+                        u.combine_rows(t, i, &ops);
+                    }
+                }
+                for j in t + 1..self.cols {
+                    if !s[(t, j)].is_zero() {
+                        let ops = Self::eliminate(&s[(t, t)], &s[(t, j)]);
+                        s.combine_columns(t, j, &ops);
+                        v.combine_columns(t, j, &ops);
+                    }
+                }
+                if (t + 1..self.rows).any(|i| !s[(i, t)].is_zero()) {
+                    continue;
+                }
+                // s_tt must divide the rest of the matrix, otherwise adding the row of an entry
+                // it does not divide to row t makes the next pass replace it with a gcd
+                let pivot = s[(t, t)].clone();
+                let not_divisible = (t + 1..self.rows)
+                    .find(|&i| (t + 1..self.cols).any(|j| !s[(i, j)].is_multiple_of(&pivot)));
+                let Some(i) = not_divisible else {
+                    break;
+                };
+                let ops = [BigInt::one(), BigInt::one(), BigInt::zero(), BigInt::one()];
+                s.combine_rows(t, i, &ops);
+                u.combine_rows(t, i, &ops);
+            }
+            if s[(t, t)].is_negative() {
+                s.negate_row(t);
+                u.negate_row(t);
+            }
+        }
+        (s, u, v)
+    }
+
+    /// The integer solutions of `self x = b`: a particular solution and a basis of the lattice of
+    /// the integer solutions of `self x = 0`, so that the solutions are the particular one plus
+    /// the integer combinations of the basis. `None` if there is no integer solution. The system
+    /// is solved with the [Smith normal form](Self::smith_normal_form).
+    ///
+    /// # Panics
+    ///
+    /// If `b` does not have one entry per row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::matrix::Matrix;
+    /// # use num::BigInt;
+    /// let n = |x: i32| BigInt::from(x);
+    /// // 6x + 10y + 15z = 1
+    /// let m = Matrix::from_rows(vec![vec![n(6), n(10), n(15)]]);
+    /// let (x, kernel) = m.solve_diophantine(&[n(1)]).unwrap();
+    /// assert_eq!(m.mul_vec(&x), [n(1)]);
+    /// assert_eq!(kernel.len(), 2);
+    /// // 2x + 4y = 1 has no integer solution
+    /// let m = Matrix::from_rows(vec![vec![n(2), n(4)]]);
+    /// assert_eq!(m.solve_diophantine(&[n(1)]), None);
+    /// ```
+    pub fn solve_diophantine(&self, b: &[BigInt]) -> Option<(Vec<BigInt>, Vec<Vec<BigInt>>)> {
+        assert_eq!(b.len(), self.rows, "dimension mismatch");
+        let (s, u, v) = self.smith_normal_form();
+        // with x = V y, the system becomes S y = U b
+        let c = u.mul_vec(b);
+        let rank = (0..self.rows.min(self.cols))
+            .take_while(|&i| !s[(i, i)].is_zero())
+            .count();
+        if c[rank..].iter().any(|x| !x.is_zero()) {
+            return None;
+        }
+        let mut y = vec![BigInt::zero(); self.cols];
+        for i in 0..rank {
+            let (q, r) = c[i].div_rem(&s[(i, i)]);
+            if !r.is_zero() {
+                return None;
+            }
+            y[i] = q;
+        }
+        Some((
+            v.mul_vec(&y),
+            (rank..self.cols).map(|j| v.column(j)).collect(),
+        ))
+    }
+}
+
+impl<R> Index<(usize, usize)> for Matrix<R> {
+    type Output = R;
+    fn index(&self, (i, j): (usize, usize)) -> &R {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &self.entries[i * self.cols + j]
+    }
+}
+
+impl<R> IndexMut<(usize, usize)> for Matrix<R> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut R {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &mut self.entries[i * self.cols + j]
+    }
+}
+
+impl<R: CommutativeRing> Add for Matrix<R> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        assert_eq!(
+            (self.rows, self.cols),
+            (other.rows, other.cols),
+            "dimension mismatch"
+        );
+        let entries = self.entries.into_iter().zip(other.entries);
+        Self::new(self.rows, self.cols, entries.map(|(a, b)| a + b).collect())
+    }
+}
+
+impl<R: CommutativeRing> Sub for Matrix<R> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        assert_eq!(
+            (self.rows, self.cols),
+            (other.rows, other.cols),
+            "dimension mismatch"
+        );
+        let entries = self.entries.into_iter().zip(other.entries);
+        let entries = entries.map(|(a, b)| CommutativeRing::sub(a, b));
+        Self::new(self.rows, self.cols, entries.collect())
+    }
+}
+
+impl<R: CommutativeRing> Neg for Matrix<R> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(
+            self.rows,
+            self.cols,
+            self.entries.into_iter().map(Neg::neg).collect(),
+        )
+    }
+}
+
+impl<R: CommutativeRing> Mul for Matrix<R> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        assert_eq!(self.cols, other.rows, "dimension mismatch");
+        Self::from_fn(self.rows, other.cols, |i, j| {
+            self.row(i)
+                .iter()
+                .enumerate()
+                .fold(R::zero(), |acc, (k, a)| {
+                    acc + a.clone() * other[(k, j)].clone()
+                })
+        })
+    }
+}
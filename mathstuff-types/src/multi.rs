@@ -0,0 +1,473 @@
+//! Sparse multivariate polynomials.
+
+use std::cmp::Ordering;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::traits::{CommutativeRing, FromUsize, IntegralDomain};
+
+/// An order on the monomials, given by their exponent vectors.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MonomialOrder {
+    /// Lexicographic order, the first variable is the most significant.
+    #[default]
+    Lex,
+    /// Total degree first, ties broken by [`MonomialOrder::Lex`].
+    GrLex,
+    /// Total degree first, ties broken by the smaller exponent in the last variable where the
+    /// exponents differ.
+    GRevLex,
+}
+
+impl MonomialOrder {
+    /// Compares the monomials with exponent vectors `a` and `b`, which must have the same length.
+    pub fn compare(self, a: &[usize], b: &[usize]) -> Ordering {
+        let degree = |x: &[usize]| x.iter().sum::<usize>();
+        match self {
+            MonomialOrder::Lex => a.cmp(b),
+            MonomialOrder::GrLex => degree(a).cmp(&degree(b)).then_with(|| a.cmp(b)),
+            MonomialOrder::GRevLex => degree(a)
+                .cmp(&degree(b))
+                .then_with(|| b.iter().rev().cmp(a.iter().rev())),
+        }
+    }
+}
+
+/// A polynomial in named variables, stored as a list of terms with nonzero coefficients in
+/// decreasing monomial order.
+///
+/// Arithmetic on polynomials over different variables works in the union of their variables,
+/// the variables of `self` coming first. The result uses the order of the operand with more
+/// variables, so that constants such as [`MultiPolynomial::one`] do not change it.
+///
+/// # Examples
+///
+/// ```
+/// use mathstuff_types::multi::{MonomialOrder, MultiPolynomial};
+///
+/// let [x, y] = MultiPolynomial::<i64>::generators(&["x", "y"], MonomialOrder::GrLex);
+/// let p = (x.clone() + y.clone()) * (x - y) + MultiPolynomial::constant(3);
+/// assert_eq!(p.to_string(), "x^2 - y^2 + 3");
+/// assert_eq!(p.eval(&[2, 1]), 6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiPolynomial<Ring> {
+    pub(crate) vars: Vec<String>,
+    pub(crate) order: MonomialOrder,
+    pub(crate) terms: Vec<(Vec<usize>, Ring)>,
+}
+
+impl<Ring: CommutativeRing> MultiPolynomial<Ring> {
+    /// Builds a polynomial in `vars` from its terms, given as exponent vectors and coefficients
+    /// in any order. Like terms are collected.
+    pub fn new(vars: &[&str], order: MonomialOrder, terms: Vec<(Vec<usize>, Ring)>) -> Self {
+        assert!(
+            terms.iter().all(|(exps, _)| exps.len() == vars.len()),
+            "the exponent vectors must have one entry per variable"
+        );
+        let mut this = Self {
+            vars: vars.iter().map(|&x| x.to_owned()).collect(),
+            order,
+            terms,
+        };
+        this.normalize();
+        this
+    }
+
+    /// The constant polynomial `c`, in no variables.
+    pub fn constant(c: Ring) -> Self {
+        Self::new(&[], MonomialOrder::default(), vec![(vec![], c)])
+    }
+
+    /// The variables of `vars` as polynomials.
+    pub fn generators<const N: usize>(vars: &[&str; N], order: MonomialOrder) -> [Self; N] {
+        std::array::from_fn(|i| {
+            let mut exps = vec![0; N];
+            exps[i] = 1;
+            Self::new(vars, order, vec![(exps, Ring::one())])
+        })
+    }
+
+    /// Sorts the terms, collects the like ones and removes the zero ones.
+    fn normalize(&mut self) {
+        let order = self.order;
+        self.terms.sort_by(|a, b| order.compare(&b.0, &a.0));
+        let mut terms: Vec<(Vec<usize>, Ring)> = Vec::with_capacity(self.terms.len());
+        for (exps, c) in self.terms.drain(..) {
+            match terms.last_mut() {
+                Some(last) if last.0 == exps => {
+                    let sum = std::mem::replace(&mut last.1, Ring::zero()) + c;
+                    last.1 = sum;
+                }
+                _ => terms.push((exps, c)),
+            }
+        }
+        terms.retain(|(_, c)| !c.is_zero());
+        self.terms = terms;
+    }
+
+    pub fn vars(&self) -> &[String] {
+        &self.vars
+    }
+
+    pub fn var_index(&self, var: &str) -> Option<usize> {
+        self.vars.iter().position(|x| x == var)
+    }
+
+    pub fn order(&self) -> MonomialOrder {
+        self.order
+    }
+
+    /// The same polynomial with its terms sorted by another monomial order.
+    #[must_use]
+    pub fn with_order(mut self, order: MonomialOrder) -> Self {
+        self.order = order;
+        self.normalize();
+        self
+    }
+
+    /// The same polynomial in `vars`, which must contain the variables of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If a variable of `self` with a nonzero exponent is missing from `vars`.
+    #[must_use]
+    pub fn with_vars(self, vars: &[&str]) -> Self {
+        let positions = self
+            .vars
+            .iter()
+            .map(|x| vars.iter().position(|y| x == y))
+            .collect::<Vec<_>>();
+        let terms = self
+            .terms
+            .into_iter()
+            .map(|(exps, c)| {
+                let mut new = vec![0; vars.len()];
+                for (e, i) in exps.into_iter().zip(&positions) {
+                    match i {
+                        Some(i) => new[*i] = e,
+                        None => assert_eq!(e, 0, "the variables must contain those of `self`"),
+                    }
+                }
+                (new, c)
+            })
+            .collect();
+        Self::new(vars, self.order, terms)
+    }
+
+    /// Rewrites `self` and `other` as polynomials in the union of their variables, sorted by the
+    /// same monomial order.
+    pub fn unify(self, other: Self) -> (Self, Self) {
+        if self.vars == other.vars {
+            let order = self.order;
+            return (self, other.with_order(order));
+        }
+        let order = if other.vars.len() > self.vars.len() {
+            other.order
+        } else {
+            self.order
+        };
+        let mut vars = self.vars.clone();
+        for var in &other.vars {
+            if !vars.contains(var) {
+                vars.push(var.clone());
+            }
+        }
+        let vars = vars.iter().map(|x| &**x).collect::<Vec<_>>();
+        (
+            self.with_vars(&vars).with_order(order),
+            other.with_vars(&vars).with_order(order),
+        )
+    }
+
+    /// A polynomial in the same variables and order as `self` with the given terms.
+    pub fn with_terms(&self, terms: Vec<(Vec<usize>, Ring)>) -> Self {
+        assert!(terms.iter().all(|(exps, _)| exps.len() == self.vars.len()));
+        let mut this = Self {
+            vars: self.vars.clone(),
+            order: self.order,
+            terms,
+        };
+        this.normalize();
+        this
+    }
+
+    /// Applies `f` to all the coefficients, dropping the terms that become zero.
+    pub fn map_coefficients<S: CommutativeRing>(
+        self,
+        mut f: impl FnMut(Ring) -> S,
+    ) -> MultiPolynomial<S> {
+        MultiPolynomial {
+            vars: self.vars,
+            order: self.order,
+            terms: self
+                .terms
+                .into_iter()
+                .map(|(exps, c)| (exps, f(c)))
+                .filter(|(_, c)| !c.is_zero())
+                .collect(),
+        }
+    }
+
+    pub fn into_terms(self) -> Vec<(Vec<usize>, Ring)> {
+        self.terms
+    }
+
+    /// The terms in decreasing monomial order.
+    pub fn terms(&self) -> impl ExactSizeIterator<Item = (&[usize], &Ring)> {
+        self.terms.iter().map(|(exps, c)| (&**exps, c))
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// The coefficient of the monomial with the exponent vector `exps`.
+    pub fn coefficient(&self, exps: &[usize]) -> Ring {
+        self.terms
+            .iter()
+            .find(|(x, _)| x == exps)
+            .map_or_else(Ring::zero, |(_, c)| c.clone())
+    }
+
+    /// The total degree of the polynomial. `None` if the polynomial is zero.
+    pub fn total_degree(&self) -> Option<usize> {
+        self.terms.iter().map(|(exps, _)| exps.iter().sum()).max()
+    }
+
+    /// The degree of the polynomial in the `i`-th variable. `None` if the polynomial is zero.
+    pub fn degree_in(&self, i: usize) -> Option<usize> {
+        self.terms.iter().map(|(exps, _)| exps[i]).max()
+    }
+
+    /// The exponent vector and coefficient of the largest term. `None` if the polynomial is zero.
+    pub fn leading_term(&self) -> Option<(&[usize], &Ring)> {
+        self.terms.first().map(|(exps, c)| (&**exps, c))
+    }
+
+    pub fn leading_monomial(&self) -> Option<&[usize]> {
+        self.terms.first().map(|(exps, _)| &**exps)
+    }
+
+    pub fn leading_coefficient(&self) -> Option<&Ring> {
+        self.terms.first().map(|(_, c)| c)
+    }
+
+    pub fn leading_coefficient_cloned(&self) -> Ring {
+        self.leading_coefficient()
+            .cloned()
+            .unwrap_or_else(Ring::zero)
+    }
+
+    /// Multiples all the coefficients by a given scalar.
+    #[must_use]
+    pub fn scalar_mul(mut self, x: Ring) -> Self {
+        for (_, coeff) in self.terms.iter_mut() {
+            let c = std::mem::replace(coeff, Ring::zero());
+            *coeff = c * x.clone();
+        }
+        self.terms.retain(|(_, c)| !c.is_zero());
+        self
+    }
+
+    /// Multiplies by the term `c x^exps`, where `exps` is an exponent vector in the variables
+    /// of `self`.
+    #[must_use]
+    pub fn mul_term(self, exps: &[usize], c: Ring) -> Self {
+        let mut this = self.scalar_mul(c);
+        for (e, _) in this.terms.iter_mut() {
+            e.iter_mut().zip(exps).for_each(|(a, b)| *a += b);
+        }
+        this
+    }
+
+    /// Evaluates the polynomial with `values[i]` in place of the `i`-th variable.
+    pub fn eval(&self, values: &[Ring]) -> Ring {
+        assert_eq!(values.len(), self.vars.len());
+        self.terms
+            .iter()
+            .map(|(exps, c)| {
+                exps.iter()
+                    .zip(values)
+                    .fold(c.clone(), |acc, (&e, x)| acc * num::pow(x.clone(), e))
+            })
+            .fold(Ring::zero(), |a, b| a + b)
+    }
+
+    /// Substitutes `value` for the `i`-th variable, which stays in the variables of the result.
+    #[must_use]
+    pub fn eval_var(&self, i: usize, value: &Ring) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(exps, c)| {
+                let mut exps = exps.clone();
+                let e = std::mem::take(&mut exps[i]);
+                (exps, c.clone() * num::pow(value.clone(), e))
+            })
+            .collect();
+        self.with_terms(terms)
+    }
+
+    /// The partial derivative with respect to the `i`-th variable.
+    #[must_use]
+    pub fn derivative(&self, i: usize) -> Self
+    where
+        Ring: FromUsize,
+    {
+        let terms = self
+            .terms
+            .iter()
+            .filter(|(exps, _)| exps[i] > 0)
+            .map(|(exps, c)| {
+                let mut exps = exps.clone();
+                let c = c.clone() * Ring::from_usize(exps[i]);
+                exps[i] -= 1;
+                (exps, c)
+            })
+            .collect();
+        self.with_terms(terms)
+    }
+
+    #[must_use]
+    pub fn pow(self, n: usize) -> Self {
+        num::pow(self, n)
+    }
+}
+
+impl<Ring: CommutativeRing + PartialEq> PartialEq for MultiPolynomial<Ring> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.vars == other.vars && self.order == other.order {
+            return self.terms == other.terms;
+        }
+        let (a, b) = self.clone().unify(other.clone());
+        a.terms == b.terms
+    }
+}
+
+impl<Ring: CommutativeRing + Eq> Eq for MultiPolynomial<Ring> {}
+
+impl<Ring: CommutativeRing> Zero for MultiPolynomial<Ring> {
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    fn zero() -> Self {
+        Self::new(&[], MonomialOrder::default(), vec![])
+    }
+
+    fn set_zero(&mut self) {
+        self.terms.clear();
+    }
+}
+
+impl<Ring: CommutativeRing> One for MultiPolynomial<Ring> {
+    fn one() -> Self {
+        Self::constant(Ring::one())
+    }
+}
+
+impl<Ring: CommutativeRing> Add for MultiPolynomial<Ring> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (mut a, b) = self.unify(other);
+        a.terms.extend(b.terms);
+        a.normalize();
+        a
+    }
+}
+
+impl<Ring: CommutativeRing> Sub for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (mut a, b) = self.unify(rhs);
+        a.terms
+            .extend(b.terms.into_iter().map(|(exps, c)| (exps, c.neg())));
+        a.normalize();
+        a
+    }
+}
+
+impl<Ring: CommutativeRing> AddAssign for MultiPolynomial<Ring> {
+    fn add_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::replace(self, Self::zero());
+        *self = lhs + rhs
+    }
+}
+
+impl<Ring: CommutativeRing> Mul for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (mut a, b) = self.unify(rhs);
+        let mut terms = Vec::with_capacity(a.terms.len() * b.terms.len());
+        for (x, c) in &a.terms {
+            for (y, d) in &b.terms {
+                let exps = x.iter().zip(y).map(|(i, j)| i + j).collect();
+                terms.push((exps, c.clone() * d.clone()));
+            }
+        }
+        a.terms = terms;
+        a.normalize();
+        a
+    }
+}
+
+impl<Ring: CommutativeRing> Mul<Ring> for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn mul(self, rhs: Ring) -> Self::Output {
+        self.scalar_mul(rhs)
+    }
+}
+
+impl<Ring: CommutativeRing> Neg for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn neg(mut self) -> Self::Output {
+        self.terms = self
+            .terms
+            .into_iter()
+            .map(|(exps, c)| (exps, c.neg()))
+            .collect();
+        self
+    }
+}
+
+impl<Ring: CommutativeRing> Product for MultiPolynomial<Ring> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |a, b| a * b)
+    }
+}
+
+impl<Ring: CommutativeRing> Sum for MultiPolynomial<Ring> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+/// The ring of polynomials in several variables over a ring (`R[x, y, ...]`)
+impl<Ring: CommutativeRing> CommutativeRing for MultiPolynomial<Ring> {}
+
+/// Polynomials in several variables over an integral domain have no zero divisors either. The
+/// quotient is found by the division algorithm, in the union of the variables and the monomial
+/// order of `self`.
+impl<Ring: IntegralDomain> IntegralDomain for MultiPolynomial<Ring> {
+    fn exact_div(&self, other: &Self) -> Option<Self> {
+        let (a, b) = self.clone().unify(other.clone());
+        let (lm, lc) = b.leading_term()?;
+        let mut quotient = vec![];
+        let mut r = a.clone();
+        while let Some((exps, c)) = r.leading_term() {
+            let exps = exps
+                .iter()
+                .zip(lm)
+                .map(|(i, j)| i.checked_sub(*j))
+                .collect::<Option<Vec<_>>>()?;
+            let c = c.exact_div(lc)?;
+            r = r - b.clone().mul_term(&exps, c.clone());
+            quotient.push((exps, c));
+        }
+        Some(a.with_terms(quotient))
+    }
+}
@@ -0,0 +1,215 @@
+//! Parsing univariate polynomials from the text they print as.
+//!
+//! [`Polynomial::from_str_with_var`] reads sums of terms like `3x^4`, `-5/2 x^2`, `2*x` and `7`,
+//! as printed by [`Polynomial::print_with_var`], and [`poly!`](crate::poly) does the same for
+//! polynomials written directly in the source code. The terms can come in any order and repeat
+//! degrees, whose coefficients are added.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[doc(hidden)]
+pub use num::BigRational;
+
+use crate::traits::CommutativeRing;
+use crate::Polynomial;
+
+/// The reason a polynomial failed to parse, with the byte offset in the input where it did.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParsePolynomialError {
+    /// A character that cannot continue the polynomial.
+    Unexpected(usize),
+    /// The input ended in the middle of a term, or has no terms.
+    UnexpectedEnd,
+    /// A coefficient the coefficient type cannot represent, like a fraction of integers.
+    InvalidCoefficient(usize),
+    /// An exponent that does not fit in a `usize`.
+    InvalidExponent(usize),
+}
+
+impl Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected(i) => write!(f, "unexpected character at offset {i}"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of input"),
+            Self::InvalidCoefficient(i) => write!(f, "invalid coefficient at offset {i}"),
+            Self::InvalidExponent(i) => write!(f, "invalid exponent at offset {i}"),
+        }
+    }
+}
+
+impl Error for ParsePolynomialError {}
+
+/// A position in the input of [`Polynomial::from_str_with_var`].
+struct Cursor<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace, then consumes `prefix` if the input continues with it.
+    fn eat(&mut self, prefix: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.rest().starts_with(prefix);
+        if found {
+            self.offset += prefix.len();
+        }
+        found
+    }
+
+    /// Skips whitespace, then consumes a nonempty run of decimal digits.
+    fn digits(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let start = self.offset;
+        let len = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+        self.offset += len;
+        (len > 0).then(|| &self.input[start..self.offset])
+    }
+
+    fn error(&self) -> ParsePolynomialError {
+        if self.rest().is_empty() {
+            ParsePolynomialError::UnexpectedEnd
+        } else {
+            ParsePolynomialError::Unexpected(self.offset)
+        }
+    }
+}
+
+impl<R: CommutativeRing + FromStr> Polynomial<R> {
+    /// Parses a polynomial in the variable `var` from a sum of terms `c var^k`, where the
+    /// coefficient `c` is an integer or a fraction of integers, parsed with [`FromStr`]. The
+    /// coefficient can be followed by a `*` and defaults to one, `var^1` can be written `var`,
+    /// and whitespace is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigRational;
+    /// let q = |n: i32, d: i32| BigRational::new(n.into(), d.into());
+    /// let p = Polynomial::<BigRational>::from_str_with_var("3x^4 - 5/2x^2 + 3", "x").unwrap();
+    /// assert_eq!(p, Polynomial::new(vec![q(3, 1), q(0, 1), q(-5, 2), q(0, 1), q(3, 1)]));
+    /// assert_eq!(p.print_with_var("x").to_string(), "3x^4 - 5/2x^2 + 3");
+    /// assert!(Polynomial::<BigRational>::from_str_with_var("3y^2", "x").is_err());
+    /// ```
+    pub fn from_str_with_var(s: &str, var: &str) -> Result<Self, ParsePolynomialError> {
+        let mut cursor = Cursor {
+            input: s,
+            offset: 0,
+        };
+        let mut coeffs = vec![];
+        let mut negative = cursor.eat("-");
+        loop {
+            let (coeff, degree) = term(&mut cursor, var)?;
+            if coeffs.len() <= degree {
+                coeffs.resize(degree + 1, R::zero());
+            }
+            let old = std::mem::replace(&mut coeffs[degree], R::zero());
+            coeffs[degree] = if negative {
+                CommutativeRing::sub(old, coeff)
+            } else {
+                old + coeff
+            };
+
+            if cursor.eat("+") {
+                negative = false;
+            } else if cursor.eat("-") {
+                negative = true;
+            } else if cursor.rest().is_empty() {
+                return Ok(Polynomial::new_trim_zeroes(coeffs));
+            } else {
+                return Err(cursor.error());
+            }
+        }
+    }
+}
+
+/// Parses a term `c var^k`, returning `c` and `k`.
+fn term<R: CommutativeRing + FromStr>(
+    cursor: &mut Cursor<'_>,
+    var: &str,
+) -> Result<(R, usize), ParsePolynomialError> {
+    cursor.skip_whitespace();
+    let start = cursor.offset;
+    let coeff = match cursor.digits() {
+        Some(numerator) => {
+            let mut text = numerator.to_owned();
+            if cursor.eat("/") {
+                text.push('/');
+                text.push_str(cursor.digits().ok_or_else(|| cursor.error())?);
+            }
+            let coeff = text
+                .parse()
+                .map_err(|_| ParsePolynomialError::InvalidCoefficient(start))?;
+            if cursor.eat("*") {
+                cursor.skip_whitespace();
+                if !cursor.rest().starts_with(var) {
+                    return Err(cursor.error());
+                }
+            }
+            coeff
+        }
+        None => R::one(),
+    };
+
+    if !cursor.eat(var) {
+        return if cursor.offset == start {
+            Err(cursor.error())
+        } else {
+            Ok((coeff, 0))
+        };
+    }
+    if !cursor.eat("^") {
+        return Ok((coeff, 1));
+    }
+    cursor.skip_whitespace();
+    let exponent = cursor.offset;
+    let degree = cursor
+        .digits()
+        .ok_or_else(|| cursor.error())?
+        .parse()
+        .map_err(|_| ParsePolynomialError::InvalidExponent(exponent))?;
+    Ok((coeff, degree))
+}
+
+/// Builds a [`Polynomial`](crate::Polynomial) with rational coefficients from the way it
+/// prints, by parsing the tokens with
+/// [`Polynomial::from_str_with_var`](crate::Polynomial::from_str_with_var). The variable is `x`,
+/// unless it is given first followed by a `;`.
+///
+/// # Panics
+///
+/// If the tokens are not a polynomial.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff_types::{poly, Polynomial};
+/// # use num::BigRational;
+/// let q = |n: i32| BigRational::from_integer(n.into());
+/// assert_eq!(poly!(3x^4 - 5x^2 + 3), Polynomial::new(vec![q(3), q(0), q(-5), q(0), q(3)]));
+/// assert_eq!(poly!(t; 2t - 1), Polynomial::new(vec![q(-1), q(2)]));
+/// ```
+#[macro_export]
+macro_rules! poly {
+    ($var:ident; $($tokens:tt)*) => {
+        $crate::Polynomial::<$crate::parse::BigRational>::from_str_with_var(
+            stringify!($($tokens)*),
+            stringify!($var),
+        )
+        .expect("not a polynomial")
+    };
+    ($($tokens:tt)*) => {
+        $crate::poly!(x; $($tokens)*)
+    };
+}
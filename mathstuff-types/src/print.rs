@@ -3,7 +3,7 @@ use std::fmt::{self, Display};
 use num::Signed;
 
 use crate::traits::CommutativeRing;
-use crate::Polynomial;
+use crate::{MultiPolynomial, Polynomial};
 
 pub trait PrintableCoeff: Display + CommutativeRing + PartialEq + Signed {}
 
@@ -105,3 +105,53 @@ impl<T: DisplayWithVar> Display for PrintWithVar<'_, T> {
 }
 
 
+
+impl<T: PrintableCoeff> Display for MultiPolynomial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return f.write_str("0");
+        }
+
+        for (i, (exps, coeff)) in self.terms.iter().enumerate() {
+            if i > 0 {
+                f.write_str(if coeff.is_negative() { " - " } else { " + " })?;
+            }
+
+            let abs;
+            let coeff = if i == 0 {
+                coeff
+            } else {
+                abs = coeff.abs();
+                &abs
+            };
+
+            if exps.iter().all(|&e| e == 0) {
+                write!(f, "{coeff}")?;
+                continue;
+            }
+
+            if i == 0 {
+                print_as_factor(coeff, f)?;
+            } else {
+                print_if_not_one(coeff, f)?;
+            }
+            let mut first = true;
+            for (var, &e) in self.vars.iter().zip(exps) {
+                if e == 0 {
+                    continue;
+                }
+                if !first {
+                    f.write_str("*")?;
+                }
+                first = false;
+                if e == 1 {
+                    write!(f, "{var}")?;
+                } else {
+                    write!(f, "{var}^{e}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
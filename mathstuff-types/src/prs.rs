@@ -0,0 +1,201 @@
+//! Polynomial remainder sequences, and gcds of polynomials over the integers.
+//!
+//! A polynomial remainder sequence of `a` and `b` starts with `a, b` and continues with multiples
+//! of the pseudo-remainders of the last two polynomials, so that it only needs the operations of
+//! an integral domain. The sequences differ by how the remainders are scaled to keep their
+//! coefficients small: not at all (Euclidean), by their content (primitive) or by a factor known
+//! in advance (subresultant).
+
+use num::{BigInt, Integer, Zero};
+
+use crate::traits::{CommutativeRing, GcdDomain, IntegralDomain};
+use crate::Polynomial;
+
+impl<Ring: CommutativeRing> Polynomial<Ring> {
+    /// The Euclidean polynomial remainder sequence of `self` and `other`, where each polynomial
+    /// after the first two is the pseudo-remainder of the previous two. The last polynomial is a
+    /// gcd of `self` and `other` up to a constant factor, but the coefficients grow exponentially.
+    ///
+    /// The polynomial of higher degree comes first. Zero polynomials are left out.
+    pub fn euclidean_prs(self, other: Self) -> Vec<Self> {
+        remainder_sequence(self, other, |r, _, _| r)
+    }
+
+    /// The subresultant polynomial remainder sequence of `self` and `other`. The remainders are
+    /// divided by a factor that keeps their coefficients as small as the subresultants, which
+    /// they are equal to up to sign.
+    ///
+    /// The divisions are exact, so `Ring` can be any integral domain. The polynomial of higher
+    /// degree comes first. Zero polynomials are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigInt;
+    /// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    /// // Knuth's example
+    /// let a = p(&[-5, 2, 8, -3, -3, 0, 1, 0, 1]);
+    /// let b = p(&[21, -9, -4, 0, 5, 0, 3]);
+    /// let prs = a.clone().subresultant_prs(b.clone());
+    /// assert_eq!(
+    ///     prs,
+    ///     [a, b, p(&[-9, 0, 3, 0, -15]), p(&[-245, 125, 65]), p(&[12300, -9326]), p(&[260708])]
+    /// );
+    /// ```
+    pub fn subresultant_prs(self, other: Self) -> Vec<Self>
+    where
+        Ring: IntegralDomain,
+    {
+        // Collins' algorithm: r_{i+1} = prem(r_{i-1}, r_i) / (g h^δ), where g = lc(r_{i-1}) and
+        // h = g^δ / h^(δ - 1) are updated after each step
+        let mut g = Ring::one();
+        let mut h = Ring::one();
+        remainder_sequence(self, other, |r, a, b| {
+            let delta = a.degree().unwrap() - b.degree().unwrap();
+            let divisor = g.clone() * num::pow(h.clone(), delta);
+            let exact = "the subresultant divisions are exact";
+            let r = r.into_iter().map(|c| c.exact_div(&divisor).expect(exact));
+            let r = Polynomial::new(r.collect());
+            g = b.leading_coefficient_cloned();
+            h = match delta {
+                0 => h.clone(),
+                _ => num::pow(g.clone(), delta)
+                    .exact_div(&num::pow(h.clone(), delta - 1))
+                    .expect(exact),
+            };
+            r
+        })
+    }
+}
+
+/// Builds the sequence `a, b, r_2, ...` where `r_{i+1} = scale(prem(r_{i-1}, r_i), r_{i-1}, r_i)`.
+fn remainder_sequence<Ring, F>(
+    mut a: Polynomial<Ring>,
+    mut b: Polynomial<Ring>,
+    mut scale: F,
+) -> Vec<Polynomial<Ring>>
+where
+    Ring: CommutativeRing,
+    F: FnMut(Polynomial<Ring>, &Polynomial<Ring>, &Polynomial<Ring>) -> Polynomial<Ring>,
+{
+    if a.degree() < b.degree() {
+        std::mem::swap(&mut a, &mut b);
+    }
+    let mut sequence = vec![];
+    if a.is_zero() {
+        return sequence;
+    }
+    sequence.push(a);
+    if b.is_zero() {
+        return sequence;
+    }
+    sequence.push(b);
+
+    loop {
+        let [.., a, b] = &sequence[..] else {
+            unreachable!()
+        };
+        let (_, r) = a.clone().pseudo_div_rem(b.clone());
+        if r.is_zero() {
+            return sequence;
+        }
+        let r = scale(r, a, b);
+        sequence.push(r);
+    }
+}
+
+impl<Ring: GcdDomain> Polynomial<Ring> {
+    /// The gcd of the coefficients times the unit of the leading coefficient (see
+    /// [`GcdDomain::unit`]), so that the primitive part is normalized: over the integers, the
+    /// gcd with the sign of the leading coefficient, and over a field the leading coefficient.
+    /// Zero for the zero polynomial.
+    pub fn content(&self) -> Ring {
+        let gcd = self.coeffs.iter().fold(Ring::zero(), |acc, c| acc.gcd(c));
+        match self.leading_coefficient() {
+            Some(lc) => gcd * lc.unit(),
+            None => gcd,
+        }
+    }
+
+    /// `self` divided by its content, which has coprime coefficients and a normalized leading
+    /// coefficient: positive over the integers, one over a field.
+    #[must_use]
+    pub fn primitive_part(self) -> Self {
+        let content = self.content();
+        if content.is_zero() {
+            return self;
+        }
+        let coeffs = self.coeffs.into_iter().map(|c| {
+            c.exact_div(&content)
+                .expect("the content divides the coefficients")
+        });
+        Polynomial::new(coeffs.collect())
+    }
+
+    /// The primitive polynomial remainder sequence of `self` and `other`, where each polynomial
+    /// after the first two is the primitive part of the pseudo-remainder of the previous two.
+    ///
+    /// The polynomial of higher degree comes first. Zero polynomials are left out.
+    pub fn primitive_prs(self, other: Self) -> Vec<Self> {
+        remainder_sequence(self, other, |r, _, _| r.primitive_part())
+    }
+}
+
+/// Gcds of polynomials over a gcd domain are the gcd of the contents times the gcd of the
+/// primitive parts, the last polynomial of their primitive remainder sequence.
+impl<Ring: GcdDomain> GcdDomain for Polynomial<Ring> {
+    fn normalize(&self) -> Self {
+        let unit = self.unit();
+        let coeffs = self.coeffs.iter().map(|c| {
+            c.exact_div(&unit.coeffs[0])
+                .expect("units divide everything")
+        });
+        Polynomial::new(coeffs.collect())
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        let content = self.content().gcd(&other.content());
+        let sequence = self
+            .clone()
+            .primitive_part()
+            .primitive_prs(other.clone().primitive_part());
+        match sequence.last() {
+            Some(g) => g.clone().primitive_part().scalar_mul(content),
+            None => Polynomial::zero(),
+        }
+    }
+
+    fn unit(&self) -> Self {
+        let unit = self
+            .leading_coefficient()
+            .map_or_else(Ring::one, GcdDomain::unit);
+        Polynomial::new(vec![unit])
+    }
+}
+
+impl Polynomial<BigInt> {
+    /// The gcd of `self` and `other` in `Z[x]`, computed without leaving the integers. It has a
+    /// positive leading coefficient, and its content is the gcd of the contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff_types::Polynomial;
+    /// # use num::BigInt;
+    /// let p = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+    /// // gcd(6x^2 - 6, 4x^2 + 8x + 4) = 2(x + 1)
+    /// assert_eq!(p(&[-6, 0, 6]).integer_gcd(p(&[4, 8, 4])), p(&[2, 2]));
+    /// ```
+    pub fn integer_gcd(self, other: Self) -> Self {
+        let content = Integer::gcd(&self.content(), &other.content());
+        let sequence = self
+            .primitive_part()
+            .subresultant_prs(other.primitive_part());
+        match sequence.last() {
+            Some(g) if g.degree() == Some(0) => Polynomial::new(vec![content]),
+            Some(g) => g.clone().primitive_part().scalar_mul(content),
+            None => Polynomial::zero(),
+        }
+    }
+}
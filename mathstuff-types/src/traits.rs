@@ -1,6 +1,8 @@
 use std::ops::{Div, Neg};
 
-use num::{BigInt, BigRational, One, Zero};
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+use crate::arith::{self, mul_karatsuba};
 
 /// Performs checked inverse.
 pub trait CheckedInv {
@@ -13,6 +15,98 @@ pub trait CommutativeRing: Zero + One + Neg<Output = Self> + Clone {
     fn sub(self, other: Self) -> Self {
         self.add(other.neg())
     }
+
+    /// The coefficients of the product of the polynomials with the coefficients `a` and `b`, in
+    /// order of increasing degree. Rings can override this with a faster multiplication, the
+    /// default uses [`mul_karatsuba`].
+    fn mul_slices(a: &[Self], b: &[Self]) -> Vec<Self> {
+        mul_karatsuba(a, b)
+    }
+}
+
+/// A commutative ring without zero divisors, where `a * b` is only zero if `a` or `b` is.
+pub trait IntegralDomain: CommutativeRing {
+    /// The quotient `self / other` if `other` divides `self`, which is unique when `other` is
+    /// nonzero. `None` if `other` is zero or does not divide `self`.
+    fn exact_div(&self, other: &Self) -> Option<Self>;
+}
+
+/// An integral domain where any two elements have a greatest common divisor.
+pub trait GcdDomain: IntegralDomain {
+    /// The representative of `self` among its associates `u * self` for the units `u`, like the
+    /// absolute value of an integer or the monic multiple of a polynomial over a field. Zero for
+    /// zero.
+    fn normalize(&self) -> Self;
+
+    /// The greatest common divisor of `self` and `other`, a common divisor that every common
+    /// divisor divides, in the normal form of [`GcdDomain::normalize`]. Zero only if both are
+    /// zero.
+    fn gcd(&self, other: &Self) -> Self;
+
+    /// The unit `u` such that `self = u * self.normalize()`, one for zero.
+    fn unit(&self) -> Self {
+        if self.is_zero() {
+            return Self::one();
+        }
+        self.exact_div(&self.normalize())
+            .expect("an element is a multiple of its normal form")
+    }
+
+    /// The least common multiple of `self` and `other`, in the normal form of
+    /// [`GcdDomain::normalize`].
+    fn lcm(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let cofactor = other
+            .exact_div(&self.gcd(other))
+            .expect("the gcd divides both elements");
+        (self.clone() * cofactor).normalize()
+    }
+}
+
+/// A gcd domain where every nonzero element that is not a unit is a product of irreducible
+/// elements, which are unique up to their order and units. The polynomials over a unique
+/// factorization domain form one too, by Gauss's lemma.
+pub trait UniqueFactorizationDomain: GcdDomain {}
+
+/// An integral domain with a division with remainder, where the remainder is smaller than the
+/// divisor, so that gcds can be computed with Euclid's algorithm.
+pub trait EuclideanDomain: UniqueFactorizationDomain {
+    /// Returns `(q, r)` such that `self = q * other + r`, where `r` is smaller than `other`: of
+    /// lower absolute value for integers, of lower degree for polynomials over a field.
+    ///
+    /// # Panics
+    ///
+    /// If `other` is zero.
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+
+    /// The extended Euclidean algorithm. Returns `(g, s, t)` such that `s * self + t * other = g`,
+    /// where `g` is the gcd of `self` and `other`.
+    fn gcd_ext(&self, other: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Self::one(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            r0 = std::mem::replace(&mut r1, r);
+            let s = CommutativeRing::sub(s0, q.clone() * s1.clone());
+            s0 = std::mem::replace(&mut s1, s);
+            let t = CommutativeRing::sub(t0, q * t1.clone());
+            t0 = std::mem::replace(&mut t1, t);
+        }
+
+        let unit = r0.unit();
+        let div = |x: Self| x.exact_div(&unit).expect("units divide everything");
+        (div(r0), div(s0), div(t0))
+    }
+}
+
+/// A ring whose characteristic is known: the least `n > 0` such that `1 + ... + 1` (`n` times) is
+/// zero, or zero if there is none.
+pub trait Characteristic {
+    fn characteristic() -> u64;
 }
 
 pub trait FromUsize {
@@ -25,6 +119,12 @@ impl FromUsize for BigInt {
     }
 }
 
+impl FromUsize for i64 {
+    fn from_usize(n: usize) -> Self {
+        Self::try_from(n).unwrap()
+    }
+}
+
 impl FromUsize for BigRational {
     fn from_usize(n: usize) -> Self {
         Self::from(BigInt::from(n))
@@ -50,12 +150,135 @@ impl CheckedInv for BigRational {
 }
 
 /// The ring of integers (`Z`)
-impl CommutativeRing for BigInt {}
+impl CommutativeRing for BigInt {
+    fn mul_slices(a: &[Self], b: &[Self]) -> Vec<Self> {
+        arith::mul_integers(a, b)
+    }
+}
 
 impl CommutativeRing for i64 {}
 
 /// The ring of rationals (`Q`)
-impl CommutativeRing for BigRational {}
+impl CommutativeRing for BigRational {
+    fn mul_slices(a: &[Self], b: &[Self]) -> Vec<Self> {
+        arith::mul_rationals(a, b)
+    }
+}
 
 /// The field of rationals (`Q`)
 impl Field for BigRational {}
+
+/// Fields have no zero divisors, and every nonzero element divides every other.
+impl<F: Field> IntegralDomain for F {
+    fn exact_div(&self, other: &Self) -> Option<Self> {
+        other.checked_inv().map(|inv| self.clone() * inv)
+    }
+}
+
+/// Every nonzero element of a field is a unit, with the normal form one.
+impl<F: Field> GcdDomain for F {
+    fn normalize(&self) -> Self {
+        if self.is_zero() {
+            Self::zero()
+        } else {
+            Self::one()
+        }
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        if self.is_zero() {
+            other.normalize()
+        } else {
+            Self::one()
+        }
+    }
+}
+
+impl<F: Field> UniqueFactorizationDomain for F {}
+
+/// Division by a nonzero element of a field leaves no remainder.
+impl<F: Field> EuclideanDomain for F {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let q = self.exact_div(other).expect("division by zero");
+        (q, Self::zero())
+    }
+}
+
+impl IntegralDomain for BigInt {
+    fn exact_div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        let (q, r) = Integer::div_rem(self, other);
+        r.is_zero().then_some(q)
+    }
+}
+
+/// Integers are normalized to their absolute value.
+impl GcdDomain for BigInt {
+    fn normalize(&self) -> Self {
+        self.abs()
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        Integer::gcd(self, other)
+    }
+}
+
+impl UniqueFactorizationDomain for BigInt {}
+
+impl EuclideanDomain for BigInt {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Integer::div_rem(self, other)
+    }
+}
+
+impl Characteristic for BigInt {
+    fn characteristic() -> u64 {
+        0
+    }
+}
+
+impl IntegralDomain for i64 {
+    fn exact_div(&self, other: &Self) -> Option<Self> {
+        let q = self.checked_div(*other)?;
+        (q * other == *self).then_some(q)
+    }
+}
+
+/// Integers are normalized to their absolute value.
+impl GcdDomain for i64 {
+    /// # Panics
+    ///
+    /// If `self` is `i64::MIN`, whose absolute value does not fit in an `i64`.
+    fn normalize(&self) -> Self {
+        self.checked_abs().expect("the absolute value of i64::MIN overflows")
+    }
+
+    /// # Panics
+    ///
+    /// If the gcd is `2^63`, which does not fit in an `i64`.
+    fn gcd(&self, other: &Self) -> Self {
+        Integer::gcd(self, other)
+    }
+}
+
+impl UniqueFactorizationDomain for i64 {}
+
+impl EuclideanDomain for i64 {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Integer::div_rem(self, other)
+    }
+}
+
+impl Characteristic for i64 {
+    fn characteristic() -> u64 {
+        0
+    }
+}
+
+impl Characteristic for BigRational {
+    fn characteristic() -> u64 {
+        0
+    }
+}
@@ -8,29 +8,91 @@ use tracing::debug;
 use crate::constant::Constant;
 use crate::simplify::ops::{self, Operation};
 use crate::simplify::{simplify_power, SimpleExpr};
-use crate::BasicAlgebraicExpr;
+use crate::{BasicAlgebraicExpr, ComputeResult};
 
 pub trait DifferentiableFunction {
-    fn diff(&self, params: Vec<SimpleExpr>) -> SimpleExpr;
+    fn diff(&self, params: Vec<SimpleExpr>) -> ComputeResult;
+}
+
+impl<F: Fn(Vec<SimpleExpr>) -> ComputeResult> DifferentiableFunction for F {
+    fn diff(&self, params: Vec<SimpleExpr>) -> ComputeResult {
+        self(params)
+    }
 }
 
-#[derive(Default)]
 pub struct Differentiator {
     pub functions: HashMap<String, Box<dyn DifferentiableFunction>>,
 }
 
-pub fn references(x: &BasicAlgebraicExpr, var: &str) -> bool {
-    match x {
-        BasicAlgebraicExpr::Product(v)
-        | BasicAlgebraicExpr::Sum(v)
-        | BasicAlgebraicExpr::Function(_, v) => v.iter().any(|x| references(x, var)),
-        BasicAlgebraicExpr::Factorial(x) => references(x, var),
-        BasicAlgebraicExpr::Pow(b) => references(&b.0, var) || references(&b.1, var),
-        BasicAlgebraicExpr::Symbol(s) => s == var,
-        BasicAlgebraicExpr::Numeric(_) => false,
+impl Default for Differentiator {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
+fn function(name: &str, arg: SimpleExpr) -> ComputeResult {
+    BasicAlgebraicExpr::Function(name.into(), vec![arg.into_inner()]).simplify()
+}
+
+fn first(params: Vec<SimpleExpr>) -> SimpleExpr {
+    params
+        .into_iter()
+        .next()
+        .expect("unary functions are called with one argument")
+}
+
+/// Derivatives of the elementary functions with respect to their argument.
+fn elementary_functions() -> HashMap<String, Box<dyn DifferentiableFunction>> {
+    let mut functions: HashMap<String, Box<dyn DifferentiableFunction>> = HashMap::new();
+    functions.insert(
+        "Exp".into(),
+        Box::new(|params: Vec<SimpleExpr>| function("Exp", first(params))),
+    );
+    functions.insert(
+        "Log".into(),
+        Box::new(|params: Vec<SimpleExpr>| {
+            simplify_power(first(params), Constant::negative_one().into())
+        }),
+    );
+    functions.insert(
+        "Sin".into(),
+        Box::new(|params: Vec<SimpleExpr>| function("Cos", first(params))),
+    );
+    functions.insert(
+        "Cos".into(),
+        Box::new(|params: Vec<SimpleExpr>| {
+            ops::Product.simplify(vec![
+                SimpleExpr::new_constant(Constant::negative_one()),
+                function("Sin", first(params))?,
+            ])
+        }),
+    );
+    // d/dx Tan[x] = Cos[x]^(-2)
+    functions.insert(
+        "Tan".into(),
+        Box::new(|params: Vec<SimpleExpr>| {
+            simplify_power(function("Cos", first(params))?, (-2).into())
+        }),
+    );
+    // d/dx ArcTan[x] = (1 + x^2)^(-1)
+    functions.insert(
+        "ArcTan".into(),
+        Box::new(|params: Vec<SimpleExpr>| {
+            let square = simplify_power(first(params), 2.into())?;
+            simplify_power(
+                ops::Sum.simplify(vec![1.into(), square])?,
+                Constant::negative_one().into(),
+            )
+        }),
+    );
+    functions
+}
+
+/// Whether `x` contains the symbol `var`.
+pub fn references(x: &BasicAlgebraicExpr, var: &str) -> bool {
+    x.any(|x| matches!(x, BasicAlgebraicExpr::Symbol(s) if s == var))
+}
+
 #[derive(Debug, Clone)]
 pub enum DifferentiationError {
     PowerReferencesVar,
@@ -46,9 +108,10 @@ impl From<crate::Undefined> for DifferentiationError {
 }
 
 impl Differentiator {
+    /// A differentiator that knows about `Exp`, `Log`, `Sin`, `Cos`, `Tan` and `ArcTan`.
     pub fn new() -> Self {
         Self {
-            functions: HashMap::new(),
+            functions: elementary_functions(),
         }
     }
     #[tracing::instrument(skip(self), level = "info")]
@@ -61,7 +124,7 @@ impl Differentiator {
         Ok(match x.into_inner() {
             Numeric(_) => SimpleExpr::new_constant(0.into()),
             Symbol(s) if s == wrt => SimpleExpr::new_constant(1.into()),
-            Symbol(s) => SimpleExpr::new_symbol(s),
+            Symbol(_) => SimpleExpr::new_constant(0.into()),
             Product(x) => {
                 // split the product into two parts, factors that references x, and factors that do not.
                 let (refs, mut norefs): (Vec<_>, Vec<_>) = x
@@ -132,7 +195,7 @@ impl Differentiator {
                 if references(&x, wrt) {
                     return Err(DifferentiationError::FactorialReferencesVar);
                 } else {
-                    SimpleExpr::assert(Factorial(x))
+                    SimpleExpr::new_constant(0.into())
                 }
             }
             Function(x, args) => {
@@ -142,7 +205,7 @@ impl Differentiator {
                         let arg = SimpleExpr::assert(arg);
                         let a2 = arg.clone();
                         return Ok(ops::Product
-                            .simplify(vec![self.differentiate(arg, wrt)?, f.diff(vec![a2])])?);
+                            .simplify(vec![self.differentiate(arg, wrt)?, f.diff(vec![a2])?])?);
                     }
                 }
                 return Err(DifferentiationError::UnrecognizedFunction);
@@ -1,8 +1,11 @@
 use std::ops::{Add, BitXor, Div, Mul, Neg, Sub};
 
-use num::BigRational;
+use mathstuff_types::traits::CommutativeRing;
+use num::{BigRational, One, Zero};
 
+use crate::constant::Constant;
 use crate::rational_expressions::SimplifiedRationalExpression;
+use crate::simplify::ops::{Operation, Product, Sum};
 use crate::simplify::SimpleExpr;
 use crate::{BasicAlgebraicExpr, ComputeResult, Undefined};
 
@@ -101,3 +104,49 @@ impl BitXor for BasicAlgebraicExpr {
         BasicAlgebraicExpr::Pow(Box::new((self, rhs)))
     }
 }
+
+// Simplified expressions form a ring, so that polynomials and matrices can have symbolic entries.
+// Sums and products of simplified expressions are always defined.
+
+impl Add for SimpleExpr {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Sum.simplify(vec![self, rhs])
+            .expect("sums of simplified expressions are defined")
+    }
+}
+
+impl Mul for SimpleExpr {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Product
+            .simplify(vec![self, rhs])
+            .expect("products of simplified expressions are defined")
+    }
+}
+
+impl Neg for SimpleExpr {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        SimpleExpr::new_constant(Constant::negative_one()) * self
+    }
+}
+
+impl Zero for SimpleExpr {
+    fn zero() -> Self {
+        SimpleExpr::new_constant(Constant::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.as_inner().is_zero()
+    }
+}
+
+impl One for SimpleExpr {
+    fn one() -> Self {
+        SimpleExpr::new_constant(Constant::one())
+    }
+}
+
+/// The ring of expressions, with the operations of the simplifier
+impl CommutativeRing for SimpleExpr {}
@@ -0,0 +1,272 @@
+//! Symbolic integration.
+//!
+//! [`integrate`] follows the integration procedure in Cohen's *Elementary Algorithms*: a table of
+//! integrals of the elementary functions, linearity, the derivative-divides substitution method
+//! and, if all of those fail, integrating the algebraic expansion of the integrand instead.
+//! Rational functions are integrated with Hermite reduction and the Lazard-Rioboo-Trager
+//! algorithm, and integrands in a tower of `Exp` and `Log` extensions of `Q(x)`, like
+//! `Exp[x] Log[x]` or `Log[Log[x]]`, with the transcendental Risch algorithm, which can also prove
+//! that no elementary integral exists. The Risch differential equation is only solved over
+//! `Q(x)`, so integrands like `Exp[Exp[x]]` that need it over a larger tower are only tried with
+//! the heuristics, and give [`IntegrationError::NotFound`] when those fail.
+
+pub(crate) mod rational;
+mod risch;
+
+use std::cell::RefCell;
+
+use crate::diff::Differentiator;
+use crate::polynomials::{free_of, substitute};
+use crate::simplify::ops::{Operation, Product, Sum};
+use crate::simplify::{simplify_power, SimpleExpr};
+use crate::{BasicAlgebraicExpr, ComputeResult, Constant};
+
+#[derive(Debug, Clone)]
+pub enum IntegrationError {
+    /// None of the methods found an antiderivative. This does not mean that the integral is not
+    /// elementary.
+    NotFound,
+    /// The integral was proven not to be elementary.
+    NonElementary,
+    Undefined,
+}
+
+impl From<crate::Undefined> for IntegrationError {
+    fn from(_: crate::Undefined) -> Self {
+        IntegrationError::Undefined
+    }
+}
+
+/// Finds an antiderivative of `u` with respect to `x`. The constant of integration is omitted.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::integrate::integrate;
+/// # use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("2 * x * Cos[x^2]")?.simplify().unwrap();
+/// assert_eq!(integrate(u, "x").unwrap(), parse("Sin[x^2]")?.simplify().unwrap());
+/// # Ok(())
+/// # }
+/// ```
+pub fn integrate(u: SimpleExpr, x: &str) -> Result<SimpleExpr, IntegrationError> {
+    let integrator = Integrator {
+        differentiator: Differentiator::new(),
+        non_elementary: RefCell::new(Vec::new()),
+    };
+
+    match integrator.integral(u.clone(), x)? {
+        Some(f) => Ok(f),
+        None if integrator.non_elementary.borrow().contains(&u) => {
+            Err(IntegrationError::NonElementary)
+        }
+        None => Err(IntegrationError::NotFound),
+    }
+}
+
+fn symbol(x: &str) -> BasicAlgebraicExpr {
+    BasicAlgebraicExpr::Symbol(x.into())
+}
+
+fn function(name: &str, arg: BasicAlgebraicExpr) -> ComputeResult {
+    BasicAlgebraicExpr::Function(name.into(), vec![arg]).simplify()
+}
+
+fn add(a: SimpleExpr, b: SimpleExpr) -> ComputeResult {
+    Sum.simplify(vec![a, b])
+}
+
+fn mul(a: SimpleExpr, b: SimpleExpr) -> ComputeResult {
+    Product.simplify(vec![a, b])
+}
+
+fn div(a: SimpleExpr, b: SimpleExpr) -> ComputeResult {
+    mul(a, simplify_power(b, Constant::negative_one().into())?)
+}
+
+/// Integrals of `x^n` and of the elementary functions applied to `x`.
+fn integral_table(u: &SimpleExpr, x: &str) -> ComputeResult<Option<SimpleExpr>> {
+    let var = symbol(x);
+
+    if free_of(u, &var) {
+        return mul(u.clone(), SimpleExpr::assert(var)).map(Some);
+    }
+
+    Ok(Some(match u.as_inner() {
+        BasicAlgebraicExpr::Symbol(_) => div(simplify_power(u.clone(), 2.into())?, 2.into())?,
+        BasicAlgebraicExpr::Pow(p) if p.0 == var && free_of(&p.1, &var) => {
+            if p.1 == -1 {
+                function("Log", var)?
+            } else {
+                let exp = add(SimpleExpr::assert(p.1.clone()), 1.into())?;
+                div(simplify_power(SimpleExpr::assert(var), exp.clone())?, exp)?
+            }
+        }
+        BasicAlgebraicExpr::Function(name, args) if args.len() == 1 && args[0] == var => {
+            match &**name {
+                "Exp" => u.clone(),
+                "Log" => add(
+                    mul(SimpleExpr::assert(var.clone()), u.clone())?,
+                    mul((-1).into(), SimpleExpr::assert(var))?,
+                )?,
+                "Sin" => mul((-1).into(), function("Cos", var)?)?,
+                "Cos" => function("Sin", var)?,
+                "Tan" => mul((-1).into(), function("Log", function("Cos", var)?.into_inner())?)?,
+                _ => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    }))
+}
+
+/// Collects the candidates for the substitution method: functions and their arguments, and
+/// powers together with their bases and exponents.
+fn trial_substitutions(u: &BasicAlgebraicExpr, out: &mut Vec<BasicAlgebraicExpr>) {
+    fn push(x: &BasicAlgebraicExpr, out: &mut Vec<BasicAlgebraicExpr>) {
+        if !x.is_constant() && !out.contains(x) {
+            out.push(x.clone());
+        }
+    }
+
+    match u {
+        BasicAlgebraicExpr::Numeric(_) | BasicAlgebraicExpr::Symbol(_) => {}
+        BasicAlgebraicExpr::Function(_, args) => {
+            push(u, out);
+            for arg in args {
+                push(arg, out);
+                trial_substitutions(arg, out);
+            }
+        }
+        BasicAlgebraicExpr::Pow(p) => {
+            push(u, out);
+            push(&p.0, out);
+            push(&p.1, out);
+            trial_substitutions(&p.0, out);
+            trial_substitutions(&p.1, out);
+        }
+        BasicAlgebraicExpr::Sum(args) | BasicAlgebraicExpr::Product(args) => {
+            args.iter().for_each(|x| trial_substitutions(x, out));
+        }
+        BasicAlgebraicExpr::Factorial(x) => trial_substitutions(x, out),
+    }
+}
+
+struct Integrator {
+    differentiator: Differentiator,
+    /// The integrands the Risch algorithm proved to have no elementary integral, so that
+    /// [`integrate`] can report it without running the algorithm again.
+    non_elementary: RefCell<Vec<SimpleExpr>>,
+}
+
+impl Integrator {
+    #[tracing::instrument(skip(self), level = "debug", ret)]
+    fn integral(&self, u: SimpleExpr, x: &str) -> ComputeResult<Option<SimpleExpr>> {
+        if let Some(f) = integral_table(&u, x)? {
+            return Ok(Some(f));
+        }
+
+        if let Some(f) = self.linear_properties(&u, x)? {
+            return Ok(Some(f));
+        }
+
+        if let Some(f) = rational::integrate_rational(&u, x)? {
+            return Ok(Some(f));
+        }
+
+        match risch::integrate_transcendental(&u, x) {
+            Ok(Some(f)) => return Ok(Some(f)),
+            Err(IntegrationError::Undefined) => return Err(crate::Undefined),
+            Err(IntegrationError::NonElementary) => self.non_elementary.borrow_mut().push(u.clone()),
+            _ => {}
+        }
+
+        if let Some(f) = self.substitution_method(&u, x)? {
+            return Ok(Some(f));
+        }
+
+        let expanded = u.clone().expand()?;
+        if expanded != u {
+            return self.integral(expanded, x);
+        }
+
+        Ok(None)
+    }
+
+    /// `∫ c f(x) dx = c ∫ f(x) dx` and `∫ f(x) + g(x) dx = ∫ f(x) dx + ∫ g(x) dx`
+    fn linear_properties(&self, u: &SimpleExpr, x: &str) -> ComputeResult<Option<SimpleExpr>> {
+        let var = symbol(x);
+
+        match u.as_inner() {
+            BasicAlgebraicExpr::Product(factors) => {
+                let (free, dependent): (Vec<_>, Vec<_>) = factors
+                    .iter()
+                    .cloned()
+                    .map(SimpleExpr::assert)
+                    .partition(|f| free_of(f, &var));
+
+                if free.is_empty() {
+                    return Ok(None);
+                }
+
+                let free = Product.simplify(free)?;
+                let Some(f) = self.integral(Product.simplify(dependent)?, x)? else {
+                    return Ok(None);
+                };
+
+                mul(free, f).map(Some)
+            }
+            BasicAlgebraicExpr::Sum(terms) => {
+                let mut result = SimpleExpr::from(0);
+                for term in terms {
+                    let Some(f) = self.integral(SimpleExpr::assert(term.clone()), x)? else {
+                        return Ok(None);
+                    };
+                    result = add(result, f)?;
+                }
+                Ok(Some(result))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The derivative-divides method: if `u = f(g(x)) g'(x)` for some candidate `g`, then
+    /// `∫ u dx = F(g(x))` where `F` is an antiderivative of `f`.
+    fn substitution_method(&self, u: &SimpleExpr, x: &str) -> ComputeResult<Option<SimpleExpr>> {
+        let var = symbol(x);
+
+        let mut candidates = Vec::new();
+        trial_substitutions(u, &mut candidates);
+
+        let mut v = format!("{x}'");
+        while !free_of(u, symbol(&v)) {
+            v.push('\'');
+        }
+        let v_expr = symbol(&v);
+
+        for g in candidates {
+            if g == var || free_of(&g, &var) {
+                continue;
+            }
+
+            let g = SimpleExpr::assert(g);
+            let Ok(dg) = self.differentiator.differentiate(g.clone(), x) else {
+                continue;
+            };
+
+            if dg.is_zero() {
+                continue;
+            }
+
+            let quotient = div(u.clone(), dg)?;
+            let w = substitute(&quotient, &g, &v_expr).simplify()?;
+
+            if free_of(&w, &var)
+            && let Some(f) = self.integral(w, &v)? {
+                return substitute(&f, &v_expr, &g).simplify().map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,317 @@
+//! Integration of rational functions of `x` with rational coefficients.
+//!
+//! The algebraic work is done by [`mathstuff_polynomials::integration`]; this module converts
+//! between expressions and polynomials, and writes the logarithmic part in terms of `Log`,
+//! `ArcTan` and square roots where the roots of the log coefficients are at most quadratic.
+//! Sums over the roots of higher degree polynomials are left as `RootSum[q(t), t Log[s(t, x)]]`.
+//!
+//! The conversions are generic over the coefficient field, so that [`super::risch`] can use them
+//! for polynomials in a monomial `θ_i` with coefficients in a tower of extensions of `Q(x)`.
+
+use mathstuff_polynomials::factorization::rational_roots;
+use mathstuff_polynomials::integration::{
+    integrate_rational_function, log_to_atan_sqrt, LogTerm, RationalIntegral,
+};
+use mathstuff_polynomials::risch::Element;
+use mathstuff_types::fraction::RationalFunction;
+use mathstuff_types::traits::{CheckedInv, Field, FromUsize, GcdDomain};
+use mathstuff_types::Polynomial;
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
+use super::{add, div, function, mul, symbol};
+use crate::polynomials::free_of;
+use crate::simplify::{simplify_power, SimpleExpr};
+use crate::{BasicAlgebraicExpr, ComputeResult};
+
+/// The coefficient fields of the polynomials in an antiderivative: `Q`, or a tower of extensions
+/// of `Q(x)` for polynomials in a monomial.
+pub(super) trait Coefficient: Field + FromUsize + PartialEq {
+    fn from_rational(c: BigRational) -> Self;
+
+    /// The value of a constant coefficient.
+    fn to_rational(&self) -> Option<BigRational>;
+}
+
+impl Coefficient for BigRational {
+    fn from_rational(c: BigRational) -> Self {
+        c
+    }
+
+    fn to_rational(&self) -> Option<BigRational> {
+        Some(self.clone())
+    }
+}
+
+impl Coefficient for Element {
+    fn from_rational(c: BigRational) -> Self {
+        Element::constant(c)
+    }
+
+    fn to_rational(&self) -> Option<BigRational> {
+        self.to_constant()
+    }
+}
+
+fn constant(c: BigRational) -> SimpleExpr {
+    SimpleExpr::new_constant(c.into())
+}
+
+/// A rational coefficient as an expression.
+pub(super) fn rational_to_expr(c: &BigRational) -> ComputeResult {
+    Ok(constant(c.clone()))
+}
+
+/// Writes `u` as a quotient of polynomials, given the quotients for the leaves of `u` that are
+/// not sums, products or integer powers. Returns `None` if `leaf` does.
+pub(super) fn to_fraction_with<F: Field>(
+    u: &BasicAlgebraicExpr,
+    leaf: &impl Fn(&BasicAlgebraicExpr) -> Option<RationalFunction<F>>,
+) -> Option<RationalFunction<F>> {
+    Some(match u {
+        BasicAlgebraicExpr::Sum(terms) => terms
+            .iter()
+            .try_fold(RationalFunction::zero(), |acc, term| {
+                Some(acc + to_fraction_with(term, leaf)?)
+            })?,
+        BasicAlgebraicExpr::Product(factors) => factors
+            .iter()
+            .try_fold(RationalFunction::one(), |acc, factor| {
+                Some(acc * to_fraction_with(factor, leaf)?)
+            })?,
+        BasicAlgebraicExpr::Pow(p) if let BasicAlgebraicExpr::Numeric(exp) = &p.1 => {
+            let exp = exp.as_integer()?;
+            let base = to_fraction_with(&p.0, leaf)?;
+            let base = if exp.is_negative() {
+                base.checked_inv()?
+            } else {
+                base
+            };
+            base.pow(exp.abs().to_usize()?)
+        }
+        u => leaf(u)?,
+    })
+}
+
+/// Writes `u` as a quotient of polynomials in `x`, if it is a rational function of `x` with
+/// rational coefficients.
+pub(crate) fn to_fraction(
+    u: &BasicAlgebraicExpr,
+    x: &str,
+) -> Option<RationalFunction<BigRational>> {
+    to_fraction_with(u, &|leaf| match leaf {
+        BasicAlgebraicExpr::Numeric(c) => Some(RationalFunction::constant(c.clone().into_inner())),
+        BasicAlgebraicExpr::Symbol(s) if s == x => Some(RationalFunction::x()),
+        _ => None,
+    })
+}
+
+/// The expression `Σ p_i var^i`, where the coefficients are written with `coeff`.
+pub(super) fn polynomial_to_expr<F: Coefficient>(
+    p: &Polynomial<F>,
+    var: &SimpleExpr,
+    coeff: &impl Fn(&F) -> ComputeResult,
+) -> ComputeResult {
+    p.clone()
+        .into_iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_zero())
+        .try_fold(0.into(), |acc, (i, c)| {
+            let power = simplify_power(var.clone(), SimpleExpr::from(i as i32))?;
+            add(acc, mul(coeff(&c)?, power)?)
+        })
+}
+
+/// Writes `|r|` as `w^2 k` with `k` a square-free integer, returning `(w, k)`.
+pub(crate) fn square_root_parts(r: &BigRational) -> (BigRational, BigInt) {
+    // |p / q| = |p q| / q^2
+    let mut k = (r.numer() * r.denom()).abs();
+    let mut m = BigInt::one();
+    let mut f = BigInt::from(2);
+    while &f * &f <= k {
+        let square = &f * &f;
+        while (&k % &square).is_zero() {
+            k /= &square;
+            m *= &f;
+        }
+        f += 1;
+    }
+    (BigRational::new(m, r.denom().abs()), k)
+}
+
+/// `p √k` if `odd`, and `p` otherwise.
+fn with_sqrt(p: SimpleExpr, odd: bool, sqrt_k: &SimpleExpr) -> ComputeResult {
+    if odd {
+        mul(p, sqrt_k.clone())
+    } else {
+        Ok(p)
+    }
+}
+
+/// Writes `Σ t Log[s(t, var)]`, summed over the roots `t` of `q`, as an expression, where the
+/// coefficients of `s` are written with `coeff`. The coefficients of `q` must be constants.
+pub(super) fn log_term<F: Coefficient>(
+    LogTerm { roots_of, argument }: LogTerm<F>,
+    var: &SimpleExpr,
+    x: &str,
+    coeff: &impl Fn(&F) -> ComputeResult,
+) -> ComputeResult {
+    let to_expr = |p: &Polynomial<F>| polynomial_to_expr(p, var, coeff);
+    let lift = |p: &Polynomial<BigRational>| -> Polynomial<F> {
+        p.clone().into_iter().map(F::from_rational).collect()
+    };
+
+    let mut q: Polynomial<BigRational> = roots_of
+        .normalize()
+        .into_iter()
+        .map(|c| c.to_rational().expect("the residues are constants"))
+        .collect();
+    let mut result = SimpleExpr::from(0);
+
+    for c in rational_roots(&q) {
+        let t = F::from_rational(c.clone());
+        let s = Polynomial::new_trim_zeroes(
+            argument.clone().into_iter().map(|p| p.eval(&t)).collect(),
+        );
+        let log = function("Log", to_expr(&s.normalize())?.into_inner())?;
+        result = add(result, mul(constant(c.clone()), log)?)?;
+        q = q
+            .exact_div(Polynomial::new(vec![-c, BigRational::one()]))
+            .expect("c is a root of q");
+    }
+
+    let argument: Vec<_> = argument
+        .into_iter()
+        .map(|p| p.div_rem(lift(&q)).1)
+        .collect();
+
+    match q.degree() {
+        None | Some(0) => Ok(result),
+        Some(2) => {
+            // q = t^2 + b t + c has the roots u ± √r, and s(t, var) = s0 + t s1
+            let part = |i| {
+                Polynomial::new_trim_zeroes(
+                    argument
+                        .iter()
+                        .map(|p| p.clone().into_iter().nth(i).unwrap_or_else(F::zero))
+                        .collect(),
+                )
+            };
+            let (s0, s1) = (part(0), part(1));
+            let u = -q.coeff_at(1).clone() / BigRational::from_integer(2.into());
+            let r = &u * &u - q.coeff_at(0);
+
+            let (w, k) = square_root_parts(&r);
+            let sqrt_k = simplify_power(
+                SimpleExpr::new_constant(k.clone().into()),
+                SimpleExpr::new_constant(BigRational::new(1.into(), 2.into()).into()),
+            )?;
+
+            if r.is_positive() {
+                for w in [w.clone(), -w] {
+                    let alpha = add(constant(u.clone()), mul(constant(w), sqrt_k.clone())?)?;
+                    let s = add(to_expr(&s0)?, mul(alpha.clone(), to_expr(&s1)?)?)?;
+                    result = add(result, mul(alpha, function("Log", s.into_inner())?)?)?;
+                }
+                return Ok(result);
+            }
+
+            // the roots are u ± i v with v = w √k, and
+            // Σ t log(a + t b) = u log(A^2 + B^2) + v i log((A + i B) / (A - i B))
+            // where A = s0 + u s1 and B = v s1
+            let a = s0 + s1.clone().scalar_mul(F::from_rational(u.clone()));
+            let norm = a.clone() * a.clone()
+                + (s1.clone() * s1.clone()).scalar_mul(F::from_rational(-r));
+            let log = function("Log", to_expr(&norm.normalize())?.into_inner())?;
+            result = add(result, mul(constant(u), log)?)?;
+
+            let two_v = mul(
+                constant(w.clone() * BigRational::from_integer(2.into())),
+                sqrt_k.clone(),
+            )?;
+            let b = s1.scalar_mul(F::from_rational(w));
+            let k = F::from_rational(BigRational::from_integer(k));
+            for (p, odd) in log_to_atan_sqrt((a, false), (b, true), &k) {
+                let arg = with_sqrt(to_expr(&p)?, odd, &sqrt_k)?;
+                let atan = function("ArcTan", arg.into_inner())?;
+                result = add(result, mul(two_v.clone(), atan)?)?;
+            }
+            Ok(result)
+        }
+        Some(_) => {
+            let mut t = String::from("t");
+            while t == x {
+                t.push('\'');
+            }
+            let t_var = SimpleExpr::new_symbol(t);
+
+            // making s monic in var only changes the sum by a constant
+            let q = lift(&q);
+            let lc = argument.last().cloned().unwrap_or_else(Polynomial::one);
+            let argument = if let Some(inv) = lc.inverse_mod(&q) {
+                argument
+                    .into_iter()
+                    .map(|p| (p * inv.clone()).div_rem(q.clone()).1)
+                    .collect()
+            } else {
+                argument
+            };
+
+            let s = argument
+                .iter()
+                .enumerate()
+                .try_fold(0.into(), |acc, (i, p)| {
+                    let power = simplify_power(var.clone(), SimpleExpr::from(i as i32))?;
+                    add(acc, mul(polynomial_to_expr(p, &t_var, coeff)?, power)?)
+                })?;
+            let summand = mul(t_var.clone(), function("Log", s.into_inner())?)?;
+
+            let root_sum = BasicAlgebraicExpr::Function(
+                "RootSum".into(),
+                vec![
+                    polynomial_to_expr(&q, &t_var, coeff)?.into_inner(),
+                    summand.into_inner(),
+                ],
+            )
+            .simplify()?;
+            add(result, root_sum)
+        }
+    }
+}
+
+/// Writes the integral found by [`integrate_rational_function`] as an expression in `x`.
+pub(super) fn rational_integral_to_expr(
+    RationalIntegral {
+        numerator,
+        denominator,
+        logs,
+    }: RationalIntegral<BigRational>,
+    x: &str,
+) -> ComputeResult {
+    let var = SimpleExpr::new_symbol(x.into());
+    let mut result = div(
+        polynomial_to_expr(&numerator, &var, &rational_to_expr)?,
+        polynomial_to_expr(&denominator, &var, &rational_to_expr)?,
+    )?;
+    for log in logs {
+        result = add(result, log_term(log, &var, x, &rational_to_expr)?)?;
+    }
+    Ok(result)
+}
+
+/// Integrates `u` if it is a rational function of `x` with rational coefficients and a
+/// denominator that depends on `x`. Polynomials are left to the heuristics.
+pub(super) fn integrate_rational(u: &SimpleExpr, x: &str) -> ComputeResult<Option<SimpleExpr>> {
+    if free_of(u, symbol(x)) {
+        return Ok(None);
+    }
+
+    let Some((a, d)) = to_fraction(u, x).map(RationalFunction::into_parts) else {
+        return Ok(None);
+    };
+
+    if d.degree() == Some(0) {
+        return Ok(None);
+    }
+
+    rational_integral_to_expr(integrate_rational_function(a, d), x).map(Some)
+}
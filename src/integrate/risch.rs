@@ -0,0 +1,230 @@
+//! The transcendental Risch algorithm for integrands in a tower of `Exp` and `Log` extensions of
+//! `Q(x)`, see [`mathstuff_polynomials::risch`].
+//!
+//! The tower is built from the `Exp` and `Log` subexpressions of the integrand from the inside
+//! out: the functions whose arguments lie in the tower so far are added as monomials, the `Exp`
+//! before the `Log`. All the `Exp[n u]` for integers `n` and a common `u` are powers of one
+//! monomial, and all the `Log[u]` for a common `u` are one monomial. Integrands with other
+//! functions, or with functions that are not monomials over the tower below them, like `Log[x^2]`
+//! next to `Log[x]`, are not handled.
+
+use mathstuff_polynomials::risch::{risch_integrate, Element, RischError, Tower};
+use mathstuff_types::fraction::RationalFunction;
+use mathstuff_types::traits::CheckedInv;
+use num::{BigInt, BigRational, Integer, One, ToPrimitive, Zero};
+
+use super::rational::{
+    log_term, polynomial_to_expr, rational_integral_to_expr, rational_to_expr, to_fraction_with,
+};
+use super::{add, div, function, IntegrationError};
+use crate::simplify::SimpleExpr;
+use crate::{BasicAlgebraicExpr, ComputeResult};
+
+/// Collects the function applications in `u` and in their arguments, the arguments first.
+fn functions<'a>(u: &'a BasicAlgebraicExpr, out: &mut Vec<&'a BasicAlgebraicExpr>) {
+    match u {
+        BasicAlgebraicExpr::Function(_, args) => {
+            args.iter().for_each(|x| functions(x, out));
+            if !out.contains(&u) {
+                out.push(u);
+            }
+        }
+        BasicAlgebraicExpr::Sum(args) | BasicAlgebraicExpr::Product(args) => {
+            args.iter().for_each(|x| functions(x, out));
+        }
+        BasicAlgebraicExpr::Pow(p) => {
+            functions(&p.0, out);
+            functions(&p.1, out);
+        }
+        BasicAlgebraicExpr::Factorial(x) => functions(x, out),
+        BasicAlgebraicExpr::Numeric(_) | BasicAlgebraicExpr::Symbol(_) => {}
+    }
+}
+
+/// The tower of the integrand, the expressions of its monomials `θ_i` and the elements that the
+/// `Exp` and `Log` subexpressions stand for.
+struct Extensions<'a> {
+    tower: Tower,
+    thetas: Vec<SimpleExpr>,
+    nodes: Vec<(&'a BasicAlgebraicExpr, Element)>,
+}
+
+impl<'a> Extensions<'a> {
+    /// Builds the tower of `u`. `None` if `u` has functions other than `Exp` and `Log`, or ones
+    /// that are not monomials over the tower below them.
+    fn new(u: &'a BasicAlgebraicExpr, x: &str) -> Option<Self> {
+        let mut pending = vec![];
+        functions(u, &mut pending);
+
+        let mut this = Extensions {
+            tower: Tower::new(),
+            thetas: vec![],
+            nodes: vec![],
+        };
+        while !pending.is_empty() {
+            let (mut exps, mut logs, mut rest) = (vec![], vec![], vec![]);
+            for node in pending {
+                let BasicAlgebraicExpr::Function(name, args) = node else {
+                    unreachable!()
+                };
+                let [arg] = &**args else {
+                    return None;
+                };
+                let Some(arg) = this.to_element(arg, x) else {
+                    rest.push(node);
+                    continue;
+                };
+                match &**name {
+                    "Exp" => exps.push((node, arg)),
+                    "Log" => logs.push((node, arg)),
+                    _ => return None,
+                }
+            }
+
+            if exps.is_empty() && logs.is_empty() {
+                return None;
+            }
+            this.push_exps(exps, x)?;
+            this.push_logs(logs, x)?;
+            pending = rest;
+        }
+        Some(this)
+    }
+
+    /// Adds the `Exp[r u]` for rationals `r` and a common `u` as the powers of one monomial
+    /// `Exp[u g / l]`, where `l` is the lcm of the denominators and `g` the gcd of the numerators
+    /// of the ratios `r`.
+    fn push_exps(
+        &mut self,
+        mut exps: Vec<(&'a BasicAlgebraicExpr, Element)>,
+        x: &str,
+    ) -> Option<()> {
+        let var = SimpleExpr::new_symbol(x.into());
+        while let Some((_, first)) = exps.first().cloned() {
+            if first.is_zero() {
+                return None;
+            }
+            let ratio = |u: &Element| (u.clone() / first.clone()).to_constant();
+            let (group, rest): (Vec<_>, Vec<_>) =
+                exps.into_iter().partition(|(_, u)| ratio(u).is_some());
+            exps = rest;
+
+            let ratios: Vec<_> = group.iter().filter_map(|(_, u)| ratio(u)).collect();
+            let l = ratios
+                .iter()
+                .fold(BigInt::one(), |acc, r| acc.lcm(r.denom()));
+            let g = ratios
+                .iter()
+                .fold(BigInt::zero(), |acc, r| acc.gcd(&(r * &l).to_integer()));
+            let scale = BigRational::new(g, l);
+            let base = first * Element::constant(scale.clone());
+
+            let theta = self.tower.push_exp(&base)?;
+            let theta_expr = function("Exp", self.to_expr(&base, &var).ok()?.into_inner()).ok()?;
+            self.thetas.push(theta_expr);
+            for ((node, _), r) in group.into_iter().zip(&ratios) {
+                let n = (r / &scale).to_integer().to_i64()?;
+                let power = num::pow(theta.clone(), n.unsigned_abs() as usize);
+                let power = if n.is_negative() {
+                    power.checked_inv()?
+                } else {
+                    power
+                };
+                self.nodes.push((node, power));
+            }
+        }
+        Some(())
+    }
+
+    /// Adds a monomial for each distinct argument of the `Log`.
+    fn push_logs(&mut self, logs: Vec<(&'a BasicAlgebraicExpr, Element)>, x: &str) -> Option<()> {
+        let var = SimpleExpr::new_symbol(x.into());
+        let mut added: Vec<(Element, Element)> = vec![];
+        for (node, u) in logs {
+            let theta = match added.iter().find(|(v, _)| *v == u) {
+                Some((_, theta)) => theta.clone(),
+                None => {
+                    let theta = self.tower.push_log(&u)?;
+                    let theta_expr =
+                        function("Log", self.to_expr(&u, &var).ok()?.into_inner()).ok()?;
+                    self.thetas.push(theta_expr);
+                    added.push((u, theta.clone()));
+                    theta
+                }
+            };
+            self.nodes.push((node, theta));
+        }
+        Some(())
+    }
+
+    /// Writes `u` as an element of the tower, if it is a rational function of `x` and the
+    /// functions in the tower.
+    fn to_element(&self, u: &BasicAlgebraicExpr, x: &str) -> Option<Element> {
+        let f = to_fraction_with(u, &|leaf| {
+            let e = match leaf {
+                BasicAlgebraicExpr::Numeric(c) => Element::constant(c.clone().into_inner()),
+                BasicAlgebraicExpr::Symbol(s) if s == x => Element::x(),
+                _ => self.nodes.iter().find(|(node, _)| *node == leaf)?.1.clone(),
+            };
+            Some(RationalFunction::constant(e))
+        })?;
+        f.to_constant()
+    }
+
+    /// Writes an element of the tower as an expression in `x` and the monomials.
+    fn to_expr(&self, e: &Element, x: &SimpleExpr) -> ComputeResult {
+        match e {
+            Element::Base(f) => div(
+                polynomial_to_expr(f.numerator(), x, &rational_to_expr)?,
+                polynomial_to_expr(f.denominator(), x, &rational_to_expr)?,
+            ),
+            Element::Extension(i, f) => {
+                let theta = &self.thetas[i - 1];
+                let coeff = |c: &Element| self.to_expr(c, x);
+                div(
+                    polynomial_to_expr(f.numerator(), theta, &coeff)?,
+                    polynomial_to_expr(f.denominator(), theta, &coeff)?,
+                )
+            }
+        }
+    }
+}
+
+/// Integrates `u` with the Risch algorithm if it lies in a tower of `Exp` and `Log` extensions
+/// of `Q(x)`. Returns `Ok(None)` if it does not or the algorithm cannot decide it, and
+/// [`IntegrationError::NonElementary`] if the integral is not elementary.
+pub(super) fn integrate_transcendental(
+    u: &SimpleExpr,
+    x: &str,
+) -> Result<Option<SimpleExpr>, IntegrationError> {
+    let Some(extensions) = Extensions::new(u, x) else {
+        return Ok(None);
+    };
+    if extensions.tower.height() == 0 {
+        return Ok(None);
+    }
+
+    let Some(f) = extensions.to_element(u, x) else {
+        return Ok(None);
+    };
+
+    let integral = match risch_integrate(&f, &extensions.tower) {
+        Ok(integral) => integral,
+        Err(RischError::NonElementary) => return Err(IntegrationError::NonElementary),
+        Err(RischError::Unsupported) => return Ok(None),
+    };
+
+    let var = SimpleExpr::new_symbol(x.into());
+    let coeff = |c: &Element| extensions.to_expr(c, &var);
+    let mut result = SimpleExpr::from(0);
+    for rational in &integral.rational {
+        result = add(result, extensions.to_expr(rational, &var)?)?;
+    }
+    for (level, log) in integral.logs {
+        let theta = &extensions.thetas[level - 1];
+        result = add(result, log_term(log, theta, x, &coeff)?)?;
+    }
+    result = add(result, rational_integral_to_expr(integral.base, x)?)?;
+
+    Ok(Some(result))
+}
@@ -11,11 +11,16 @@ mod cmp;
 pub mod constant;
 pub mod diff;
 mod helpers;
+pub mod integrate;
+pub mod limit;
 pub mod parse;
 pub mod polynomials;
 pub mod print;
 mod rational_expressions;
+pub mod series;
 pub mod simplify;
+pub mod solve;
+pub mod visit;
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
 pub enum Constants {
@@ -0,0 +1,558 @@
+//! Limits of expressions.
+//!
+//! [`limit`] first tries to read `u` as a rational function of `x` and compares the leading
+//! terms of its numerator and denominator, and then looks at the leading term of the series of
+//! `u` at the point. Otherwise the limit is rewritten as a right-sided
+//! limit `t → 0+` and computed recursively: limits of functions are evaluated at the limits of
+//! their arguments where they are continuous, and the indeterminate forms `0 · ∞` (which
+//! includes `0 / 0` and `∞ / ∞`) and `∞ - ∞` are resolved with L'Hôpital's rule. Powers with an
+//! exponent that depends on `x` are written as `Exp[b Log[a]]`, which reduces `1^∞`, `0^0` and
+//! `∞^0` to `0 · ∞`.
+
+use std::cmp::Ordering;
+
+use mathstuff_types::Polynomial;
+use num::{BigRational, Integer, One, Signed, ToPrimitive, Zero};
+
+use crate::diff::Differentiator;
+use crate::integrate::rational::to_fraction;
+use crate::polynomials::{free_of, substitute};
+use crate::series::series;
+use crate::simplify::ops::{Operation, Product, Sum};
+use crate::simplify::{simplify_power, SimpleExpr};
+use crate::{BasicAlgebraicExpr, ComputeResult, Constant};
+
+/// The point that `x` approaches.
+#[derive(Debug, Clone)]
+pub enum Point {
+    Finite(SimpleExpr),
+    Infinity,
+    NegInfinity,
+}
+
+/// The side from which `x` approaches a finite point. Ignored for the infinite points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    /// Both one-sided limits must exist and agree.
+    Both,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Limit {
+    Finite(SimpleExpr),
+    Infinity,
+    NegInfinity,
+}
+
+#[derive(Debug, Clone)]
+pub enum LimitError {
+    /// The limit was shown not to exist, e.g. because the one-sided limits differ.
+    DoesNotExist,
+    /// None of the methods could decide the limit.
+    NotFound,
+    Undefined,
+}
+
+impl From<crate::Undefined> for LimitError {
+    fn from(_: crate::Undefined) -> Self {
+        LimitError::Undefined
+    }
+}
+
+/// How many times L'Hôpital's rule and the other rewrites may be applied before giving up.
+const MAX_DEPTH: usize = 8;
+
+/// The symbol standing for `π` in limits, like `Pi / 2` for `ArcTan[x]` at infinity.
+const PI: &str = "Pi";
+
+/// Computes the limit of `u` as `x` approaches `point` from `direction`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::limit::{limit, Direction, Limit, Point};
+/// # use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("Sin[x] / x")?.simplify().unwrap();
+/// let l = limit(u, "x", Point::Finite(0.into()), Direction::Both).unwrap();
+/// assert_eq!(l, Limit::Finite(1.into()));
+/// # Ok(())
+/// # }
+/// ```
+pub fn limit(
+    u: SimpleExpr,
+    x: &str,
+    point: Point,
+    direction: Direction,
+) -> Result<Limit, LimitError> {
+    match (&point, direction) {
+        (Point::Finite(_), Direction::Both) => {
+            let left = one_sided_limit(&u, x, &point, false)?;
+            let right = one_sided_limit(&u, x, &point, true)?;
+            if left == right {
+                Ok(right)
+            } else {
+                Err(LimitError::DoesNotExist)
+            }
+        }
+        _ => one_sided_limit(&u, x, &point, direction != Direction::Left),
+    }
+}
+
+fn one_sided_limit(
+    u: &SimpleExpr,
+    x: &str,
+    point: &Point,
+    from_right: bool,
+) -> Result<Limit, LimitError> {
+    if let Some(l) = rational_limit(u, x, point, from_right) {
+        return l;
+    }
+
+    if let Some(l) = series_limit(u, x, point, from_right) {
+        return l;
+    }
+
+    // a symbol that does not occur in `u`
+    let mut t = String::from("t");
+    while !free_of(u, BasicAlgebraicExpr::Symbol(t.clone())) {
+        t.push('\'');
+    }
+    let t_expr = SimpleExpr::new_symbol(t.clone());
+
+    let replacement = match point {
+        Point::Finite(p) if from_right => add(p.clone(), t_expr)?,
+        Point::Finite(p) => add(p.clone(), neg(t_expr)?)?,
+        Point::Infinity => simplify_power(t_expr, (-1).into())?,
+        Point::NegInfinity => neg(simplify_power(t_expr, (-1).into())?)?,
+    };
+    let v = substitute(u, &BasicAlgebraicExpr::Symbol(x.into()), &replacement).simplify()?;
+
+    let limiter = Limiter {
+        differentiator: Differentiator::new(),
+        t,
+    };
+    match limiter.limit(v.clone(), 0) {
+        // products of sums like `(a / t + 1) t` are often easier after expanding
+        Err(LimitError::NotFound) => limiter.limit(v.expand()?, 0),
+        l => l,
+    }
+}
+
+/// The sign of a rational number as `-1`, `0` or `1`.
+fn signum(c: &BigRational) -> i8 {
+    match c.cmp(&BigRational::zero()) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// `∞` with the given sign.
+fn infinity(sign: i8) -> Limit {
+    if sign < 0 {
+        Limit::NegInfinity
+    } else {
+        Limit::Infinity
+    }
+}
+
+/// The limit of a rational function of `x` with rational coefficients at a rational point or at
+/// infinity, from the leading terms of its numerator and denominator. Returns `None` if `u` is
+/// not such a function or the point is not rational.
+fn rational_limit(
+    u: &SimpleExpr,
+    x: &str,
+    point: &Point,
+    from_right: bool,
+) -> Option<Result<Limit, LimitError>> {
+    let (n, d) = to_fraction(u, x)?.into_parts();
+    if n.is_zero() {
+        return Some(Ok(Limit::Finite(0.into())));
+    }
+
+    let (k, c, side) = match point {
+        Point::Finite(p) => {
+            let BasicAlgebraicExpr::Numeric(p) = p.as_inner() else {
+                return None;
+            };
+            // write n / d = (x - p)^k c(x) with c(p) != 0
+            let p = p.clone().into_inner();
+            let (mn, n) = divide_out_root(n, &p);
+            let (md, d) = divide_out_root(d, &p);
+            let c = n.eval(&p) / d.eval(&p);
+            (mn as i64 - md as i64, c, if from_right { 1 } else { -1 })
+        }
+        Point::Infinity | Point::NegInfinity => {
+            // n / d behaves like c (1 / x)^k with k the difference of the degrees
+            let k = d.degree()? as i64 - n.degree()? as i64;
+            let c = n.leading_coefficient_cloned() / d.leading_coefficient_cloned();
+            let side = if matches!(point, Point::Infinity) {
+                1
+            } else {
+                -1
+            };
+            (k, c, side)
+        }
+    };
+
+    // `side` is the sign of `x - p`, or of `1 / x` at infinity, and n / d behaves like
+    // c side^k |x - p|^k
+    Some(Ok(match k.cmp(&0) {
+        Ordering::Greater => Limit::Finite(0.into()),
+        Ordering::Equal => Limit::Finite(SimpleExpr::new_constant(c.into())),
+        Ordering::Less => infinity(signum(&c) * if k % 2 == 0 { 1 } else { side }),
+    }))
+}
+
+/// The limit at a rational point from the leading term of the series of `u`. Returns `None` if
+/// `u` has no series with rational coefficients there.
+fn series_limit(
+    u: &SimpleExpr,
+    x: &str,
+    point: &Point,
+    from_right: bool,
+) -> Option<Result<Limit, LimitError>> {
+    let Point::Finite(p) = point else {
+        return None;
+    };
+    let BasicAlgebraicExpr::Numeric(p) = p.as_inner() else {
+        return None;
+    };
+    let s = series(u, x, p.clone(), 1).ok()?;
+
+    let Some(c) = s.leading_coefficient() else {
+        // u = O(x - p)
+        return s
+            .precision()
+            .is_positive()
+            .then(|| Ok(Limit::Finite(0.into())));
+    };
+    let v = s.valuation();
+    Some(Ok(match v.cmp(&BigRational::zero()) {
+        Ordering::Greater => Limit::Finite(0.into()),
+        Ordering::Equal => Limit::Finite(c.clone().into()),
+        // (x - p)^v for x < p is only real if the denominator of v is odd
+        _ if !from_right && v.denom().is_even() => return None,
+        _ if !from_right && v.numer().is_odd() => infinity(-signum(c)),
+        _ => infinity(signum(c)),
+    }))
+}
+
+/// Divides `p` by `x - a` as often as possible, returning the multiplicity of `a` and the
+/// quotient.
+fn divide_out_root(
+    mut p: Polynomial<BigRational>,
+    a: &BigRational,
+) -> (usize, Polynomial<BigRational>) {
+    let linear = Polynomial::new(vec![-a.clone(), BigRational::one()]);
+    let mut m = 0;
+    while !p.is_zero() {
+        let (q, r) = p.clone().div_rem(linear.clone());
+        if !r.is_zero() {
+            break;
+        }
+        p = q;
+        m += 1;
+    }
+    (m, p)
+}
+
+fn add(a: SimpleExpr, b: SimpleExpr) -> ComputeResult {
+    Sum.simplify(vec![a, b])
+}
+
+fn mul(a: SimpleExpr, b: SimpleExpr) -> ComputeResult {
+    Product.simplify(vec![a, b])
+}
+
+fn neg(a: SimpleExpr) -> ComputeResult {
+    mul((-1).into(), a)
+}
+
+fn recip(a: SimpleExpr) -> ComputeResult {
+    simplify_power(a, Constant::negative_one().into())
+}
+
+/// `π / 2`, the limit of `ArcTan` at infinity.
+fn half_pi() -> ComputeResult {
+    let pi = SimpleExpr::new_symbol(PI.into());
+    let half = SimpleExpr::new_constant(BigRational::new(1.into(), 2.into()).into());
+    mul(half, pi)
+}
+
+fn function(name: &str, arg: SimpleExpr) -> ComputeResult {
+    BasicAlgebraicExpr::Function(name.into(), vec![arg.into_inner()]).simplify()
+}
+
+/// A floating point approximation of a constant expression.
+fn approximate(u: &BasicAlgebraicExpr) -> Option<f64> {
+    Some(match u {
+        BasicAlgebraicExpr::Numeric(c) => c.clone().into_inner().to_f64()?,
+        BasicAlgebraicExpr::Symbol(s) if s == PI => std::f64::consts::PI,
+        BasicAlgebraicExpr::Sum(terms) => terms.iter().map(approximate).sum::<Option<f64>>()?,
+        BasicAlgebraicExpr::Product(factors) => {
+            factors.iter().map(approximate).product::<Option<f64>>()?
+        }
+        BasicAlgebraicExpr::Pow(p) => approximate(&p.0)?.powf(approximate(&p.1)?),
+        BasicAlgebraicExpr::Function(name, args) if let [arg] = &**args => {
+            let arg = approximate(arg)?;
+            match &**name {
+                "Exp" => arg.exp(),
+                "Log" => arg.ln(),
+                "Sin" => arg.sin(),
+                "Cos" => arg.cos(),
+                "Tan" => arg.tan(),
+                "ArcTan" => arg.atan(),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    })
+    .filter(|x| x.is_finite())
+}
+
+/// The sign of a nonzero constant expression.
+fn constant_sign(u: &SimpleExpr) -> Result<i8, LimitError> {
+    match u.as_inner() {
+        BasicAlgebraicExpr::Numeric(c) => Ok(signum(&c.clone().into_inner())),
+        u => match approximate(u) {
+            Some(x) if x > 0.0 => Ok(1),
+            Some(x) if x < 0.0 => Ok(-1),
+            _ => Err(LimitError::NotFound),
+        },
+    }
+}
+
+/// The sign of a limit, where `0` means that it is zero.
+fn limit_sign(l: &Limit) -> Result<i8, LimitError> {
+    match l {
+        Limit::Finite(c) if c.is_zero() => Ok(0),
+        Limit::Finite(c) => constant_sign(c),
+        Limit::Infinity => Ok(1),
+        Limit::NegInfinity => Ok(-1),
+    }
+}
+
+/// Computes limits as the symbol `t` approaches `0` from the right.
+struct Limiter {
+    differentiator: Differentiator,
+    t: String,
+}
+
+impl Limiter {
+    fn differentiate(&self, u: SimpleExpr) -> Result<SimpleExpr, LimitError> {
+        self.differentiator
+            .differentiate(u, &self.t)
+            .map_err(|_| LimitError::NotFound)
+    }
+
+    fn limit(&self, u: SimpleExpr, depth: usize) -> Result<Limit, LimitError> {
+        if depth > MAX_DEPTH {
+            return Err(LimitError::NotFound);
+        }
+
+        if free_of(&u, BasicAlgebraicExpr::Symbol(self.t.clone())) {
+            return Ok(Limit::Finite(u));
+        }
+
+        match u.into_inner() {
+            BasicAlgebraicExpr::Symbol(_) => Ok(Limit::Finite(0.into())),
+            BasicAlgebraicExpr::Sum(terms) => {
+                self.sum_limit(terms.into_iter().map(SimpleExpr::assert).collect(), depth)
+            }
+            BasicAlgebraicExpr::Product(factors) => {
+                self.product_limit(factors.into_iter().map(SimpleExpr::assert).collect(), depth)
+            }
+            BasicAlgebraicExpr::Pow(p) => {
+                let (base, exp) = *p;
+                self.power_limit(SimpleExpr::assert(base), SimpleExpr::assert(exp), depth)
+            }
+            BasicAlgebraicExpr::Function(name, args) if args.len() == 1 => {
+                let arg = args.into_iter().next().expect("len == 1");
+                self.function_limit(&name, SimpleExpr::assert(arg), depth)
+            }
+            BasicAlgebraicExpr::Function(..) | BasicAlgebraicExpr::Factorial(_) => {
+                Err(LimitError::NotFound)
+            }
+            BasicAlgebraicExpr::Numeric(_) => unreachable!("constants are free of t"),
+        }
+    }
+
+    fn sum_limit(&self, terms: Vec<SimpleExpr>, depth: usize) -> Result<Limit, LimitError> {
+        let mut finite = SimpleExpr::from(0);
+        let mut infinities = vec![];
+
+        for term in &terms {
+            match self.limit(term.clone(), depth)? {
+                Limit::Finite(c) => finite = add(finite, c)?,
+                l => infinities.push((term, l)),
+            }
+        }
+
+        match &*infinities {
+            [] => Ok(Limit::Finite(finite)),
+            [(_, first), rest @ ..] if rest.iter().all(|(_, l)| l == first) => Ok(first.clone()),
+            [(a, _), ..] => {
+                // ∞ - ∞: write the sum as a (1 + (u - a) / a)
+                let rest = terms
+                    .iter()
+                    .filter(|term| term != a)
+                    .try_fold(SimpleExpr::from(0), |acc, term| add(acc, term.clone()))?;
+                let ratio = add(1.into(), mul(rest, recip((*a).clone())?)?)?;
+                self.product_limit(vec![(*a).clone(), ratio], depth + 1)
+            }
+        }
+    }
+
+    fn product_limit(&self, factors: Vec<SimpleExpr>, depth: usize) -> Result<Limit, LimitError> {
+        let mut finite = SimpleExpr::from(1);
+        let mut zeros = vec![];
+        let mut infinities = vec![];
+        let mut sign = 1;
+
+        for factor in factors {
+            match self.limit(factor.clone(), depth)? {
+                Limit::Finite(c) if c.is_zero() => zeros.push(factor),
+                Limit::Finite(c) => finite = mul(finite, c)?,
+                l => {
+                    sign *= limit_sign(&l)?;
+                    infinities.push(factor);
+                }
+            }
+        }
+
+        if zeros.is_empty() && infinities.is_empty() {
+            return Ok(Limit::Finite(finite));
+        }
+        if infinities.is_empty() {
+            return Ok(Limit::Finite(0.into()));
+        }
+        if zeros.is_empty() {
+            return Ok(infinity(sign * constant_sign(&finite)?));
+        }
+
+        // 0 · ∞: apply L'Hôpital's rule to the quotient 0 / 0 and, if that does not settle it,
+        // to the quotient ∞ / ∞. Which one works depends on the factors, e.g. `t Log[t]` needs
+        // the second and `Sin[t] / t` the first.
+        let reciprocal = |factors: &[SimpleExpr]| {
+            factors.iter().try_fold(SimpleExpr::from(1), |acc, factor| {
+                mul(acc, recip(factor.clone())?)
+            })
+        };
+        let quotients = [
+            (Product.simplify(zeros.clone())?, reciprocal(&infinities)?),
+            (Product.simplify(infinities)?, reciprocal(&zeros)?),
+        ];
+
+        for (f, g) in quotients {
+            let l = self.differentiate(f).and_then(|df| {
+                let quotient = mul(df, recip(self.differentiate(g)?)?)?;
+                self.limit(quotient, depth + 1)
+            });
+            match l {
+                Ok(Limit::Finite(c)) => return Ok(Limit::Finite(mul(finite, c)?)),
+                Ok(l) => return Ok(infinity(limit_sign(&l)? * constant_sign(&finite)?)),
+                Err(LimitError::NotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Err(LimitError::NotFound)
+    }
+
+    fn power_limit(
+        &self,
+        base: SimpleExpr,
+        exp: SimpleExpr,
+        depth: usize,
+    ) -> Result<Limit, LimitError> {
+        let BasicAlgebraicExpr::Numeric(n) = exp.as_inner() else {
+            if free_of(&exp, BasicAlgebraicExpr::Symbol(self.t.clone())) {
+                return match self.limit(base, depth)? {
+                    Limit::Finite(c) if !c.is_zero() => Ok(Limit::Finite(simplify_power(c, exp)?)),
+                    _ => Err(LimitError::NotFound),
+                };
+            }
+            // a^b = Exp[b Log[a]]
+            let u = function("Exp", mul(exp, function("Log", base)?)?)?;
+            return self.limit(u, depth + 1);
+        };
+
+        let n = n.clone().into_inner();
+        // the sign of c^n for c of the given sign
+        let power_sign = |sign: i8| {
+            if !n.is_integer() && sign < 0 {
+                Err(LimitError::NotFound)
+            } else if n.is_integer() && n.to_integer().is_odd() {
+                Ok(sign)
+            } else {
+                Ok(1)
+            }
+        };
+
+        Ok(match self.limit(base.clone(), depth)? {
+            Limit::Finite(c) if !c.is_zero() => Limit::Finite(simplify_power(c, exp)?),
+            Limit::Finite(_) if n.is_positive() => Limit::Finite(0.into()),
+            Limit::Finite(_) => infinity(power_sign(self.sign_near_zero(base, depth)?)?),
+            _ if n.is_negative() => Limit::Finite(0.into()),
+            l => infinity(power_sign(limit_sign(&l)?)?),
+        })
+    }
+
+    fn function_limit(
+        &self,
+        name: &str,
+        arg: SimpleExpr,
+        depth: usize,
+    ) -> Result<Limit, LimitError> {
+        let l = self.limit(arg.clone(), depth)?;
+        Ok(match (name, l) {
+            ("Exp", Limit::Infinity) => Limit::Infinity,
+            ("Exp", Limit::NegInfinity) => Limit::Finite(0.into()),
+            ("Log", Limit::Infinity) => Limit::Infinity,
+            ("Log", Limit::Finite(c)) if c.is_zero() => {
+                if self.sign_near_zero(arg, depth)? > 0 {
+                    Limit::NegInfinity
+                } else {
+                    return Err(LimitError::Undefined);
+                }
+            }
+            ("Log", Limit::Finite(c)) => match constant_sign(&c)? {
+                1 => Limit::Finite(function(name, c)?),
+                _ => return Err(LimitError::Undefined),
+            },
+            ("Tan", Limit::Finite(c)) => {
+                // Tan has poles where Cos vanishes
+                let cos = function("Cos", c.clone())?;
+                match approximate(&cos) {
+                    Some(x) if !cos.is_zero() && x.abs() > 1e-9 => {
+                        Limit::Finite(function(name, c)?)
+                    }
+                    _ => return Err(LimitError::NotFound),
+                }
+            }
+            ("Exp" | "Sin" | "Cos" | "ArcTan", Limit::Finite(c)) => {
+                Limit::Finite(function(name, c)?)
+            }
+            ("ArcTan", Limit::Infinity) => Limit::Finite(half_pi()?),
+            ("ArcTan", Limit::NegInfinity) => Limit::Finite(neg(half_pi()?)?),
+            _ => return Err(LimitError::NotFound),
+        })
+    }
+
+    /// The sign of `u` for small positive `t`, where `u` approaches `0`. This is the sign of the
+    /// first derivative of `u` that does not approach `0`.
+    fn sign_near_zero(&self, mut u: SimpleExpr, depth: usize) -> Result<i8, LimitError> {
+        for depth in depth + 1..=MAX_DEPTH {
+            u = self.differentiate(u)?;
+            let l = self.limit(u.clone(), depth)?;
+            let sign = limit_sign(&l)?;
+            if sign != 0 {
+                return Ok(sign);
+            }
+        }
+        Err(LimitError::NotFound)
+    }
+}
@@ -1,8 +1,17 @@
+use std::ops::ControlFlow;
+
 use fxhash::FxHashSet;
-use num::One;
+use mathstuff_polynomials::factorization::SquareFreeFactorization;
+use mathstuff_polynomials::partial_fractions::{self, PartialFraction, PartialFractions};
+use mathstuff_types::groebner::GroebnerBasis;
+use mathstuff_types::{MonomialOrder, MultiPolynomial, Polynomial};
+use num::{BigInt, BigRational, Integer, One, ToPrimitive, Zero};
 
-use crate::simplify::SimpleExpr;
-use crate::{BasicAlgebraicExpr, Undefined};
+use crate::integrate::rational::to_fraction;
+use crate::simplify::ops::{Operation, Product, Sum};
+use crate::simplify::{simplify_power, SimpleExpr};
+use crate::visit::{Fold, Visitor, Walk};
+use crate::{BasicAlgebraicExpr, ComputeResult, Undefined};
 
 /// Given an expression, identify potential candidates for variables.
 ///
@@ -43,37 +52,49 @@ use crate::{BasicAlgebraicExpr, Undefined};
 /// # Ok(())
 /// # }
 /// ```
-// TODO it doesn't feel very efficient to create single value hashsets
 pub fn variables(x: BasicAlgebraicExpr) -> FxHashSet<BasicAlgebraicExpr> {
-    match x {
-        BasicAlgebraicExpr::Numeric(_) => FxHashSet::default(),
-        BasicAlgebraicExpr::Pow(x) => {
+    let mut variables = Variables::default();
+    let _ = x.visit(&mut variables);
+    variables.0
+}
+
+#[derive(Default)]
+struct Variables(FxHashSet<BasicAlgebraicExpr>);
+
+impl Visitor for Variables {
+    type Break = ();
+
+    fn enter(&mut self, x: &BasicAlgebraicExpr) -> ControlFlow<(), Walk> {
+        match x {
+            BasicAlgebraicExpr::Numeric(_) => {}
+            BasicAlgebraicExpr::Sum(_) => return ControlFlow::Continue(Walk::Children),
             // If the power has an exponent that is an integer greater than one,
             // we return the exponent base.
-            let var = if let BasicAlgebraicExpr::Numeric(c) = &x.1
-            && let Some(int) = c.as_integer()
-            && int > &One::one()
+            BasicAlgebraicExpr::Pow(x)
+                if let BasicAlgebraicExpr::Numeric(c) = &x.1
+                && let Some(int) = c.as_integer()
+                && int > &One::one() =>
             {
-                (*x).0
-            } else {
-                BasicAlgebraicExpr::Pow(x)
-            };
-            [var].into_iter().collect()
-        }
-        BasicAlgebraicExpr::Sum(values) => values.into_iter().flat_map(variables).collect(),
-        BasicAlgebraicExpr::Product(values) => values
-            .into_iter()
-            .flat_map(|x| {
-                if let BasicAlgebraicExpr::Sum(_) = x {
-                    [x].into_iter().collect()
-                } else {
-                    variables(x)
+                self.0.insert(x.0.clone());
+            }
+            // the sums in a product are variables themselves
+            BasicAlgebraicExpr::Product(values) => {
+                for x in values {
+                    if let BasicAlgebraicExpr::Sum(_) = x {
+                        self.0.insert(x.clone());
+                    } else {
+                        let _ = x.visit(self);
+                    }
                 }
-            })
-            .collect(),
-        BasicAlgebraicExpr::Factorial(_)
-        | BasicAlgebraicExpr::Symbol(_)
-        | BasicAlgebraicExpr::Function(..) => [x].into_iter().collect(),
+            }
+            BasicAlgebraicExpr::Pow(_)
+            | BasicAlgebraicExpr::Factorial(_)
+            | BasicAlgebraicExpr::Symbol(_)
+            | BasicAlgebraicExpr::Function(..) => {
+                self.0.insert(x.clone());
+            }
+        }
+        ControlFlow::Continue(Walk::Skip)
     }
 }
 
@@ -93,7 +114,7 @@ impl GeneralizedVars for BasicAlgebraicExpr {
     }
 }
 
-impl<T: GeneralizedVars> GeneralizedVars for &T {
+impl<T: GeneralizedVars + ?Sized> GeneralizedVars for &T {
     fn contains(&self, x: &BasicAlgebraicExpr) -> bool {
         (**self).contains(x)
     }
@@ -101,25 +122,53 @@ impl<T: GeneralizedVars> GeneralizedVars for &T {
 
 /// Returns whether `u` is free of `t` i.e. that `u` does not contain `t`.
 pub fn free_of(u: &BasicAlgebraicExpr, t: impl GeneralizedVars) -> bool {
-    if t.contains(u) {
-        return true;
-    }
+    !u.any(|x| t.contains(x))
+}
 
-    match u {
-        BasicAlgebraicExpr::Numeric(_) | BasicAlgebraicExpr::Symbol(_) => true,
-        BasicAlgebraicExpr::Pow(x) => {
-            let (base, exp) = &**x;
-            free_of(base, &t) && free_of(exp, &t)
+/// Replaces every occurrence of `t` in `u` with `r`. The result is not simplified.
+pub fn substitute(
+    u: &BasicAlgebraicExpr,
+    t: &BasicAlgebraicExpr,
+    r: &BasicAlgebraicExpr,
+) -> BasicAlgebraicExpr {
+    Substitute { t, r }.fold(u.clone())
+}
+
+struct Substitute<'a> {
+    t: &'a BasicAlgebraicExpr,
+    r: &'a BasicAlgebraicExpr,
+}
+
+impl Fold for Substitute<'_> {
+    fn fold(&mut self, x: BasicAlgebraicExpr) -> BasicAlgebraicExpr {
+        if x == *self.t {
+            return self.r.clone();
         }
-        BasicAlgebraicExpr::Product(args)
-        | BasicAlgebraicExpr::Sum(args)
-        | BasicAlgebraicExpr::Function(_, args) => args.iter().all(|x| free_of(x, &t)),
-        BasicAlgebraicExpr::Factorial(x) => free_of(x, t),
+        x.map_children(|x| self.fold(x))
     }
 }
 
 /// Let `s` be a set of generalized variables. Extract the coefficient and variable parts of `u`.
 /// Returns undefined is `u` is not a general monomial expression in `s`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::polynomials::coeff_var_monomial;
+/// use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("3 * a * x^2 * y")?.simplify().unwrap();
+/// let vars = [parse("x")?, parse("y")?];
+///
+/// let (coeff, var) = coeff_var_monomial(u, &vars[..]).unwrap();
+/// assert_eq!(coeff, parse("3 * a")?.simplify().unwrap().into_inner());
+/// assert_eq!(var, parse("x^2 * y")?.simplify().unwrap().into_inner());
+///
+/// let u = parse("x * Sin[x]")?.simplify().unwrap();
+/// assert!(coeff_var_monomial(u, &vars[..]).is_err());
+/// # Ok(())
+/// # }
+/// ```
 pub fn coeff_var_monomial(
     u: SimpleExpr,
     vars: impl GeneralizedVars,
@@ -137,15 +186,28 @@ pub fn coeff_var_monomial(
             panic!("should have been caught by `free_of` above");
         }
         BasicAlgebraicExpr::Pow(x) => {
-            if vars.contains(&x.0) {
+            if is_variable_power(&x, &vars) {
                 Ok((One::one(), BasicAlgebraicExpr::Pow(x)))
             } else {
                 Err(Undefined)
             }
         }
-        BasicAlgebraicExpr::Product(_values) => {
-            // let mut ret = Vec::new();
-            todo!()
+        BasicAlgebraicExpr::Product(values) => {
+            // the factors are sorted, so every subsequence of them is a simplified product
+            let mut coeff = Vec::new();
+            let mut var = Vec::new();
+            for value in values {
+                if free_of(&value, &vars) {
+                    coeff.push(value);
+                } else if vars.contains(&value)
+                    || matches!(&value, BasicAlgebraicExpr::Pow(x) if is_variable_power(x, &vars))
+                {
+                    var.push(value);
+                } else {
+                    return Err(Undefined);
+                }
+            }
+            Ok((product(coeff), product(var)))
         }
         BasicAlgebraicExpr::Sum(values) => {
             if let Ok([val]) = TryInto::<[_; 1]>::try_into(values) {
@@ -158,6 +220,40 @@ pub fn coeff_var_monomial(
     }
 }
 
+/// Whether `x` is `v^n` for a generalized variable `v` and an integer `n > 1`.
+fn is_variable_power(
+    x: &(BasicAlgebraicExpr, BasicAlgebraicExpr),
+    vars: &impl GeneralizedVars,
+) -> bool {
+    vars.contains(&x.0)
+        && matches!(&x.1, BasicAlgebraicExpr::Numeric(c) if c.as_integer().is_some_and(|n| n > &One::one()))
+}
+
+/// The product of simplified expressions that are already in order.
+fn product(mut factors: Vec<BasicAlgebraicExpr>) -> BasicAlgebraicExpr {
+    match factors.len() {
+        0 => One::one(),
+        1 => factors.pop().expect("len == 1"),
+        _ => BasicAlgebraicExpr::Product(factors),
+    }
+}
+
+/// Let `s` be a set of generalized variables. Collects the terms of the sum `u` that have the
+/// same variable part, so that `u` becomes a sum of monomials with distinct variable parts.
+/// Returns undefined if a term of `u` is not a general monomial expression in `s`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::polynomials::collect_terms;
+/// use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("a * x + b * x + c")?.simplify().unwrap();
+/// let collected = collect_terms(u, parse("x")?).unwrap();
+/// assert_eq!(collected, parse("c + (a + b) * x")?.simplify().unwrap().into_inner());
+/// # Ok(())
+/// # }
+/// ```
 pub fn collect_terms(
     simple: SimpleExpr,
     vars: impl GeneralizedVars,
@@ -168,11 +264,510 @@ pub fn collect_terms(
         return Ok(u);
     }
 
-    let BasicAlgebraicExpr::Sum(_terms) = u else {
+    let BasicAlgebraicExpr::Sum(terms) = u else {
         coeff_var_monomial(u.clone().assert_simple(), vars)?;
 
         return Ok(u);
     };
 
-    todo!()
+    let mut collected: Vec<(SimpleExpr, BasicAlgebraicExpr)> = Vec::new();
+    for term in terms {
+        let (coeff, var) = coeff_var_monomial(term.assert_simple(), &vars)?;
+        let coeff = coeff.assert_simple();
+        if let Some((c, _)) = collected.iter_mut().find(|(_, v)| *v == var) {
+            *c = Sum.simplify(vec![c.clone(), coeff])?;
+        } else {
+            collected.push((coeff, var));
+        }
+    }
+
+    let mut terms = collected
+        .into_iter()
+        .filter(|(c, _)| !c.is_zero())
+        .map(|(c, v)| Product.simplify(vec![c, v.assert_simple()]))
+        .collect::<Result<Vec<_>, _>>()?;
+    terms.sort_unstable();
+    Sum.simplify(terms).map(SimpleExpr::into_inner)
+}
+
+/// The degree of `u` as a general monomial expression in `vars`, or `None` if it is not one.
+fn degree_monomial_gpe(u: &BasicAlgebraicExpr, vars: &impl GeneralizedVars) -> Option<usize> {
+    if vars.contains(u) {
+        return Some(1);
+    }
+    if free_of(u, vars) {
+        return Some(0);
+    }
+
+    match u {
+        BasicAlgebraicExpr::Pow(x) if is_variable_power(x, vars) => {
+            let BasicAlgebraicExpr::Numeric(n) = &x.1 else {
+                unreachable!("variable powers have integer exponents")
+            };
+            n.as_integer()?.to_usize()
+        }
+        BasicAlgebraicExpr::Product(values) => values
+            .iter()
+            .map(|x| degree_monomial_gpe(x, vars))
+            .sum(),
+        _ => None,
+    }
+}
+
+/// Returns whether `u` is a general polynomial expression in `vars`, i.e. a sum of monomials
+/// whose coefficient parts are free of `vars`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::polynomials::is_polynomial_gpe;
+/// use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("a * Sin[x]^2 + b * Sin[x] + c")?.simplify().unwrap();
+/// assert!(is_polynomial_gpe(&u, parse("Sin[x]")?));
+/// assert!(!is_polynomial_gpe(&u, parse("x")?));
+/// # Ok(())
+/// # }
+/// ```
+pub fn is_polynomial_gpe(u: &BasicAlgebraicExpr, vars: impl GeneralizedVars) -> bool {
+    degree_gpe(u, vars).is_ok()
+}
+
+/// The total degree of `u` as a general polynomial expression in `vars`. The degree of `0` is
+/// taken to be `0`. Returns undefined if `u` is not a general polynomial expression in `vars`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::polynomials::degree_gpe;
+/// use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("3 * w * x^2 * y^3 * z^4")?.simplify().unwrap();
+/// let vars = [parse("x")?, parse("z")?];
+/// assert_eq!(degree_gpe(&u, &vars[..]).unwrap(), 6);
+/// # Ok(())
+/// # }
+/// ```
+pub fn degree_gpe(u: &BasicAlgebraicExpr, vars: impl GeneralizedVars) -> Result<usize, Undefined> {
+    match u {
+        BasicAlgebraicExpr::Sum(terms) if !vars.contains(u) => terms
+            .iter()
+            .map(|x| degree_monomial_gpe(x, &vars))
+            .try_fold(0, |acc, d| Some(acc.max(d?)))
+            .ok_or(Undefined),
+        u => degree_monomial_gpe(u, &vars).ok_or(Undefined),
+    }
+}
+
+/// Splits the monomial `u` into its coefficient and the degree of `x` in it.
+fn coefficient_monomial_gpe(
+    u: &BasicAlgebraicExpr,
+    x: &BasicAlgebraicExpr,
+) -> Result<(SimpleExpr, usize), Undefined> {
+    if u == x {
+        return Ok((SimpleExpr::from(1), 1));
+    }
+
+    match u {
+        BasicAlgebraicExpr::Pow(p) if is_variable_power(p, x) => {
+            let m = degree_monomial_gpe(u, &x).ok_or(Undefined)?;
+            Ok((SimpleExpr::from(1), m))
+        }
+        BasicAlgebraicExpr::Product(values) => {
+            // at most one factor contains x, and the others make up the coefficient
+            let mut m = 0;
+            let mut coeff = Vec::new();
+            for value in values {
+                match coefficient_monomial_gpe(value, x)? {
+                    (_, 0) => coeff.push(value.clone()),
+                    (_, d) => m = d,
+                }
+            }
+            Ok((SimpleExpr::assert(product(coeff)), m))
+        }
+        u if free_of(u, x) => Ok((SimpleExpr::assert(u.clone()), 0)),
+        _ => Err(Undefined),
+    }
+}
+
+/// The coefficient of `x^j` in `u`, which may be symbolic. Returns undefined if `u` is not a
+/// general polynomial expression in `x`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::polynomials::coefficient_gpe;
+/// use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("a * x^2 + b * x^2 + c * x + d")?.simplify().unwrap();
+/// let x = parse("x")?;
+/// assert_eq!(coefficient_gpe(&u, &x, 2).unwrap(), parse("a + b")?.simplify().unwrap());
+/// assert_eq!(coefficient_gpe(&u, &x, 0).unwrap(), parse("d")?.simplify().unwrap());
+/// assert_eq!(coefficient_gpe(&u, &x, 3).unwrap(), parse("0")?.simplify().unwrap());
+/// # Ok(())
+/// # }
+/// ```
+pub fn coefficient_gpe(
+    u: &BasicAlgebraicExpr,
+    x: &BasicAlgebraicExpr,
+    j: usize,
+) -> Result<SimpleExpr, Undefined> {
+    match u {
+        BasicAlgebraicExpr::Sum(terms) if u != x => {
+            let mut coeffs = Vec::new();
+            for term in terms {
+                let (c, m) = coefficient_monomial_gpe(term, x)?;
+                if m == j {
+                    coeffs.push(c);
+                }
+            }
+            coeffs.sort_unstable();
+            Sum.simplify(coeffs)
+        }
+        u => {
+            let (c, m) = coefficient_monomial_gpe(u, x)?;
+            Ok(if m == j { c } else { SimpleExpr::from(0) })
+        }
+    }
+}
+
+/// The coefficient of the highest power of `x` in `u`. Returns undefined if `u` is not a general
+/// polynomial expression in `x`.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::polynomials::leading_coefficient_gpe;
+/// use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("a * Sin[x]^2 + b * Sin[x]")?.simplify().unwrap();
+/// let lc = leading_coefficient_gpe(&u, &parse("Sin[x]")?).unwrap();
+/// assert_eq!(lc, parse("a")?.simplify().unwrap());
+/// # Ok(())
+/// # }
+/// ```
+pub fn leading_coefficient_gpe(
+    u: &BasicAlgebraicExpr,
+    x: &BasicAlgebraicExpr,
+) -> Result<SimpleExpr, Undefined> {
+    coefficient_gpe(u, x, degree_gpe(u, x)?)
+}
+
+/// The expression is not a polynomial in the variable with rational coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotPolynomial;
+
+/// The expression is not a rational function of the variable with rational coefficients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotRationalFunction;
+
+fn to_polynomial_rec(
+    u: &BasicAlgebraicExpr,
+    var: &str,
+) -> Result<Polynomial<BigRational>, NotPolynomial> {
+    Ok(match u {
+        BasicAlgebraicExpr::Numeric(c) => Polynomial::new_trim_zeroes(vec![c.clone().into_inner()]),
+        BasicAlgebraicExpr::Symbol(x) if x == var => {
+            Polynomial::from_elem_with_degree(BigRational::one(), 1)
+        }
+        BasicAlgebraicExpr::Sum(terms) => terms
+            .iter()
+            .map(|x| to_polynomial_rec(x, var))
+            .sum::<Result<_, _>>()?,
+        BasicAlgebraicExpr::Product(factors) => factors
+            .iter()
+            .map(|x| to_polynomial_rec(x, var))
+            .product::<Result<_, _>>()?,
+        BasicAlgebraicExpr::Pow(p) => {
+            let BasicAlgebraicExpr::Numeric(n) = &p.1 else {
+                return Err(NotPolynomial);
+            };
+            let n = n.as_integer().and_then(ToPrimitive::to_usize).ok_or(NotPolynomial)?;
+            let base = to_polynomial_rec(&p.0, var)?;
+            (0..n).fold(Polynomial::one(), |acc, _| acc * base.clone())
+        }
+        _ => return Err(NotPolynomial),
+    })
+}
+
+fn to_multi_polynomial_rec(
+    u: &BasicAlgebraicExpr,
+    vars: &[&str],
+    order: MonomialOrder,
+) -> Result<MultiPolynomial<BigRational>, NotPolynomial> {
+    let term = |exps: Vec<usize>, c: BigRational| MultiPolynomial::new(vars, order, vec![(exps, c)]);
+    Ok(match u {
+        BasicAlgebraicExpr::Numeric(c) => term(vec![0; vars.len()], c.clone().into_inner()),
+        BasicAlgebraicExpr::Symbol(x) => {
+            let i = vars.iter().position(|v| v == x).ok_or(NotPolynomial)?;
+            let mut exps = vec![0; vars.len()];
+            exps[i] = 1;
+            term(exps, BigRational::one())
+        }
+        BasicAlgebraicExpr::Sum(terms) => terms
+            .iter()
+            .map(|x| to_multi_polynomial_rec(x, vars, order))
+            .sum::<Result<_, _>>()?,
+        BasicAlgebraicExpr::Product(factors) => factors
+            .iter()
+            .map(|x| to_multi_polynomial_rec(x, vars, order))
+            .product::<Result<_, _>>()?,
+        BasicAlgebraicExpr::Pow(p) => {
+            let BasicAlgebraicExpr::Numeric(n) = &p.1 else {
+                return Err(NotPolynomial);
+            };
+            let n = n.as_integer().and_then(ToPrimitive::to_usize).ok_or(NotPolynomial)?;
+            to_multi_polynomial_rec(&p.0, vars, order)?.pow(n)
+        }
+        _ => return Err(NotPolynomial),
+    })
+}
+
+impl SimpleExpr {
+    /// Converts `self` to a polynomial in `var`, if it is built from `var` and rational numbers
+    /// with sums, products and nonnegative integer powers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff::parse;
+    /// use mathstuff_types::Polynomial;
+    /// use num::{BigRational, One};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let u = parse("(x + 1)^2 / 2")?.simplify().unwrap();
+    /// let p = u.to_polynomial("x").unwrap();
+    /// let half = BigRational::new(1.into(), 2.into());
+    /// assert_eq!(p, Polynomial::new(vec![half.clone(), BigRational::one(), half]));
+    ///
+    /// assert!(parse("x^2 + y")?.simplify().unwrap().to_polynomial("x").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_polynomial(&self, var: &str) -> Result<Polynomial<BigRational>, NotPolynomial> {
+        to_polynomial_rec(self, var)
+    }
+
+    /// Converts `self` to a polynomial in `vars` with rational coefficients, if it is built from
+    /// `vars` and rational numbers with sums, products and nonnegative integer powers.
+    pub fn to_multi_polynomial(
+        &self,
+        vars: &[&str],
+        order: MonomialOrder,
+    ) -> Result<MultiPolynomial<BigRational>, NotPolynomial> {
+        to_multi_polynomial_rec(self, vars, order)
+    }
+
+    /// Factors `self` as a polynomial in `var` into its square-free factorization. The factors
+    /// are primitive polynomials with integer coefficients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff::parse;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let u = parse("x^5 + 6 * x^4 + 10 * x^3 - 4 * x^2 - 24 * x - 16")?.simplify().unwrap();
+    /// let factored = parse("(x^2 - 2) * (x + 2)^3")?.simplify().unwrap();
+    /// assert_eq!(u.factor("x").unwrap(), factored);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn factor(&self, var: &str) -> Result<SimpleExpr, NotPolynomial> {
+        let SquareFreeFactorization {
+            mut leading_coeff,
+            factors,
+        } = SquareFreeFactorization::factor_polynomial(self.to_polynomial(var)?);
+
+        let mut result = Vec::new();
+        for (factor, multiplicity) in factors {
+            let k = integer_scale(&factor);
+            let factor = factor.scalar_mul(k.clone());
+            leading_coeff /= num::pow(k, multiplicity.get());
+
+            let factor = factor.to_expr(var).expect("polynomials are defined");
+            result.push(
+                simplify_power(factor, SimpleExpr::from(multiplicity.get() as i32))
+                    .expect("powers of nonzero polynomials are defined"),
+            );
+        }
+
+        let leading_coeff = SimpleExpr::new_constant(leading_coeff.into());
+        Ok(result.into_iter().fold(leading_coeff, |acc, factor| {
+            Product
+                .simplify(vec![acc, factor])
+                .expect("products of polynomials are defined")
+        }))
+    }
+
+    /// Writes `self`, a rational function of `var` with rational coefficients, as a sum of a
+    /// polynomial and partial fractions `p / f^k` with `deg p < deg f`. The denominators are
+    /// factored as in [`partial_fractions::apart`], and written with integer coefficients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff::parse;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let u = parse("(x^3 + 3) / (x^2 - 1)")?.simplify().unwrap();
+    /// let expected = parse("x - 1 / (x + 1) + 2 / (x - 1)")?.simplify().unwrap();
+    /// assert_eq!(u.apart("x").unwrap(), expected);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apart(&self, var: &str) -> Result<SimpleExpr, NotRationalFunction> {
+        let (numerator, denominator) = to_fraction(self, var)
+            .ok_or(NotRationalFunction)?
+            .into_parts();
+        let PartialFractions { polynomial, terms } =
+            partial_fractions::apart(numerator, denominator);
+
+        let mut result = polynomial.to_expr(var).expect("polynomials are defined");
+        for PartialFraction {
+            numerator,
+            factor,
+            power,
+        } in terms
+        {
+            let k = integer_scale(&factor);
+            let numerator = numerator.scalar_mul(num::pow(k.clone(), power.get()));
+            let factor = factor.scalar_mul(k);
+            let term = Product
+                .simplify(vec![
+                    numerator.to_expr(var).expect("polynomials are defined"),
+                    simplify_power(
+                        factor.to_expr(var).expect("polynomials are defined"),
+                        SimpleExpr::from(-(power.get() as i32)),
+                    )
+                    .expect("powers of nonzero polynomials are defined"),
+                ])
+                .expect("products of rational functions are defined");
+            result = Sum
+                .simplify(vec![result, term])
+                .expect("sums of rational functions are defined");
+        }
+        Ok(result)
+    }
+}
+
+/// The factor `k` that scales `p` to a primitive polynomial `k p` with integer coefficients.
+fn integer_scale(p: &Polynomial<BigRational>) -> BigRational {
+    let lcm = p
+        .clone()
+        .into_iter()
+        .fold(BigInt::one(), |acc, c| acc.lcm(c.denom()));
+    let content = p
+        .clone()
+        .into_iter()
+        .fold(BigInt::zero(), |acc, c| acc.gcd(&(c * &lcm).to_integer()));
+    BigRational::new(lcm, content)
+}
+
+/// Converts polynomials back to expressions.
+pub trait ToExpr {
+    /// The expression `Σ c_i var^i`.
+    fn to_expr(&self, var: &str) -> ComputeResult;
+}
+
+impl ToExpr for Polynomial<BigRational> {
+    fn to_expr(&self, var: &str) -> ComputeResult {
+        let x = SimpleExpr::new_symbol(var.into());
+        self.clone()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .try_fold(SimpleExpr::from(0), |acc, (i, c)| {
+                let term = Product.simplify(vec![
+                    SimpleExpr::new_constant(c.into()),
+                    simplify_power(x.clone(), SimpleExpr::from(i as i32))?,
+                ])?;
+                Sum.simplify(vec![acc, term])
+            })
+    }
+}
+
+/// The expression `Σ c_i x^e_i` for the terms `c_i x^e_i` of `p`.
+pub fn multi_polynomial_to_expr(p: &MultiPolynomial<BigRational>) -> ComputeResult {
+    let vars = p
+        .vars()
+        .iter()
+        .map(|x| SimpleExpr::new_symbol(x.clone()))
+        .collect::<Vec<_>>();
+    let mut terms = vec![];
+    for (exps, c) in p.terms() {
+        let mut factors = vec![SimpleExpr::new_constant(c.clone().into())];
+        for (x, &e) in vars.iter().zip(exps).filter(|(_, e)| **e > 0) {
+            factors.push(simplify_power(x.clone(), SimpleExpr::from(e as i32))?);
+        }
+        factors.sort_unstable();
+        terms.push(Product.simplify(factors)?);
+    }
+    terms.sort_unstable();
+    match terms.len() {
+        0 => Ok(SimpleExpr::from(0)),
+        1 => Ok(terms.pop().unwrap()),
+        _ => Sum.simplify(terms),
+    }
+}
+
+fn to_generators(
+    polys: &[SimpleExpr],
+    vars: &[&str],
+    order: MonomialOrder,
+) -> Result<GroebnerBasis<BigRational>, NotPolynomial> {
+    let polys = polys
+        .iter()
+        .map(|u| u.to_multi_polynomial(vars, order))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(GroebnerBasis::buchberger(&polys))
+}
+
+/// The reduced Gröbner basis of the ideal generated by `polys`, which must be polynomials in
+/// `vars` with rational coefficients.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::parse;
+/// use mathstuff::polynomials::groebner_basis;
+/// use mathstuff_types::MonomialOrder;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let polys = [parse("x^2 + y^2 - 1")?, parse("x - y")?].map(|u| u.simplify().unwrap());
+/// let basis = groebner_basis(&polys, &["x", "y"], MonomialOrder::Lex).unwrap();
+/// let expected = [parse("x - y")?, parse("y^2 - 1/2")?].map(|u| u.simplify().unwrap());
+/// assert_eq!(basis, expected);
+/// # Ok(())
+/// # }
+/// ```
+pub fn groebner_basis(
+    polys: &[SimpleExpr],
+    vars: &[&str],
+    order: MonomialOrder,
+) -> Result<Vec<SimpleExpr>, NotPolynomial> {
+    Ok(to_generators(polys, vars, order)?
+        .polynomials()
+        .iter()
+        .map(|p| multi_polynomial_to_expr(p).expect("polynomials are defined"))
+        .collect())
+}
+
+/// The normal form of `u` modulo the ideal generated by `polys`, see [`groebner_basis`].
+pub fn normal_form(
+    u: &SimpleExpr,
+    polys: &[SimpleExpr],
+    vars: &[&str],
+    order: MonomialOrder,
+) -> Result<SimpleExpr, NotPolynomial> {
+    let basis = to_generators(polys, vars, order)?;
+    let u = u.to_multi_polynomial(vars, order)?;
+    Ok(multi_polynomial_to_expr(&basis.normal_form(&u)).expect("polynomials are defined"))
+}
+
+/// Whether `u` lies in the ideal generated by `polys`, see [`groebner_basis`].
+pub fn ideal_contains(
+    u: &SimpleExpr,
+    polys: &[SimpleExpr],
+    vars: &[&str],
+    order: MonomialOrder,
+) -> Result<bool, NotPolynomial> {
+    let basis = to_generators(polys, vars, order)?;
+    Ok(basis.contains(&u.to_multi_polynomial(vars, order)?))
 }
@@ -1,8 +1,11 @@
 use std::fmt::{self, Write};
+use std::ops::ControlFlow;
 
 use num::{One, Signed};
 
 use crate::constant::Constant;
+use crate::series::Series;
+use crate::visit::{Visitor, Walk};
 use crate::{BasicAlgebraicExpr, PrecedenceContext};
 
 
@@ -11,8 +14,71 @@ pub fn print_expr_to_string(x: &BasicAlgebraicExpr) -> String {
     p.print(x).expect("String format does not have errors");
     p.into_inner()
 }
+
+pub fn print_series_to_string(s: &Series) -> String {
+    let mut p = Printer::new_string();
+    p.print_series(s).expect("String format does not have errors");
+    p.into_inner()
+}
+
 pub struct Printer<W: Write> {
     writer: W,
+    /// The expressions being printed, innermost last.
+    stack: Vec<Frame>,
+    /// Whether the next product is printed without its coefficient, after a `-`.
+    hide_coefficient: bool,
+}
+
+/// How the children of an expression being printed are separated.
+#[derive(Clone, Copy)]
+enum FrameKind {
+    /// Written back to back.
+    Plain,
+    /// Terms, with `+` or `-`.
+    Sum,
+    /// Factors after the first `skip`, which are not printed.
+    Product { skip: usize },
+    /// Bases of the factors of a denominator.
+    Denominator,
+    /// A base and an exponent.
+    Pow,
+    /// Arguments, with commas.
+    Function,
+}
+
+/// An expression being printed by the [`Visitor`] implementation of [`Printer`].
+struct Frame {
+    kind: FrameKind,
+    /// The precedence context of the children.
+    ctxt: PrecedenceContext,
+    /// The index of the next child.
+    index: usize,
+    /// Whether the previous factor can be juxtaposed with the next one.
+    prev_can_combine: bool,
+    /// What is written after the children.
+    close: &'static str,
+    /// The number of parens around the expression.
+    parens: usize,
+}
+
+impl Frame {
+    fn new(kind: FrameKind, ctxt: PrecedenceContext) -> Self {
+        Self {
+            kind,
+            ctxt,
+            index: 0,
+            prev_can_combine: false,
+            close: "",
+            parens: 0,
+        }
+    }
+}
+
+fn into_result(flow: ControlFlow<fmt::Error>) -> fmt::Result {
+    match flow {
+        ControlFlow::Continue(()) => Ok(()),
+        ControlFlow::Break(err) => Err(err),
+    }
 }
 
 fn is_denominator(x: &BasicAlgebraicExpr) -> bool {
@@ -92,74 +158,14 @@ impl<W: Write> Printer<W> {
     ) -> fmt::Result {
         self.maybe_enter_parens(
             |this| {
-                if exprs.iter().any(is_denominator) {
-                    write!(this.writer, "frac(")?;
-
-                    let mut denom = Printer::new_string();
-                    let mut num_empty = true;
-                    let mut num_prev_can_combine = false;
-                    let mut denom_prev_can_combine = false;
-                    for exp in exprs {
-                        if is_denominator(exp) {
-                            let BasicAlgebraicExpr::Pow(x) = exp else {
-                                panic!("expected power")
-                            };
-
-                            if !denom.writer.is_empty() {
-                                // TODO: if numeric power is not one, then we would print a power,
-                                // which means it would always be able to combine with previous.
-                                if denom_prev_can_combine && can_combine_with_prev(exp) {
-                                    denom.writer.push(' ');
-                                } else {
-                                    denom.writer.push_str(" dot.op ");
-                                }
-                            }
-
-                            denom_prev_can_combine = can_combine_with_next(&x.0);
-
-                            denom.print_with_precedence(&x.0, PrecedenceContext::Product)?;
-
-                            // TODO detect (x)^(-y)
-                            if let BasicAlgebraicExpr::Numeric(x) = &x.1 {
-                                let abs = x.abs();
-                                if !abs.is_one() {
-                                    denom.writer.push_str("^(");
-                                    denom.print_constant(&abs)?;
-                                    denom.writer.push(')');
-                                    denom_prev_can_combine = true;
-                                }
-                            }
-                        } else {
-                            if !num_empty {
-                                if num_prev_can_combine && can_combine_with_prev(exp) {
-                                    this.writer.write_char(' ')?;
-                                } else {
-                                    this.writer.write_str(" dot.op ")?;
-                                }
-
-                                num_empty = false;
-                            }
-                            num_prev_can_combine = can_combine_with_next(exp);
-                            this.print_with_precedence(exp, PrecedenceContext::Product)?;
-                        }
-                    }
-                    let denom = denom.into_inner();
-                    write!(this.writer, ",{denom})")?;
-                } else {
-                    let mut prev_can_combine = false;
-                    for (n, item) in exprs.iter().enumerate() {
-                        if n != 0 {
-                            if prev_can_combine && can_combine_with_prev(item) {
-                                this.writer.write_char(' ')?;
-                            } else {
-                                this.writer.write_str(" dot.op ")?;
-                            }
-                        }
-                        prev_can_combine = can_combine_with_next(item);
-                        this.print_with_precedence(item, PrecedenceContext::Product)?;
-                    }
-                }
-                Ok(())
+                this.stack.push(Frame::new(FrameKind::Plain, PrecedenceContext::Product));
+                let result = this.product(exprs, 0).and_then(|walk| match walk {
+                    Walk::Children => exprs.iter().try_for_each(|x| into_result(x.visit(this))),
+                    Walk::Skip => Ok(()),
+                });
+                let frame = this.stack.pop().expect("the product frame was pushed above");
+                result?;
+                this.close(&frame)
             },
             PrecedenceContext::Product < p,
         )
@@ -170,84 +176,215 @@ impl<W: Write> Printer<W> {
         x: &BasicAlgebraicExpr,
         p: PrecedenceContext,
     ) -> fmt::Result {
-        let new_ctxt = x.precedence_ctxt();
-        match x {
-            BasicAlgebraicExpr::Factorial(x) => {
-                self.print_with_precedence(x, new_ctxt)?;
-                write!(self.writer, "!")?;
-            }
-            BasicAlgebraicExpr::Numeric(x) => {
-                self.print_constant(x)?;
+        self.stack.push(Frame::new(FrameKind::Plain, p));
+        let result = into_result(x.visit(self));
+        self.stack.pop();
+        result
+    }
+
+    /// Writes what goes between the previous child of the innermost frame and `x`, and returns
+    /// the precedence context of `x` together with whether it is wrapped in parens, or `None` if
+    /// `x` is not printed at all.
+    fn separate(
+        &mut self,
+        x: &BasicAlgebraicExpr,
+    ) -> Result<Option<(PrecedenceContext, bool)>, fmt::Error> {
+        let frame = self.stack.last_mut().expect("expressions are printed inside a frame");
+        let (kind, index, ctxt, prev_can_combine) =
+            (frame.kind, frame.index, frame.ctxt, frame.prev_can_combine);
+        frame.index += 1;
+
+        match kind {
+            FrameKind::Plain => {}
+            FrameKind::Sum if index != 0 => {
+                if let BasicAlgebraicExpr::Product(x) = x
+                && let [BasicAlgebraicExpr::Numeric(n), ..] = &**x
+                && n.is_negative()
+                {
+                    self.writer.write_char('-')?;
+                    // a coefficient of -1 is left out
+                    self.hide_coefficient = n.abs().is_one();
+                } else {
+                    self.writer.write_char('+')?;
+                }
             }
-            BasicAlgebraicExpr::Symbol(x) => {
-                self.writer.write_str(x)?;
+            FrameKind::Sum => {}
+            FrameKind::Product { skip } if index < skip => return Ok(None),
+            FrameKind::Product { skip } => {
+                if index != skip {
+                    self.separate_factor(prev_can_combine && can_combine_with_prev(x))?;
+                }
+                self.stack.last_mut().unwrap().prev_can_combine = can_combine_with_next(x);
             }
-            BasicAlgebraicExpr::Sum(items) => {
-                self.maybe_enter_parens(
-                    |this| {
-                        for (n, item) in items.iter().enumerate() {
-                            if n != 0 {
-                                if let BasicAlgebraicExpr::Product(x) = item
-                                && let [BasicAlgebraicExpr::Numeric(n), rest @ ..] = &**x
-                                && n.is_negative()
-                            {
-                                write!(this.writer, "-")?;
-
-                                if n.abs().is_one() {
-                                    this.print_product(rest, new_ctxt)?;
-                                    continue;
-                                }
-                            } else {
-                                write!(this.writer, "+")?;
-                            }
-                            }
-                            this.print_with_precedence(item, new_ctxt)?;
-                        }
-                        Ok(())
-                    },
-                    new_ctxt < p,
-                )?;
+            FrameKind::Denominator => {
+                // `x` is the base of a power, which always combines with the previous factor
+                if index != 0 {
+                    self.separate_factor(prev_can_combine)?;
+                }
+                self.stack.last_mut().unwrap().prev_can_combine = can_combine_with_next(x);
             }
-            BasicAlgebraicExpr::Product(items) => {
-                self.print_product(items, p)?;
+            FrameKind::Pow if index == 0 => {
+                let base_ctxt = x.precedence_ctxt();
+                let parens = base_ctxt != PrecedenceContext::NoPrecedence
+                    && base_ctxt < PrecedenceContext::Pow;
+                return Ok(Some((ctxt, parens)));
             }
-            BasicAlgebraicExpr::Pow(x) => {
-                let (base, exp) = &**x;
-                let base_ctxt = base.precedence_ctxt();
-                self.maybe_enter_parens(
-                    |this| this.print_with_precedence(base, PrecedenceContext::NoPrecedence),
-                    base_ctxt != PrecedenceContext::NoPrecedence
-                        && base_ctxt < PrecedenceContext::Pow,
-                )?;
+            FrameKind::Pow => {
                 self.writer.write_char('^')?;
-                self.enter_parens(|this| {
-                    this.print_with_precedence(exp, PrecedenceContext::NoPrecedence)
-                })?;
+                return Ok(Some((ctxt, true)));
             }
-            BasicAlgebraicExpr::Function(name, params) => {
-                write!(self.writer, "\"{name}\"[")?;
-                for (n, param) in params.iter().enumerate() {
-                    if n != 0 {
-                        self.writer.write_char(',')?;
-                    }
+            FrameKind::Function if index != 0 => self.writer.write_char(',')?,
+            FrameKind::Function => {}
+        }
+        Ok(Some((ctxt, false)))
+    }
+
+    fn separate_factor(&mut self, can_combine: bool) -> fmt::Result {
+        if can_combine {
+            self.writer.write_char(' ')
+        } else {
+            self.writer.write_str(" dot.op ")
+        }
+    }
+
+    /// Prints the factors of a product after skipping `skip` of them, either as a fraction, with
+    /// the numerator factors written back to back, or with [`Walk::Children`].
+    fn product(&mut self, exprs: &[BasicAlgebraicExpr], skip: usize) -> Result<Walk, fmt::Error> {
+        let factors = &exprs[skip..];
+        if !factors.iter().any(is_denominator) {
+            let frame = self.stack.last_mut().unwrap();
+            frame.kind = FrameKind::Product { skip };
+            frame.ctxt = PrecedenceContext::Product;
+            return Ok(Walk::Children);
+        }
 
-                    self.print_with_precedence(param, PrecedenceContext::NoPrecedence)?;
+        write!(self.writer, "frac(")?;
+        self.stack.push(Frame::new(FrameKind::Plain, PrecedenceContext::Product));
+        for x in factors.iter().filter(|x| !is_denominator(x)) {
+            into_result(x.visit(self))?;
+        }
+        self.writer.write_char(',')?;
+        self.stack.pop();
+        self.stack.push(Frame::new(FrameKind::Denominator, PrecedenceContext::Product));
+        for x in factors.iter().filter(|x| is_denominator(x)) {
+            let BasicAlgebraicExpr::Pow(x) = x else {
+                unreachable!("denominators are powers")
+            };
+            into_result(x.0.visit(self))?;
+
+            // TODO detect (x)^(-y)
+            if let BasicAlgebraicExpr::Numeric(x) = &x.1 {
+                let abs = x.abs();
+                if !abs.is_one() {
+                    self.writer.write_str("^(")?;
+                    self.print_constant(&abs)?;
+                    self.writer.write_char(')')?;
+                    self.stack.last_mut().unwrap().prev_can_combine = true;
                 }
-                self.writer.write_char(']')?;
             }
         }
+        self.stack.pop();
+        self.stack.last_mut().unwrap().close = ")";
+        Ok(Walk::Skip)
+    }
+
+    fn open(&mut self, x: &BasicAlgebraicExpr) -> Result<Walk, fmt::Error> {
+        let Some((p, parens)) = self.separate(x)? else {
+            // an empty frame, so that leaving `x` writes nothing
+            self.stack.push(Frame::new(FrameKind::Plain, PrecedenceContext::NoPrecedence));
+            return Ok(Walk::Skip);
+        };
+        let hide_coefficient = std::mem::take(&mut self.hide_coefficient);
+        let new_ctxt = x.precedence_ctxt();
+        let own_parens = match x {
+            BasicAlgebraicExpr::Sum(_) | BasicAlgebraicExpr::Product(_) => new_ctxt < p,
+            _ => false,
+        };
+
+        let mut frame = Frame::new(FrameKind::Plain, new_ctxt);
+        frame.parens = usize::from(parens) + usize::from(own_parens);
+        for _ in 0..frame.parens {
+            self.writer.write_char('(')?;
+        }
+        match x {
+            BasicAlgebraicExpr::Numeric(x) => self.print_constant(x)?,
+            BasicAlgebraicExpr::Symbol(x) => self.writer.write_str(x)?,
+            BasicAlgebraicExpr::Sum(_) => frame.kind = FrameKind::Sum,
+            BasicAlgebraicExpr::Pow(_) => {
+                frame.kind = FrameKind::Pow;
+                frame.ctxt = PrecedenceContext::NoPrecedence;
+            }
+            BasicAlgebraicExpr::Factorial(_) => frame.close = "!",
+            BasicAlgebraicExpr::Function(name, _) => {
+                write!(self.writer, "\"{name}\"[")?;
+                frame.kind = FrameKind::Function;
+                frame.ctxt = PrecedenceContext::NoPrecedence;
+                frame.close = "]";
+            }
+            BasicAlgebraicExpr::Product(factors) => {
+                self.stack.push(frame);
+                return self.product(factors, usize::from(hide_coefficient));
+            }
+        }
+        self.stack.push(frame);
+        Ok(Walk::Children)
+    }
+
+    /// Writes the end of the expression of `frame`.
+    fn close(&mut self, frame: &Frame) -> fmt::Result {
+        self.writer.write_str(frame.close)?;
+        for _ in 0..frame.parens {
+            self.writer.write_char(')')?;
+        }
         Ok(())
     }
 
+    /// Prints the known terms of `s` followed by its remainder `O((x - a)^p)`.
+    pub fn print_series(&mut self, s: &Series) -> fmt::Result {
+        let terms = s.to_expr().map_err(|_| fmt::Error)?;
+        if !terms.is_zero() {
+            self.print(&terms)?;
+            self.writer.write_char('+')?;
+        }
+        let remainder = s.remainder_expr().map_err(|_| fmt::Error)?;
+        write!(self.writer, "O(")?;
+        self.print(&remainder)?;
+        self.writer.write_char(')')
+    }
+
     pub fn into_inner(self) -> W {
         self.writer
     }
 }
 
+/// Prints an expression as it is walked: [`Visitor::enter`] writes everything up to its first
+/// child and [`Visitor::leave`] everything after its last one, while the frame of the parent
+/// decides what goes between the children.
+impl<W: Write> Visitor for Printer<W> {
+    type Break = fmt::Error;
+
+    fn enter(&mut self, x: &BasicAlgebraicExpr) -> ControlFlow<fmt::Error, Walk> {
+        match self.open(x) {
+            Ok(walk) => ControlFlow::Continue(walk),
+            Err(err) => ControlFlow::Break(err),
+        }
+    }
+
+    fn leave(&mut self, _x: &BasicAlgebraicExpr) -> ControlFlow<fmt::Error> {
+        let frame = self.stack.pop().expect("every expression entered has a frame");
+        match self.close(&frame) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(err) => ControlFlow::Break(err),
+        }
+    }
+}
+
 impl Printer<String> {
     pub fn new_string() -> Self {
         Self {
             writer: String::new(),
+            stack: vec![],
+            hide_coefficient: false,
         }
     }
 }
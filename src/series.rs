@@ -0,0 +1,584 @@
+//! Truncated Laurent and Puiseux series.
+//!
+//! A [`Series`] is `Σ c_k (x - a)^(k / d) + O((x - a)^(p / d))` for rational coefficients `c_k`,
+//! a rational point `a` and a ramification index `d`. The arithmetic keeps track of the order of
+//! the remainder, so that every coefficient of a result is exact.
+
+use num::{BigInt, BigRational, Integer, Signed, ToPrimitive, Zero};
+
+use crate::polynomials::free_of;
+use crate::simplify::ops::{Operation, Product, Sum};
+use crate::simplify::{simplify_power, SimpleExpr};
+use crate::{BasicAlgebraicExpr, ComputeResult, Constant};
+
+#[derive(Debug, Clone)]
+pub enum SeriesError {
+    /// The series has a coefficient that is not rational, like the `Log[2]` in the series of
+    /// `Log[x]` at `2`, or terms that are not powers of `x - a`, like `Log[x]` at `0`.
+    NotRepresentable,
+    Undefined,
+}
+
+impl From<crate::Undefined> for SeriesError {
+    fn from(_: crate::Undefined) -> Self {
+        SeriesError::Undefined
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Series {
+    var: String,
+    point: Constant,
+    /// The exponents are multiples of `1 / ramification`.
+    ramification: i64,
+    /// `coeffs[i]` is the coefficient of `(x - a)^((valuation + i) / ramification)`. The first
+    /// coefficient is not zero, so the series is known up to `valuation + coeffs.len()`.
+    valuation: i64,
+    coeffs: Vec<Constant>,
+}
+
+impl Series {
+    fn from_parts(
+        var: String,
+        point: Constant,
+        ramification: i64,
+        valuation: i64,
+        coeffs: Vec<Constant>,
+    ) -> Self {
+        let mut s = Self {
+            var,
+            point,
+            ramification,
+            valuation,
+            coeffs,
+        };
+        s.normalize();
+        s
+    }
+
+    /// Strips the leading zeros and makes the ramification index as small as possible.
+    fn normalize(&mut self) {
+        let zeros = self.coeffs.iter().take_while(|c| c.is_zero()).count();
+        self.coeffs.drain(..zeros);
+        self.valuation += zeros as i64;
+
+        let precision = self.precision_index();
+        let g = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .fold(self.ramification.gcd(&precision), |g, (i, _)| {
+                g.gcd(&(self.valuation + i as i64))
+            });
+        if g > 1 {
+            self.ramification /= g;
+            self.valuation /= g;
+            self.coeffs = self.coeffs.iter().step_by(g as usize).cloned().collect();
+        }
+    }
+
+    /// `c + O((x - a)^precision)`.
+    pub fn constant(c: Constant, var: &str, point: Constant, precision: i64) -> Self {
+        Self::from_parts(var.into(), point, 1, 0, vec![]).constant_like(c, precision)
+    }
+
+    /// `c + O((x - a)^(precision / d))` where `d` is the ramification index of `self`.
+    fn constant_like(&self, c: Constant, precision: i64) -> Self {
+        let coeffs = (0..precision)
+            .map(|i| if i == 0 { c.clone() } else { 0.into() })
+            .collect();
+        Self::from_parts(
+            self.var.clone(),
+            self.point.clone(),
+            self.ramification,
+            precision.min(0),
+            coeffs,
+        )
+    }
+
+    /// `x + O((x - a)^precision)`, which is written as `a + (x - a)`.
+    pub fn variable(var: &str, point: Constant, precision: i64) -> Self {
+        let coeffs = (0..precision)
+            .map(|i| match i {
+                0 => point.clone(),
+                1 => 1.into(),
+                _ => 0.into(),
+            })
+            .collect();
+        Self::from_parts(var.into(), point, 1, 0, coeffs)
+    }
+
+    /// `O((x - a)^(precision / ramification))` with the same variable and point as `self`.
+    fn big_o(&self, ramification: i64, precision: i64) -> Self {
+        Self {
+            var: self.var.clone(),
+            point: self.point.clone(),
+            ramification,
+            valuation: precision,
+            coeffs: vec![],
+        }
+    }
+
+    pub fn var(&self) -> &str {
+        &self.var
+    }
+
+    pub fn point(&self) -> &Constant {
+        &self.point
+    }
+
+    fn precision_index(&self) -> i64 {
+        self.valuation + self.coeffs.len() as i64
+    }
+
+    /// The exponent of the first nonzero term, or the precision if all known terms are zero.
+    pub fn valuation(&self) -> BigRational {
+        BigRational::new(self.valuation.into(), self.ramification.into())
+    }
+
+    /// The exponent `p` of the remainder `O((x - a)^p)`.
+    pub fn precision(&self) -> BigRational {
+        BigRational::new(self.precision_index().into(), self.ramification.into())
+    }
+
+    /// The coefficient of the first nonzero term, if any term is known to be nonzero.
+    pub fn leading_coefficient(&self) -> Option<&Constant> {
+        self.coeffs.first()
+    }
+
+    /// The coefficient of `(x - a)^exponent`. Panics if `exponent` is not below the precision.
+    pub fn coefficient(&self, exponent: &BigRational) -> Constant {
+        assert!(*exponent < self.precision(), "the coefficient is not known");
+        let i = exponent * BigInt::from(self.ramification);
+        if !i.is_integer() {
+            return 0.into();
+        }
+        let i = i.to_integer().to_i64().expect("exponent is small") - self.valuation;
+        usize::try_from(i).map_or_else(|_| 0.into(), |i| self.coeffs[i].clone())
+    }
+
+    /// Drops the terms of order `precision` and higher.
+    pub fn truncate(mut self, precision: &BigRational) -> Self {
+        if *precision < self.precision() {
+            let p = (precision * BigInt::from(self.ramification))
+                .ceil()
+                .to_integer();
+            let p = p.to_i64().expect("precision is small");
+            let len = (p - self.valuation).max(0) as usize;
+            self.coeffs.truncate(len);
+            self.valuation = self.valuation.min(p);
+            self.normalize();
+        }
+        self
+    }
+
+    /// The series with `ramification` a multiple of the current one.
+    fn ramified(&self, ramification: i64) -> Self {
+        let k = ramification / self.ramification;
+        let mut coeffs = vec![Constant::from(0); self.coeffs.len() * k as usize];
+        for (i, c) in self.coeffs.iter().enumerate() {
+            coeffs[i * k as usize] = c.clone();
+        }
+        Self {
+            var: self.var.clone(),
+            point: self.point.clone(),
+            ramification,
+            valuation: self.valuation * k,
+            coeffs,
+        }
+    }
+
+    fn aligned(&self, other: &Self) -> (Self, Self) {
+        assert!(
+            self.var == other.var && self.point == other.point,
+            "series in different variables or at different points"
+        );
+        let d = self.ramification.lcm(&other.ramification);
+        (self.ramified(d), other.ramified(d))
+    }
+
+    /// The coefficient at index `i`, which must be below the precision.
+    fn coeff(&self, i: i64) -> Constant {
+        usize::try_from(i - self.valuation).map_or_else(|_| 0.into(), |i| self.coeffs[i].clone())
+    }
+
+    /// The multiplicative inverse. Fails if no term is known to be nonzero.
+    pub fn checked_inv(&self) -> Result<Self, SeriesError> {
+        let Some(c) = self.coeffs.first() else {
+            return Err(SeriesError::Undefined);
+        };
+        // 1 / (c x^v (1 + r)), where b is the inverse of u = 1 + r
+        let u: Vec<_> = self.coeffs.iter().map(|x| x.clone() / c.clone()).collect();
+        let mut b: Vec<Constant> = vec![1.into()];
+        for n in 1..u.len() {
+            let bn = -(1..=n)
+                .map(|k| u[k].clone() * b[n - k].clone())
+                .sum::<Constant>();
+            b.push(bn);
+        }
+        let inv = Constant::from(1) / c.clone();
+        Ok(Self::from_parts(
+            self.var.clone(),
+            self.point.clone(),
+            self.ramification,
+            -self.valuation,
+            b.into_iter().map(|x| x * inv.clone()).collect(),
+        ))
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Result<Self, SeriesError> {
+        Ok(self.clone() * other.checked_inv()?)
+    }
+
+    /// `self^q` for a rational `q`. The leading coefficient must have a rational `q`-th power.
+    pub fn pow(&self, q: &BigRational) -> Result<Self, SeriesError> {
+        let (m, k) = (q.numer().clone(), q.denom().clone());
+        let k = k.to_i64().ok_or(SeriesError::NotRepresentable)?;
+        let m = m.to_i64().ok_or(SeriesError::NotRepresentable)?;
+
+        let Some(c) = self.coeffs.first() else {
+            // O(x^p)^q = O(x^(p q))
+            return if q.is_positive() {
+                Ok(self.big_o(self.ramification * k, self.valuation * m))
+            } else {
+                Err(SeriesError::Undefined)
+            };
+        };
+        let cq = rational_power(c, q).ok_or(SeriesError::NotRepresentable)?;
+
+        // (c x^v (1 + r))^q = c^q x^(v q) (1 + r)^q, where (1 + r)^q is computed with the
+        // recurrence b_n = 1 / n Σ ((q + 1) j - n) u_j b_(n-j)
+        let u: Vec<_> = self.coeffs.iter().map(|x| x.clone() / c.clone()).collect();
+        let q = Constant::from(q.clone());
+        let mut b: Vec<Constant> = vec![1.into()];
+        for n in 1..u.len() {
+            let sum = (1..=n)
+                .map(|j| {
+                    let factor = (q.clone() + 1.into()) * (j as i128).into() - (n as i128).into();
+                    factor * u[j].clone() * b[n - j].clone()
+                })
+                .sum::<Constant>();
+            b.push(sum / (n as i128).into());
+        }
+
+        let powered = Self {
+            var: self.var.clone(),
+            point: self.point.clone(),
+            ramification: self.ramification,
+            valuation: 0,
+            coeffs: b.into_iter().map(|x| x * cq.clone()).collect(),
+        }
+        .ramified(self.ramification * k);
+        Ok(Self::from_parts(
+            powered.var,
+            powered.point,
+            powered.ramification,
+            self.valuation * m,
+            powered.coeffs,
+        ))
+    }
+
+    /// `self(inner)`, where `self` is a series at the constant term of `inner`. Fails if the
+    /// constant term of `inner` is not the point of `self`.
+    pub fn compose(&self, inner: &Self) -> Result<Self, SeriesError> {
+        // self = Σ c_i y^(i / d) with y = inner - b
+        let (b, y) = inner.split_constant()?;
+        if b != self.point {
+            return Err(SeriesError::NotRepresentable);
+        }
+        let root = y.pow(&BigRational::new(1.into(), self.ramification.into()))?;
+
+        let remainder = root.pow(&BigRational::from_integer(self.precision_index().into()))?;
+        self.coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .try_fold(remainder.big_o_of(), |acc, (i, c)| {
+                let exp = BigRational::from_integer((self.valuation + i as i64).into());
+                Ok(acc + root.pow(&exp)?.scale(c))
+            })
+    }
+
+    /// `O` of the first term of `self`.
+    fn big_o_of(&self) -> Self {
+        self.big_o(self.ramification, self.valuation)
+    }
+
+    fn scale(self, c: &Constant) -> Self {
+        let coeffs = self.coeffs.iter().map(|x| x.clone() * c.clone()).collect();
+        Self::from_parts(
+            self.var,
+            self.point,
+            self.ramification,
+            self.valuation,
+            coeffs,
+        )
+    }
+
+    /// Splits `self` into its constant term and the part with positive exponents. Fails if there
+    /// are terms with negative exponents.
+    fn split_constant(&self) -> Result<(Constant, Self), SeriesError> {
+        if self.valuation < 0 {
+            return Err(SeriesError::NotRepresentable);
+        }
+        if self.precision_index() <= 0 {
+            return Err(SeriesError::Undefined);
+        }
+        let c = self.coeff(0);
+        let mut rest = self.clone();
+        if let Ok(i) = usize::try_from(-self.valuation) {
+            rest.coeffs[i] = 0.into();
+        }
+        rest.normalize();
+        Ok((c, rest))
+    }
+
+    /// `Σ a_k r^k` for a series `r` with positive valuation.
+    fn apply(r: &Self, a: impl Fn(usize) -> Constant) -> Self {
+        let terms = if r.coeffs.is_empty() {
+            1
+        } else {
+            // r^k = O(x^p) once k v >= p
+            Integer::div_ceil(&r.precision_index(), &r.valuation) as usize
+        };
+
+        let mut power = r.constant_like(1.into(), r.precision_index());
+        let mut result = power.clone().scale(&a(0));
+        for k in 1..=terms {
+            power = power * r.clone();
+            result = result + power.clone().scale(&a(k));
+        }
+        result
+    }
+
+    pub fn exp(&self) -> Result<Self, SeriesError> {
+        let (c, r) = self.split_constant()?;
+        if !c.is_zero() {
+            return Err(SeriesError::NotRepresentable);
+        }
+        Ok(Self::apply(&r, |k| Constant::from(1) / factorial(k)))
+    }
+
+    pub fn log(&self) -> Result<Self, SeriesError> {
+        let (c, r) = self.split_constant()?;
+        if c != Constant::from(1) {
+            return Err(SeriesError::NotRepresentable);
+        }
+        Ok(Self::apply(&r, |k| match k {
+            0 => 0.into(),
+            k if k % 2 == 0 => Constant::from(-1) / (k as i128).into(),
+            k => Constant::from(1) / (k as i128).into(),
+        }))
+    }
+
+    pub fn sin(&self) -> Result<Self, SeriesError> {
+        let (c, r) = self.split_constant()?;
+        if !c.is_zero() {
+            return Err(SeriesError::NotRepresentable);
+        }
+        Ok(Self::apply(&r, |k| match k % 4 {
+            1 => Constant::from(1) / factorial(k),
+            3 => Constant::from(-1) / factorial(k),
+            _ => 0.into(),
+        }))
+    }
+
+    pub fn cos(&self) -> Result<Self, SeriesError> {
+        let (c, r) = self.split_constant()?;
+        if !c.is_zero() {
+            return Err(SeriesError::NotRepresentable);
+        }
+        Ok(Self::apply(&r, |k| match k % 4 {
+            0 => Constant::from(1) / factorial(k),
+            2 => Constant::from(-1) / factorial(k),
+            _ => 0.into(),
+        }))
+    }
+
+    pub fn tan(&self) -> Result<Self, SeriesError> {
+        self.sin()?.checked_div(&self.cos()?)
+    }
+
+    pub fn arctan(&self) -> Result<Self, SeriesError> {
+        let (c, r) = self.split_constant()?;
+        if !c.is_zero() {
+            return Err(SeriesError::NotRepresentable);
+        }
+        Ok(Self::apply(&r, |k| match k % 4 {
+            1 => Constant::from(1) / (k as i128).into(),
+            3 => Constant::from(-1) / (k as i128).into(),
+            _ => 0.into(),
+        }))
+    }
+
+    /// The sum of the known terms, without the remainder.
+    pub fn to_expr(&self) -> ComputeResult {
+        let base = Sum.simplify(vec![
+            SimpleExpr::new_symbol(self.var.clone()),
+            SimpleExpr::new_constant(-self.point.clone()),
+        ])?;
+        self.coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .try_fold(SimpleExpr::from(0), |acc, (i, c)| {
+                let exp =
+                    BigRational::new((self.valuation + i as i64).into(), self.ramification.into());
+                let term = Product.simplify(vec![
+                    SimpleExpr::new_constant(c.clone()),
+                    simplify_power(base.clone(), SimpleExpr::new_constant(exp.into()))?,
+                ])?;
+                Sum.simplify(vec![acc, term])
+            })
+    }
+
+    /// The expression `(x - a)^p` in the remainder `O((x - a)^p)`.
+    pub fn remainder_expr(&self) -> ComputeResult {
+        let base = Sum.simplify(vec![
+            SimpleExpr::new_symbol(self.var.clone()),
+            SimpleExpr::new_constant(-self.point.clone()),
+        ])?;
+        simplify_power(base, SimpleExpr::new_constant(self.precision().into()))
+    }
+}
+
+impl std::ops::Add for Series {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let (a, b) = self.aligned(&other);
+        let valuation = a.valuation.min(b.valuation);
+        let precision = a.precision_index().min(b.precision_index());
+        let coeffs = (valuation..precision)
+            .map(|i| a.coeff(i) + b.coeff(i))
+            .collect();
+        Self::from_parts(a.var, a.point, a.ramification, valuation, coeffs)
+    }
+}
+
+impl std::ops::Neg for Series {
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        self.coeffs = self.coeffs.into_iter().map(|c| -c).collect();
+        self
+    }
+}
+
+impl std::ops::Sub for Series {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl std::ops::Mul for Series {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = self.aligned(&other);
+        let valuation = a.valuation + b.valuation;
+        let precision = (a.valuation + b.precision_index()).min(b.valuation + a.precision_index());
+        let len = (precision - valuation) as usize;
+
+        let mut coeffs = vec![Constant::from(0); len];
+        for (i, x) in a.coeffs.iter().enumerate().take(len) {
+            for (j, y) in b.coeffs.iter().enumerate().take(len - i) {
+                coeffs[i + j] = coeffs[i + j].clone() + x.clone() * y.clone();
+            }
+        }
+        Self::from_parts(a.var, a.point, a.ramification, valuation, coeffs)
+    }
+}
+
+fn factorial(k: usize) -> Constant {
+    (1..=k as i128).map(Constant::from).product()
+}
+
+/// `c^q` if it is rational.
+fn rational_power(c: &Constant, q: &BigRational) -> Option<Constant> {
+    let root = |n: &BigInt, k: u32| {
+        let r = n.nth_root(k);
+        (num::pow(r.clone(), k as usize) == *n).then_some(r)
+    };
+
+    let k = q.denom().to_u32()?;
+    if c.is_negative() && k % 2 == 0 {
+        return None;
+    }
+    let numer = root(&c.numer().abs(), k)? * c.numer().signum();
+    let base = Constant::from(BigRational::new(numer, root(c.denom(), k)?));
+    let m = q.numer();
+    Some(if m.is_negative() {
+        Constant::from(1) / num::traits::Pow::pow(base, &-m)
+    } else {
+        num::traits::Pow::pow(base, m)
+    })
+}
+
+/// Expands `u` as a series in `x` at `a` up to `O((x - a)^n)`, where `a` is rational.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::series::series;
+/// # use mathstuff::parse;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let u = parse("Sin[x] / x")?.simplify().unwrap();
+/// let s = series(&u, "x", 0.into(), 4).unwrap();
+/// assert_eq!(s.to_expr().unwrap(), parse("1 - x^2 / 6")?.simplify().unwrap());
+/// # Ok(())
+/// # }
+/// ```
+pub fn series(u: &SimpleExpr, x: &str, a: Constant, n: i64) -> Result<Series, SeriesError> {
+    let target = BigRational::from_integer(n.into());
+    let mut result = Err(SeriesError::Undefined);
+
+    // cancellations and negative powers lose precision, so retry with more terms
+    for extra in [0, 2, 4, 8, 16] {
+        result = to_series(u, x, &a, n + extra);
+        match &result {
+            Ok(s) if s.precision() >= target => return Ok(s.clone().truncate(&target)),
+            Err(SeriesError::NotRepresentable) => return result,
+            _ => {}
+        }
+    }
+    result
+}
+
+fn to_series(u: &BasicAlgebraicExpr, x: &str, a: &Constant, n: i64) -> Result<Series, SeriesError> {
+    Ok(match u {
+        BasicAlgebraicExpr::Numeric(c) => Series::constant(c.clone(), x, a.clone(), n),
+        BasicAlgebraicExpr::Symbol(s) if s == x => Series::variable(x, a.clone(), n),
+        BasicAlgebraicExpr::Sum(terms) => terms
+            .iter()
+            .try_fold(Series::constant(0.into(), x, a.clone(), n), |acc, term| {
+                Ok::<_, SeriesError>(acc + to_series(term, x, a, n)?)
+            })?,
+        BasicAlgebraicExpr::Product(factors) => factors.iter().try_fold(
+            Series::constant(1.into(), x, a.clone(), n),
+            |acc, factor| Ok::<_, SeriesError>(acc * to_series(factor, x, a, n)?),
+        )?,
+        BasicAlgebraicExpr::Pow(p) => match &p.1 {
+            BasicAlgebraicExpr::Numeric(q) => to_series(&p.0, x, a, n)?.pow(q)?,
+            exp if !free_of(exp, BasicAlgebraicExpr::Symbol(x.into())) => {
+                // a^b = Exp[b Log[a]]
+                let log = to_series(&p.0, x, a, n)?.log()?;
+                (to_series(exp, x, a, n)? * log).exp()?
+            }
+            _ => return Err(SeriesError::NotRepresentable),
+        },
+        BasicAlgebraicExpr::Function(name, args) if let [arg] = &**args => {
+            let arg = to_series(arg, x, a, n)?;
+            match &**name {
+                "Exp" => arg.exp()?,
+                "Log" => arg.log()?,
+                "Sin" => arg.sin()?,
+                "Cos" => arg.cos()?,
+                "Tan" => arg.tan()?,
+                "ArcTan" => arg.arctan()?,
+                _ => return Err(SeriesError::NotRepresentable),
+            }
+        }
+        _ => return Err(SeriesError::NotRepresentable),
+    })
+}
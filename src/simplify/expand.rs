@@ -0,0 +1,74 @@
+//! Algebraic expansion.
+
+use num::{BigInt, ToPrimitive, Zero};
+
+use super::ops::{Operation, Product, Sum};
+use super::{s, simplify_power, SimpleExpr};
+use crate::{BasicAlgebraicExpr, ComputeResult};
+
+fn binomial(n: usize, k: usize) -> BigInt {
+    (0..k).fold(BigInt::from(1), |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Distributes `r * t` over the operands of `r` and `t` when they are sums.
+fn expand_product(r: SimpleExpr, t: SimpleExpr) -> ComputeResult {
+    match r.inner {
+        BasicAlgebraicExpr::Sum(terms) => terms.into_iter().try_fold(0.into(), |acc, term| {
+            Sum.simplify(vec![acc, expand_product(s(term), t.clone())?])
+        }),
+        r if matches!(t.inner, BasicAlgebraicExpr::Sum(_)) => expand_product(t, s(r)),
+        r => Product.simplify(vec![s(r), t]),
+    }
+}
+
+/// Expands `u^n` with the binomial theorem if `u` is a sum.
+fn expand_power(u: SimpleExpr, n: usize) -> ComputeResult {
+    match u.inner {
+        BasicAlgebraicExpr::Sum(mut terms) => {
+            let f = s(terms.remove(0));
+            let rest = Sum.simplify(terms.into_iter().map(s).collect())?;
+
+            (0..=n).try_fold(0.into(), |acc, k| {
+                let c = SimpleExpr::new_constant(binomial(n, k).into());
+                let f = simplify_power(f.clone(), SimpleExpr::from((n - k) as i128))?;
+                let term = expand_product(
+                    Product.simplify(vec![c, f])?,
+                    expand_power(rest.clone(), k)?,
+                )?;
+                Sum.simplify(vec![acc, term])
+            })
+        }
+        u => simplify_power(s(u), SimpleExpr::from(n as i128)),
+    }
+}
+
+impl SimpleExpr {
+    /// Distributes products over sums and expands positive integer powers of sums.
+    ///
+    /// Negative powers of sums are kept as they are, so denominators stay factored.
+    pub fn expand(self) -> ComputeResult {
+        match self.inner {
+            BasicAlgebraicExpr::Sum(terms) => terms.into_iter().try_fold(0.into(), |acc, term| {
+                Sum.simplify(vec![acc, s(term).expand()?])
+            }),
+            BasicAlgebraicExpr::Product(factors) => {
+                factors.into_iter().try_fold(1.into(), |acc, factor| {
+                    expand_product(acc, s(factor).expand()?)
+                })
+            }
+            BasicAlgebraicExpr::Pow(x) => {
+                let (base, exp) = *x;
+                let base = s(base).expand()?;
+                if let BasicAlgebraicExpr::Numeric(c) = &exp
+                && let Some(n) = c.as_integer()
+                && n > &BigInt::zero()
+                && let Some(n) = n.to_usize() {
+                    expand_power(base, n)
+                } else {
+                    simplify_power(base, s(exp))
+                }
+            }
+            inner => Ok(s(inner)),
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::{BasicAlgebraicExpr, ComputeResult, Constant, Undefined};
 
 use self::ops::{Operation, Product};
 
+mod expand;
 pub(crate) mod ops;
 
 fn s(x: BasicAlgebraicExpr) -> SimpleExpr {
@@ -205,3 +206,29 @@ fn simplify_factorial(x: SimpleExpr) -> ComputeResult {
         _ => Ok(SimpleExpr::assert(BasicAlgebraicExpr::Factorial(Box::new(x.inner)))),
     }
 }
+
+/// Evaluates elementary functions at points where the result is rational, and
+/// cancels `Exp` and `Log` when they are applied to each other.
+fn simplify_function(name: String, args: Vec<SimpleExpr>) -> ComputeResult {
+    if let [arg] = &*args {
+        match &*name {
+            "Exp" | "Cos" if arg.is_zero() => return Ok(1.into()),
+            "Sin" | "Tan" | "ArcTan" if arg.is_zero() => return Ok(0.into()),
+            "Log" if arg.is_zero() => return Err(Undefined),
+            "Log" if *arg == 1 => return Ok(0.into()),
+            _ => {}
+        }
+
+        // Exp[Log[x]] = x, Log[Exp[x]] = x
+        if let BasicAlgebraicExpr::Function(inner, inner_args) = &arg.inner
+        && let [inner_arg] = &**inner_args
+        && matches!((&*name, &**inner), ("Exp", "Log") | ("Log", "Exp")) {
+            return Ok(s(inner_arg.clone()));
+        }
+    }
+
+    Ok(s(BasicAlgebraicExpr::Function(
+        name,
+        args.into_iter().map(SimpleExpr::into_inner).collect(),
+    )))
+}
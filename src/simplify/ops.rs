@@ -349,7 +349,12 @@ impl BasicAlgebraicExpr {
             Sum(x) => self::Sum.simplify_entry(x)?,
             Product(x) => self::Product.simplify_entry(x)?,
             Factorial(x) => super::simplify_factorial(x.simplify()?)?,
-            Function(..) => todo!(),
+            Function(name, args) => super::simplify_function(
+                name,
+                args.into_iter()
+                    .map(BasicAlgebraicExpr::simplify)
+                    .collect::<ComputeResult<_>>()?,
+            )?,
         })
     }
 }
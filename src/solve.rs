@@ -0,0 +1,398 @@
+//! Solving systems of polynomial equations, and eigenvalues of matrices.
+//!
+//! The solutions of a system with finitely many solutions are found from a lexicographic
+//! Gröbner basis in shape position: after making the ideal radical and adding a separating
+//! linear form `t = Σ c_i x_i` as the smallest variable, the basis is `x_i - p_i(t)` together
+//! with a square-free `g(t)`, so that every root of `g` gives exactly one solution.
+
+use mathstuff_polynomials::factorization::{rational_roots, Kronecker, SquareFreeFactorization};
+use mathstuff_polynomials::roots::complex_roots;
+use mathstuff_types::groebner::GroebnerBasis;
+use mathstuff_types::matrix::Matrix;
+use mathstuff_types::{MonomialOrder, MultiPolynomial, Polynomial};
+use num::complex::Complex64;
+use num::{BigRational, One, Signed, ToPrimitive, Zero};
+
+use crate::integrate::rational::square_root_parts;
+use crate::polynomials::{substitute, NotPolynomial, ToExpr};
+use crate::simplify::ops::{Operation, Product, Sum};
+use crate::simplify::{simplify_power, SimpleExpr};
+use crate::BasicAlgebraicExpr;
+
+type Poly = MultiPolynomial<BigRational>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// An equation is not a polynomial in the unknowns with rational coefficients.
+    NotPolynomial,
+    /// The system has infinitely many solutions.
+    InfinitelyMany,
+}
+
+impl From<NotPolynomial> for SolveError {
+    fn from(_: NotPolynomial) -> Self {
+        SolveError::NotPolynomial
+    }
+}
+
+/// The name of the separating variable, which cannot clash with a parsed symbol.
+const SEPARATOR: &str = "#t";
+
+/// The polynomial in the `i`-th variable equal to `p`, if `p` does not depend on the others.
+fn univariate(p: &Poly, i: usize) -> Option<Polynomial<BigRational>> {
+    let mut coeffs = vec![BigRational::zero(); p.degree_in(i).map_or(0, |d| d + 1)];
+    for (exps, c) in p.terms() {
+        if exps.iter().enumerate().any(|(j, e)| j != i && *e > 0) {
+            return None;
+        }
+        coeffs[exps[i]] = c.clone();
+    }
+    Some(Polynomial::new(coeffs))
+}
+
+/// `q` as a polynomial in the `i`-th of `vars`.
+fn from_univariate(q: &Polynomial<BigRational>, vars: &[&str], i: usize) -> Poly {
+    let terms = q
+        .clone()
+        .into_iter()
+        .enumerate()
+        .map(|(k, c)| {
+            let mut exps = vec![0; vars.len()];
+            exps[i] = k;
+            (exps, c)
+        })
+        .collect();
+    MultiPolynomial::new(vars, MonomialOrder::Lex, terms)
+}
+
+fn square_free_part(q: Polynomial<BigRational>) -> Polynomial<BigRational> {
+    SquareFreeFactorization::factor_polynomial(q)
+        .factors
+        .into_iter()
+        .map(|(f, _)| f)
+        .product()
+}
+
+/// The nonzero polynomial of least degree in the `i`-th variable in the ideal generated by
+/// `polys`, which must have finitely many common zeros.
+fn eliminant(polys: &[Poly], i: usize) -> Polynomial<BigRational> {
+    let vars = polys[0].vars();
+    let last = vars.len() - 1;
+    // move the variable to the end, which makes it the smallest in the lexicographic order
+    let mut order = vars.iter().map(String::as_str).collect::<Vec<_>>();
+    let var = order.remove(i);
+    order.push(var);
+    let polys = polys
+        .iter()
+        .map(|p| p.clone().with_vars(&order))
+        .collect::<Vec<_>>();
+    GroebnerBasis::buchberger(&polys)
+        .polynomials()
+        .iter()
+        .find_map(|p| univariate(p, last).filter(|q| !q.is_zero()))
+        .expect("zero-dimensional ideals contain univariate polynomials")
+}
+
+/// A triangular description of the solutions: they are `x_i = coords[i](t)` where `t` runs
+/// over the roots of the square-free polynomial `g`.
+struct Shape {
+    g: Polynomial<BigRational>,
+    coords: Vec<Polynomial<BigRational>>,
+}
+
+/// Reads `basis` as `x_i - p_i(t)` and `g(t)`, if it has this form.
+fn shape_position(basis: &GroebnerBasis<BigRational>, n: usize) -> Option<Shape> {
+    let polys = basis.polynomials();
+    if polys.len() != n + 1 {
+        return None;
+    }
+    let mut coords = vec![];
+    for (i, p) in polys[..n].iter().enumerate() {
+        let mut x = vec![0; n + 1];
+        x[i] = 1;
+        if p.leading_monomial() != Some(&x[..]) {
+            return None;
+        }
+        let rest = p.with_terms(
+            p.terms()
+                .skip(1)
+                .map(|(exps, c)| (exps.to_vec(), -c.clone()))
+                .collect(),
+        );
+        coords.push(univariate(&rest, n)?);
+    }
+    let g = univariate(&polys[n], n)?;
+    Some(Shape { g, coords })
+}
+
+fn shape(equations: &[SimpleExpr], vars: &[&str]) -> Result<Option<Shape>, SolveError> {
+    let n = vars.len();
+    let mut all_vars = vars.to_vec();
+    all_vars.push(SEPARATOR);
+    let mut polys = equations
+        .iter()
+        .map(|u| u.to_multi_polynomial(&all_vars, MonomialOrder::Lex))
+        .collect::<Result<Vec<_>, _>>()?;
+    polys.retain(|p| !p.is_zero());
+
+    let basis = GroebnerBasis::buchberger(&polys);
+    if basis.is_unit_ideal() {
+        return Ok(None);
+    }
+    // the system has finitely many solutions iff every unknown has a pure power as a leading
+    // monomial
+    for i in 0..n {
+        let is_pure_power =
+            |exps: &[usize]| exps.iter().enumerate().all(|(j, e)| (j == i) == (*e > 0));
+        if !basis
+            .polynomials()
+            .iter()
+            .any(|p| p.leading_monomial().is_some_and(is_pure_power))
+        {
+            return Err(SolveError::InfinitelyMany);
+        }
+    }
+
+    // an ideal with finitely many zeros that contains a square-free polynomial in each
+    // variable is radical (Seidenberg's lemma)
+    let mut radical = basis.into_polynomials();
+    for i in 0..n {
+        let g = square_free_part(eliminant(&radical, i));
+        radical.push(from_univariate(&g, &all_vars, i));
+    }
+
+    // t = x_n is tried first, then Σ k^(i + 1) x_i for k = 1, 2, ..., of which all but finitely
+    // many separate the solutions
+    for k in 0usize.. {
+        let mut separator = vec![];
+        for i in 0..n {
+            let c = match k {
+                0 if i + 1 == n => BigRational::one(),
+                0 => continue,
+                _ => BigRational::from_integer(k.pow(i as u32 + 1).into()),
+            };
+            let mut exps = vec![0; n + 1];
+            exps[i] = 1;
+            separator.push((exps, c));
+        }
+        let mut t = vec![0; n + 1];
+        t[n] = 1;
+        separator.push((t, -BigRational::one()));
+
+        let mut polys = radical.clone();
+        polys.push(MultiPolynomial::new(
+            &all_vars,
+            MonomialOrder::Lex,
+            separator,
+        ));
+        if let Some(shape) = shape_position(&GroebnerBasis::buchberger(&polys), n) {
+            return Ok(Some(shape));
+        }
+    }
+    unreachable!()
+}
+
+fn constant(c: BigRational) -> SimpleExpr {
+    SimpleExpr::new_constant(c.into())
+}
+
+/// The solutions for `t` running over the roots `u ± w √k` of the monic quadratic `q`.
+fn quadratic_solutions(
+    q: &Polynomial<BigRational>,
+    coords: &[Polynomial<BigRational>],
+) -> Vec<Vec<SimpleExpr>> {
+    let two = BigRational::from_integer(2.into());
+    let u = -q.coeff_at(1) / &two;
+    let r = &u * &u - q.coeff_at(0);
+    let (w, k) = square_root_parts(&r);
+    let k = if r.is_negative() { -k } else { k };
+    let half = BigRational::new(1.into(), 2.into());
+    let sqrt_k = simplify_power(constant(k.into()), constant(half))
+        .expect("square roots of nonzero numbers are defined");
+
+    [-w.clone(), w]
+        .into_iter()
+        .map(|w| {
+            coords
+                .iter()
+                .map(|p| {
+                    // p(t) = a + b t modulo q
+                    let p = p.clone().div_rem(q.clone()).1;
+                    let (a, b) = (p.coeff_at(0).clone(), p.coeff_at(1).clone());
+                    let irrational = Product
+                        .simplify(vec![constant(b.clone() * w.clone()), sqrt_k.clone()])
+                        .expect("products of numbers are defined");
+                    Sum.simplify(vec![constant(a + b * &u), irrational])
+                        .expect("sums of numbers are defined")
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The solutions for `t` running over the roots `RootOf[q(#t), j]` of `q`, numbered in the
+/// order of [`complex_roots`].
+fn root_of_solutions(
+    q: &Polynomial<BigRational>,
+    coords: &[Polynomial<BigRational>],
+) -> Vec<Vec<SimpleExpr>> {
+    let t = BasicAlgebraicExpr::Symbol(SEPARATOR.into());
+    let q_expr = q.to_expr(SEPARATOR).expect("polynomials are defined");
+    (1..=q.degree().unwrap_or(0))
+        .map(|j| {
+            let root = BasicAlgebraicExpr::Function(
+                "RootOf".into(),
+                vec![q_expr.clone().into_inner(), (j as i128).into()],
+            );
+            coords
+                .iter()
+                .map(|p| {
+                    let p = p.clone().div_rem(q.clone()).1;
+                    let p = p.to_expr(SEPARATOR).expect("polynomials are defined");
+                    substitute(&p, &t, &root)
+                        .simplify()
+                        .expect("polynomials in a root are defined")
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Solves the system of polynomial `equations` (each meaning `u = 0`) in the unknowns `vars`.
+/// Each solution gives the values of `vars` in order, with the rational solutions first in
+/// lexicographic order. The values are rational when possible, square roots when they come from
+/// an irreducible quadratic factor, and `RootOf[q(#t), j]` (the `j`-th root of the irreducible
+/// `q`, see [`complex_roots`]) otherwise. Complex solutions are included.
+///
+/// # Examples
+///
+/// ```
+/// # use mathstuff::parse;
+/// use mathstuff::simplify::SimpleExpr;
+/// use mathstuff::solve::solve_system;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let equations = [parse("x^2 + y^2 - 5")?, parse("x * y - 2")?].map(|u| u.simplify().unwrap());
+/// let solutions = solve_system(&equations, &["x", "y"]).unwrap();
+/// let expected = [[-2, -1], [-1, -2], [1, 2], [2, 1]].map(|s| s.map(SimpleExpr::from).to_vec());
+/// assert_eq!(solutions, expected);
+/// # Ok(())
+/// # }
+/// ```
+pub fn solve_system(
+    equations: &[SimpleExpr],
+    vars: &[&str],
+) -> Result<Vec<Vec<SimpleExpr>>, SolveError> {
+    let Some(Shape { g, coords }) = shape(equations, vars)? else {
+        return Ok(vec![]);
+    };
+
+    let roots = rational_roots(&g);
+    let mut rational = roots
+        .iter()
+        .map(|r| coords.iter().map(|p| p.eval(r)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    rational.sort();
+    let mut solutions = rational
+        .into_iter()
+        .map(|s| s.into_iter().map(constant).collect())
+        .collect::<Vec<_>>();
+
+    solutions.extend(irrational_solutions(g, &roots, &coords));
+    Ok(solutions)
+}
+
+/// The solutions for `t` running over the irrational roots of the square-free `g`, whose
+/// rational roots are `roots`, grouped by the irreducible factors of `g` in increasing order of
+/// degree.
+fn irrational_solutions(
+    g: Polynomial<BigRational>,
+    roots: &[BigRational],
+    coords: &[Polynomial<BigRational>],
+) -> Vec<Vec<SimpleExpr>> {
+    let rest = roots.iter().fold(g, |q, r| {
+        let linear = Polynomial::new(vec![-r.clone(), BigRational::one()]);
+        q.div_rem(linear).0
+    });
+    Kronecker::<BigRational>::new(rest)
+        .into_factors()
+        .into_iter()
+        .flat_map(|q| match q.degree() {
+            Some(0) | None => vec![],
+            Some(2) => quadratic_solutions(&q, coords),
+            Some(_) => root_of_solutions(&q, coords),
+        })
+        .collect()
+}
+
+/// Approximates the solutions of the system of polynomial `equations` in the unknowns `vars`,
+/// see [`solve_system`]. The solutions are sorted by the values of the separating linear form in
+/// the order of [`complex_roots`].
+pub fn solve_system_numeric(
+    equations: &[SimpleExpr],
+    vars: &[&str],
+) -> Result<Vec<Vec<Complex64>>, SolveError> {
+    let Some(Shape { g, coords }) = shape(equations, vars)? else {
+        return Ok(vec![]);
+    };
+    let coords = coords
+        .iter()
+        .map(|p| {
+            p.clone()
+                .into_iter()
+                .map(|c| c.to_f64().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    Ok(complex_roots(&g)
+        .into_iter()
+        .map(|z| {
+            coords
+                .iter()
+                .map(|p| p.iter().rev().fold(Complex64::zero(), |acc, c| acc * z + c))
+                .collect()
+        })
+        .collect())
+}
+
+/// The eigenvalues of the square matrix `m`, the roots of its
+/// [characteristic polynomial](Matrix::charpoly), with their algebraic multiplicities. They are
+/// given like the solutions of [`solve_system`], with the rational eigenvalues first in
+/// increasing order, and the others in the order of their multiplicities.
+///
+/// # Examples
+///
+/// ```
+/// use mathstuff::simplify::SimpleExpr;
+/// use mathstuff::solve::eigenvalues;
+/// use mathstuff_types::matrix::Matrix;
+/// use num::BigRational;
+/// let q = |n: i64| BigRational::from_integer(n.into());
+/// let m = Matrix::from_rows(vec![
+///     vec![q(2), q(1), q(0)],
+///     vec![q(0), q(2), q(0)],
+///     vec![q(0), q(0), q(-1)],
+/// ]);
+/// let expected = [(-1, 1), (2, 2)].map(|(x, k)| (SimpleExpr::from(x), k));
+/// assert_eq!(eigenvalues(&m), expected);
+/// ```
+///
+/// # Panics
+///
+/// If the matrix is not square.
+pub fn eigenvalues(m: &Matrix<BigRational>) -> Vec<(SimpleExpr, usize)> {
+    let x = Polynomial::new(vec![BigRational::zero(), BigRational::one()]);
+    let mut rational = vec![];
+    let mut irrational = vec![];
+    for (factor, k) in SquareFreeFactorization::factor_polynomial(m.charpoly()).factors {
+        let roots = rational_roots(&factor);
+        let values = irrational_solutions(factor, &roots, std::slice::from_ref(&x));
+        rational.extend(roots.into_iter().map(|r| (r, k.get())));
+        irrational.extend(values.into_iter().flatten().map(|v| (v, k.get())));
+    }
+    rational.sort();
+    rational
+        .into_iter()
+        .map(|(r, k)| (constant(r), k))
+        .chain(irrational)
+        .collect()
+}
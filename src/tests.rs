@@ -9,8 +9,15 @@ use tracing_subscriber::{EnvFilter, Registry};
 use crate::{BasicAlgebraicExpr, SimpleExpr};
 
 mod derivative;
+mod integrate;
+mod limit;
+mod matrix;
 mod parse;
+mod polynomials;
+mod series;
+mod solve;
 mod variables;
+mod visit;
 
 #[derive(Debug, Clone)]
 pub enum TestExpr {
@@ -0,0 +1,127 @@
+use crate::diff::Differentiator;
+use crate::integrate::rational::to_fraction;
+use crate::integrate::{integrate, IntegrationError};
+use crate::simplify::SimpleExpr;
+
+fn expr(s: &str) -> SimpleExpr {
+    crate::parse(s).unwrap().simplify().unwrap()
+}
+
+/// Integrates `u` and checks the result by differentiating it.
+fn assert_integrates(u: &str, expected: &str) {
+    let u = expr(u);
+    let f = integrate(u.clone(), "x").unwrap();
+    assert_eq!(f, expr(expected));
+
+    let df = Differentiator::new().differentiate(f, "x").unwrap();
+    assert_eq!(df.expand().unwrap(), u.expand().unwrap());
+}
+
+#[test]
+pub fn integral_table() {
+    assert_integrates("3", "3 * x");
+    assert_integrates("x^3", "x^4 / 4");
+    assert_integrates("1 / x", "Log[x]");
+    assert_integrates("Sin[x]", "-Cos[x]");
+    assert_integrates("Log[x]", "x * Log[x] - x");
+}
+
+#[test]
+pub fn linear_properties() {
+    assert_integrates("a * Exp[x]", "a * Exp[x]");
+    assert_integrates("x^2 + 2 * Cos[x]", "x^3 / 3 + 2 * Sin[x]");
+}
+
+#[test]
+pub fn substitution() {
+    assert_integrates("2 * x * Cos[x^2]", "Sin[x^2]");
+    assert_integrates("Sin[x] * Cos[x]", "-Cos[x]^2 / 2");
+    assert_integrates("x / (x^2 + 1)", "Log[x^2 + 1] / 2");
+    assert_integrates("(x + 1)^2", "(x + 1)^3 / 3");
+    assert_integrates("1 / (x * Log[x])", "Log[Log[x]]");
+}
+
+#[test]
+pub fn expansion() {
+    assert_integrates("x * (x + 1)", "x^3 / 3 + x^2 / 2");
+}
+
+#[test]
+pub fn not_found() {
+    assert!(matches!(
+        integrate(expr("Sin[x] / x"), "x"),
+        Err(IntegrationError::NotFound)
+    ));
+    // the Risch differential equation is only solved over Q(x)
+    assert!(matches!(
+        integrate(expr("Exp[Exp[x]]"), "x"),
+        Err(IntegrationError::NotFound)
+    ));
+}
+
+/// Integrates the rational function `u` and checks that the derivative of the result is `u`
+/// as a rational function.
+fn assert_integrates_rational(u: &str, expected: &str) {
+    let u = expr(u);
+    let f = integrate(u.clone(), "x").unwrap();
+    assert_eq!(f, expr(expected));
+
+    let df = Differentiator::new().differentiate(f, "x").unwrap();
+    assert_eq!(to_fraction(&df, "x"), to_fraction(&u, "x"));
+}
+
+#[test]
+pub fn rational_functions() {
+    assert_integrates_rational("1 / (x^2 + 1)", "ArcTan[x]");
+    assert_integrates_rational("1 / (x^2 - 1)", "Log[x - 1] / 2 - Log[x + 1] / 2");
+    assert_integrates_rational("1 / (x + 1)^2", "-1 / (x + 1)");
+    assert_integrates_rational("(x^3 + 1) / (x^2 - 1)", "x^2 / 2 + Log[x - 1]");
+    assert_integrates_rational(
+        "(x^4 - 3 * x^2 + 6) / (x^6 - 5 * x^4 + 5 * x^2 + 4)",
+        "ArcTan[x] + ArcTan[x^3] + ArcTan[x^5 / 2 - 3 * x^3 / 2 + x / 2]",
+    );
+}
+
+#[test]
+pub fn rational_functions_algebraic() {
+    assert_eq!(
+        integrate(expr("1 / (x^2 + 2)"), "x").unwrap(),
+        expr("2^(1/2) / 2 * ArcTan[2^(1/2) * x / 2]")
+    );
+    assert_eq!(
+        integrate(expr("1 / (x^2 - 2)"), "x").unwrap(),
+        expr("2^(1/2) / 4 * Log[1 - 2^(1/2) * x / 2] - 2^(1/2) / 4 * Log[1 + 2^(1/2) * x / 2]")
+    );
+    assert_eq!(
+        integrate(expr("1 / (x^4 + 1)"), "x").unwrap(),
+        expr("RootSum[t^4 + 1/256, t * Log[x + 4 * t]]")
+    );
+}
+
+#[test]
+pub fn transcendental() {
+    let cases = [
+        ("x * Exp[x]", "(x - 1) * Exp[x]"),
+        ("x^2 * Exp[3 * x]", "(x^2 / 3 - 2 * x / 9 + 2 / 27) * Exp[3 * x]"),
+        ("Exp[x / 2] + Exp[x]", "2 * Exp[x / 2] + Exp[x]"),
+        ("Log[x]^2", "x * Log[x]^2 - 2 * x * Log[x] + 2 * x"),
+        ("1 / (Exp[x] + 1)", "x - Log[Exp[x] + 1]"),
+        ("Exp[x] / (Exp[2 * x] + 1)", "ArcTan[Exp[x]]"),
+        ("Exp[x] / x + Exp[x] * Log[x]", "Exp[x] * Log[x]"),
+        ("Log[Log[x]] / (x * Log[x])", "Log[Log[x]]^2 / 2"),
+        ("Log[Log[x]] + 1 / Log[x]", "x * Log[Log[x]]"),
+    ];
+    for (u, expected) in cases {
+        assert_eq!(integrate(expr(u), "x").unwrap(), expr(expected), "{u}");
+    }
+}
+
+#[test]
+pub fn non_elementary() {
+    for u in ["Exp[x^2]", "Exp[x] / x", "1 / Log[x]", "Exp[x] * Log[x]", "Log[Log[x]]"] {
+        assert!(matches!(
+            integrate(expr(u), "x"),
+            Err(IntegrationError::NonElementary)
+        ));
+    }
+}
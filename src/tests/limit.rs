@@ -0,0 +1,111 @@
+use crate::limit::{limit, Direction, Limit, LimitError, Point};
+use crate::simplify::SimpleExpr;
+
+fn expr(s: &str) -> SimpleExpr {
+    crate::parse(s).unwrap().simplify().unwrap()
+}
+
+fn limit_at(u: &str, point: Point, direction: Direction) -> Result<Limit, LimitError> {
+    limit(expr(u), "x", point, direction)
+}
+
+fn assert_limit(u: &str, point: Point, expected: &str) {
+    assert_eq!(
+        limit_at(u, point, Direction::Both).unwrap(),
+        Limit::Finite(expr(expected)),
+        "{u}"
+    );
+}
+
+#[test]
+pub fn rational_functions() {
+    assert_limit("(x^2 - 1) / (x - 1)", Point::Finite(1.into()), "2");
+    assert_limit("(2 * x^2 + 1) / (3 * x^2 - x)", Point::Infinity, "2 / 3");
+    assert_limit("x / (x^2 + 1)", Point::NegInfinity, "0");
+    assert!(matches!(
+        limit_at("x^3 / (1 - x)", Point::NegInfinity, Direction::Both),
+        Ok(Limit::NegInfinity)
+    ));
+    assert!(matches!(
+        limit_at("1 / x^2", Point::Finite(0.into()), Direction::Both),
+        Ok(Limit::Infinity)
+    ));
+}
+
+#[test]
+pub fn one_sided() {
+    let zero = || Point::Finite(0.into());
+    assert!(matches!(
+        limit_at("1 / x", zero(), Direction::Left),
+        Ok(Limit::NegInfinity)
+    ));
+    assert!(matches!(
+        limit_at("1 / x", zero(), Direction::Right),
+        Ok(Limit::Infinity)
+    ));
+    assert!(matches!(
+        limit_at("1 / x", zero(), Direction::Both),
+        Err(LimitError::DoesNotExist)
+    ));
+    assert_eq!(
+        limit_at("Exp[1 / x]", zero(), Direction::Left).unwrap(),
+        Limit::Finite(0.into())
+    );
+    assert!(matches!(
+        limit_at("Log[x]", zero(), Direction::Right),
+        Ok(Limit::NegInfinity)
+    ));
+}
+
+#[test]
+pub fn functions() {
+    assert_limit("ArcTan[x]", Point::Infinity, "Pi / 2");
+    assert_eq!(
+        limit_at("ArcTan[x^3]", Point::NegInfinity, Direction::Both).unwrap(),
+        Limit::Finite(-expr("Pi / 2"))
+    );
+    assert_limit("ArcTan[x] - x / (x + 1)", Point::Infinity, "Pi / 2 - 1");
+    assert_limit("Log[x]", Point::Finite(2.into()), "Log[2]");
+    assert!(matches!(
+        limit_at("Log[x]", Point::Finite((-1).into()), Direction::Both),
+        Err(LimitError::Undefined)
+    ));
+    assert!(matches!(
+        limit_at("Log[x - 3]", Point::Infinity, Direction::Both),
+        Ok(Limit::Infinity)
+    ));
+    assert!(matches!(
+        limit_at("Log[-1 * x^2]", Point::Finite(1.into()), Direction::Both),
+        Err(LimitError::Undefined)
+    ));
+    assert!(matches!(
+        limit_at("Sin[x]", Point::Infinity, Direction::Both),
+        Err(LimitError::NotFound)
+    ));
+}
+
+#[test]
+pub fn indeterminate_forms() {
+    let zero = || Point::Finite(0.into());
+    assert_limit("Sin[x] / x", zero(), "1");
+    assert_limit("(1 - Cos[x]) / x^2", zero(), "1 / 2");
+    assert_limit("(Exp[x] - 1) / x", zero(), "1");
+    assert_limit("Sin[a * x] / x", zero(), "a");
+    assert_limit("Log[x] / x", Point::Infinity, "0");
+    assert_limit("x * Exp[-x]", Point::Infinity, "0");
+    assert_limit("(1 + 1 / x)^x", Point::Infinity, "Exp[1]");
+    assert_limit("(a * x + 1) / x", Point::Infinity, "a");
+    assert!(matches!(
+        limit_at("Exp[x] - x", Point::Infinity, Direction::Both),
+        Ok(Limit::Infinity)
+    ));
+
+    assert_eq!(
+        limit_at("x * Log[x]", zero(), Direction::Right).unwrap(),
+        Limit::Finite(0.into())
+    );
+    assert_eq!(
+        limit_at("x^x", zero(), Direction::Right).unwrap(),
+        Limit::Finite(1.into())
+    );
+}
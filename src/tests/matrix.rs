@@ -0,0 +1,63 @@
+use mathstuff_types::matrix::Matrix;
+use mathstuff_types::Polynomial;
+use num::{BigRational, One, Zero};
+
+use crate::{solve, SimpleExpr};
+
+fn expr(s: &str) -> SimpleExpr {
+    crate::parse(s).unwrap().simplify().unwrap()
+}
+
+#[test]
+pub fn symbolic_entries() {
+    let m = Matrix::from_rows(vec![vec![expr("a"), expr("b")], vec![expr("c"), expr("d")]]);
+    let adjugate = Matrix::from_rows(vec![
+        vec![expr("d"), expr("-1 * b")],
+        vec![expr("-1 * c"), expr("a")],
+    ]);
+    let det = expr("a * d - b * c");
+    assert_eq!(m.clone() * adjugate, Matrix::identity(2).scalar_mul(det));
+    assert_eq!(
+        m.transpose() - m.clone(),
+        Matrix::from_rows(vec![
+            vec![SimpleExpr::zero(), expr("c - b")],
+            vec![expr("b - c"), SimpleExpr::zero()],
+        ])
+    );
+    assert_eq!(
+        m.mul_vec(&[SimpleExpr::one(), expr("x")]),
+        [expr("a + b * x"), expr("c + d * x")]
+    );
+
+    // polynomials with symbolic coefficients
+    let p = Polynomial::new(vec![expr("a"), SimpleExpr::one()]);
+    assert_eq!(
+        p.clone() * p,
+        Polynomial::new(vec![expr("a^2"), expr("2 * a"), SimpleExpr::one()])
+    );
+}
+
+#[test]
+pub fn eigenvalues() {
+    let m = Matrix::from_rows(vec![vec![expr("a"), expr("b")], vec![expr("c"), expr("d")]]);
+    assert_eq!(
+        m.charpoly(),
+        Polynomial::new(vec![
+            expr("a * d - b * c"),
+            expr("-1 * a - d"),
+            SimpleExpr::one()
+        ])
+    );
+
+    let q = |n: i64| BigRational::from_integer(n.into());
+    let m = Matrix::from_rows(vec![
+        vec![q(0), q(2), q(0), q(0)],
+        vec![q(1), q(0), q(0), q(0)],
+        vec![q(0), q(0), q(3), q(1)],
+        vec![q(0), q(0), q(0), q(3)],
+    ]);
+    assert_eq!(
+        solve::eigenvalues(&m),
+        [("3", 2), ("-1 * 2^(1/2)", 1), ("2^(1/2)", 1)].map(|(x, k)| (expr(x), k))
+    );
+}
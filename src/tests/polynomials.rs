@@ -0,0 +1,167 @@
+use crate::polynomials::{
+    coeff_var_monomial, coefficient_gpe, collect_terms, degree_gpe, groebner_basis, ideal_contains,
+    is_polynomial_gpe, leading_coefficient_gpe, normal_form, ToExpr,
+};
+use crate::simplify::SimpleExpr;
+use crate::BasicAlgebraicExpr;
+use mathstuff_types::MonomialOrder;
+
+fn expr(s: &str) -> SimpleExpr {
+    crate::parse(s).unwrap().simplify().unwrap()
+}
+
+fn vars(names: &[&str]) -> Vec<BasicAlgebraicExpr> {
+    names.iter().map(|x| expr(x).into_inner()).collect()
+}
+
+#[test]
+pub fn monomials() {
+    let xy = vars(&["x", "y"]);
+    let (c, v) = coeff_var_monomial(expr("-2 * a * b * x * y^3"), &xy[..]).unwrap();
+    assert_eq!(c, expr("-2 * a * b").into_inner());
+    assert_eq!(v, expr("x * y^3").into_inner());
+
+    let (c, v) = coeff_var_monomial(expr("a^2"), &xy[..]).unwrap();
+    assert_eq!((c, v), (expr("a^2").into_inner(), expr("1").into_inner()));
+
+    let sin = vars(&["Sin[x]"]);
+    let (c, v) = coeff_var_monomial(expr("a * Sin[x]^2"), &sin[..]).unwrap();
+    assert_eq!(
+        (c, v),
+        (expr("a").into_inner(), expr("Sin[x]^2").into_inner())
+    );
+
+    for u in ["x^(-1) * y", "(x + 1) * y", "Sin[x] * y", "x^a"] {
+        assert!(coeff_var_monomial(expr(u), &xy[..]).is_err(), "{u}");
+    }
+}
+
+#[test]
+pub fn collect() {
+    let x = expr("x").into_inner();
+    assert_eq!(
+        collect_terms(expr("a * x + b * x"), &x).unwrap(),
+        expr("(a + b) * x").into_inner()
+    );
+
+    let xy = vars(&["x", "y"]);
+    assert_eq!(
+        collect_terms(expr("a * x * y + 2 * x + b * x * y + c * x + 3"), &xy[..]).unwrap(),
+        expr("3 + (2 + c) * x + (a + b) * x * y").into_inner()
+    );
+
+    // the coefficients cancel
+    assert_eq!(
+        collect_terms(expr("a * x - b * y + b * y"), &xy[..]).unwrap(),
+        expr("a * x").into_inner()
+    );
+
+    assert!(collect_terms(expr("x * Sin[x] + x"), &x).is_err());
+}
+
+#[test]
+pub fn gpe_queries() {
+    let xy = vars(&["x", "y"]);
+    let u = expr("a * x^2 * y + b * x * y^3 + c");
+    assert!(is_polynomial_gpe(&u, &xy[..]));
+    assert_eq!(degree_gpe(&u, &xy[..]).unwrap(), 4);
+    assert_eq!(degree_gpe(&expr("0"), &xy[..]).unwrap(), 0);
+    assert!(!is_polynomial_gpe(&expr("x / y"), &xy[..]));
+    assert!(!is_polynomial_gpe(&expr("x^(1/2) + 1"), &xy[..]));
+
+    let x = expr("x").into_inner();
+    assert_eq!(coefficient_gpe(&u, &x, 1).unwrap(), expr("b * y^3"));
+    assert_eq!(leading_coefficient_gpe(&u, &x).unwrap(), expr("a * y"));
+
+    let sin = expr("Sin[x]").into_inner();
+    let u = expr("a * Sin[x]^2 + b * Sin[x] + 2 * Sin[x]^2");
+    assert_eq!(degree_gpe(&u, &sin).unwrap(), 2);
+    assert_eq!(coefficient_gpe(&u, &sin, 2).unwrap(), expr("a + 2"));
+    assert_eq!(coefficient_gpe(&u, &sin, 0).unwrap(), expr("0"));
+    assert!(coefficient_gpe(&u, &x, 1).is_err());
+}
+
+#[test]
+pub fn to_polynomial() {
+    for u in ["x^3 - 2 * x + 1/2", "(x + 1)^3 * (2 * x - 1)", "5", "0"] {
+        let p = expr(u).to_polynomial("x").unwrap();
+        assert_eq!(
+            p.to_expr("x").unwrap().to_polynomial("x").unwrap(),
+            p,
+            "{u}"
+        );
+    }
+    for u in ["Sin[x]", "x^(-1)", "x * y", "x^(1/2)"] {
+        assert!(expr(u).to_polynomial("x").is_err(), "{u}");
+    }
+}
+
+#[test]
+pub fn factor() {
+    let u = expr("x^5 + 6 * x^4 + 10 * x^3 - 4 * x^2 - 24 * x - 16");
+    assert_eq!(u.factor("x").unwrap(), expr("(x^2 - 2) * (x + 2)^3"));
+
+    let u = expr("-750 * x^4 - 525 * x^3 + 90 * x^2 + 132 * x + 24");
+    let factored = u.factor("x").unwrap();
+    assert_eq!(
+        factored.to_polynomial("x").unwrap(),
+        u.to_polynomial("x").unwrap()
+    );
+    assert_eq!(factored, expr("-3 * (2 * x - 1) * (5 * x + 2)^3"));
+
+    assert!(expr("Sin[x]").factor("x").is_err());
+}
+
+#[test]
+pub fn apart() {
+    let u = expr("(x^4 + 1) / (x^3 - x^2)");
+    assert_eq!(u.apart("x").unwrap(), expr("x + 1 - 1 / x - 1 / x^2 + 2 / (x - 1)"));
+
+    // the factors are written with integer coefficients
+    let u = expr("1 / ((2 * x + 1) * (x^2 + 3))");
+    assert_eq!(
+        u.apart("x").unwrap(),
+        expr("4/13 / (2 * x + 1) + (1/13 - 2/13 * x) / (x^2 + 3)")
+    );
+
+    assert_eq!(expr("x^2 + 1").apart("x").unwrap(), expr("x^2 + 1"));
+    assert!(expr("1 / Sin[x]").apart("x").is_err());
+}
+
+#[test]
+pub fn groebner() {
+    let polys = ["x^3 - 2 * x * y", "x^2 * y - 2 * y^2 + x"].map(expr);
+    let vars = ["x", "y"];
+    let basis = groebner_basis(&polys, &vars, MonomialOrder::GrLex).unwrap();
+    assert_eq!(basis, ["x^2", "x * y", "y^2 - x / 2"].map(expr));
+
+    assert!(ideal_contains(
+        &expr("x^2 * y + x * y^2"),
+        &polys,
+        &vars,
+        MonomialOrder::GrLex
+    )
+    .unwrap());
+    assert!(!ideal_contains(&expr("x + y"), &polys, &vars, MonomialOrder::GrLex).unwrap());
+    assert_eq!(
+        normal_form(
+            &expr("y^3 + 3 * y^2 + x"),
+            &polys,
+            &vars,
+            MonomialOrder::GrLex
+        )
+        .unwrap(),
+        expr("5/2 * x")
+    );
+
+    // eliminating t from the parametrization of the twisted cubic
+    let polys = ["x - t", "y - t^2", "z - t^3"].map(expr);
+    let basis = groebner_basis(&polys, &["t", "x", "y", "z"], MonomialOrder::Lex).unwrap();
+    assert_eq!(
+        basis,
+        ["t - x", "x^2 - y", "x * y - z", "x * z - y^2", "y^3 - z^2"].map(expr)
+    );
+
+    assert!(groebner_basis(&[expr("Sin[x]")], &vars, MonomialOrder::Lex).is_err());
+    assert!(groebner_basis(&[expr("a * x")], &vars, MonomialOrder::Lex).is_err());
+}
@@ -0,0 +1,83 @@
+use num::BigRational;
+
+use crate::print::print_series_to_string;
+use crate::series::{series, Series, SeriesError};
+use crate::simplify::SimpleExpr;
+
+fn expr(s: &str) -> SimpleExpr {
+    crate::parse(s).unwrap().simplify().unwrap()
+}
+
+/// Expands `u` at `a` up to `O((x - a)^n)` and compares the known terms with `expected`.
+fn assert_series(u: &str, a: i128, n: i64, expected: &str) {
+    let s = series(&expr(u), "x", a.into(), n).unwrap();
+    assert_eq!(s.precision(), BigRational::from_integer(n.into()), "{u}");
+    assert_eq!(s.to_expr().unwrap(), expr(expected), "{u}");
+}
+
+#[test]
+pub fn elementary_functions() {
+    assert_series("Exp[x]", 0, 5, "1 + x + x^2 / 2 + x^3 / 6 + x^4 / 24");
+    assert_series("Log[x]", 1, 4, "(x - 1) - (x - 1)^2 / 2 + (x - 1)^3 / 3");
+    assert_series(
+        "Tan[x]",
+        0,
+        8,
+        "x + x^3 / 3 + 2 * x^5 / 15 + 17 * x^7 / 315",
+    );
+    assert_series("ArcTan[x]", 0, 6, "x - x^3 / 3 + x^5 / 5");
+    assert_series("Exp[Sin[x]]", 0, 5, "1 + x + x^2 / 2 - x^4 / 8");
+}
+
+#[test]
+pub fn cancellation() {
+    assert_series("Sin[x] / x", 0, 6, "1 - x^2 / 6 + x^4 / 120");
+    assert_series("(1 - Cos[x]) / x^2", 0, 4, "1 / 2 - x^2 / 24");
+    assert_series("1 / (Sin[x] - x)", 0, 0, "-6 / x^3 - 3 / (10 * x)");
+}
+
+#[test]
+pub fn laurent_and_puiseux() {
+    assert_series("1 / Sin[x]", 0, 3, "1 / x + x / 6");
+    assert_series(
+        "1 / (x^2 - 1)",
+        1,
+        2,
+        "1 / (2 * (x - 1)) - 1 / 4 + (x - 1) / 8",
+    );
+    assert_series("(1 + x)^(1/2)", 0, 4, "1 + x / 2 - x^2 / 8 + x^3 / 16");
+    assert_series("(x + x^3)^(1/3)", 0, 3, "x^(1/3) + x^(7/3) / 3");
+}
+
+#[test]
+pub fn not_representable() {
+    for u in ["Log[x]", "x^x", "Exp[1 / x]", "Exp[x] * a"] {
+        assert!(matches!(
+            series(&expr(u), "x", 0.into(), 3),
+            Err(SeriesError::NotRepresentable)
+        ));
+    }
+}
+
+#[test]
+pub fn arithmetic() {
+    let x = Series::variable("x", 0.into(), 6);
+    let sin = x.sin().unwrap();
+    let cos = x.cos().unwrap();
+    let one = sin.clone() * sin.clone() + cos.clone() * cos;
+    assert_eq!(one.to_expr().unwrap(), expr("1"));
+
+    // the series of Log[y] at 1, composed with 1 + Sin[x]
+    let log = Series::variable("y", 1.into(), 6).log().unwrap();
+    let inner = Series::constant(1.into(), "x", 0.into(), 6) + sin;
+    assert_eq!(
+        log.compose(&inner).unwrap(),
+        series(&expr("Log[1 + Sin[x]]"), "x", 0.into(), 6).unwrap()
+    );
+}
+
+#[test]
+pub fn printing() {
+    let s = series(&expr("1 / (1 - x)"), "x", 0.into(), 3).unwrap();
+    assert_eq!(print_series_to_string(&s), "1+x+x^(2)+O(x^(3))");
+}
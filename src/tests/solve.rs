@@ -0,0 +1,127 @@
+use crate::polynomials::substitute;
+use crate::simplify::SimpleExpr;
+use crate::solve::{solve_system, solve_system_numeric, SolveError};
+use crate::BasicAlgebraicExpr;
+
+fn expr(s: &str) -> SimpleExpr {
+    crate::parse(s).unwrap().simplify().unwrap()
+}
+
+fn solve(equations: &[&str], vars: &[&str]) -> Result<Vec<Vec<SimpleExpr>>, SolveError> {
+    solve_system(&equations.iter().map(|u| expr(u)).collect::<Vec<_>>(), vars)
+}
+
+#[test]
+pub fn rational_solutions() {
+    assert_eq!(
+        solve(&["x^2 + y^2 - 25", "x - y - 1"], &["x", "y"]).unwrap(),
+        [["-3", "-4"], ["4", "3"]].map(|s| s.map(expr).to_vec())
+    );
+    assert_eq!(
+        solve(
+            &[
+                "x + y + z - 6",
+                "x * y * z - 6",
+                "x * y + y * z + x * z - 11"
+            ],
+            &["x", "y", "z"]
+        )
+        .unwrap()
+        .len(),
+        6
+    );
+    // a multiple solution is only listed once
+    assert_eq!(
+        solve(&["x^2", "x * y", "y^2"], &["x", "y"]).unwrap(),
+        [vec![expr("0"), expr("0")]]
+    );
+    assert_eq!(solve(&["0"], &[]).unwrap(), [Vec::<SimpleExpr>::new()]);
+}
+
+#[test]
+pub fn radical_solutions() {
+    assert_eq!(
+        solve(&["x^2 + y^2 - 1", "x - y"], &["x", "y"]).unwrap(),
+        [["-2^(1/2) / 2"; 2], ["2^(1/2) / 2"; 2]].map(|s| s.map(expr).to_vec())
+    );
+    let solutions = solve(&["x^2 * y - 1", "x * y^2 - 1", "x^3 - 1"], &["x", "y"]).unwrap();
+    assert_eq!(solutions.len(), 3);
+    assert_eq!(solutions[0], [expr("1"), expr("1")]);
+}
+
+#[test]
+pub fn root_of_solutions() {
+    // the roots are polynomials in the separating variable `#t`, which cannot be parsed
+    let in_separator = |u: &str| {
+        let t = BasicAlgebraicExpr::Symbol("t".into());
+        let separator = BasicAlgebraicExpr::Symbol("#t".into());
+        substitute(&crate::parse(u).unwrap(), &t, &separator)
+            .simplify()
+            .unwrap()
+    };
+    let root = |j| format!("RootOf[t^3 - 2 * t^2 + t - 1, {j}]");
+    assert_eq!(
+        solve(&["x^3 - x - 1", "y - x^2"], &["x", "y"]).unwrap(),
+        (1..=3)
+            .map(|j| {
+                let x = in_separator(&format!("{0}^2 - {0}", root(j)));
+                vec![x, in_separator(&root(j))]
+            })
+            .collect::<Vec<_>>()
+    );
+    // the unknown `t` is not confused with the separating variable
+    let root = |j| format!("RootOf[t^3 - t - 1, {j}]");
+    assert_eq!(
+        solve(&["t^3 - t - 1"], &["t"]).unwrap(),
+        (1..=3).map(|j| vec![in_separator(&root(j))]).collect::<Vec<_>>()
+    );
+
+    // the irreducible quadratic factors of x^4 - 5x^2 + 6 = (x^2 - 3)(x^2 - 2) give square roots
+    assert_eq!(
+        solve(&["x^4 - 5 * x^2 + 6"], &["x"]).unwrap(),
+        ["-3^(1/2)", "3^(1/2)", "-2^(1/2)", "2^(1/2)"].map(|x| vec![expr(x)])
+    );
+    // and the cubic factor of (x^2 + 1)(x^3 - x - 1) gives roots of itself
+    let root = |j| format!("RootOf[t^3 - t - 1, {j}]");
+    let mut expected = vec![vec![expr("-1 * (-1)^(1/2)")], vec![expr("(-1)^(1/2)")]];
+    expected.extend((1..=3).map(|j| vec![in_separator(&root(j))]));
+    assert_eq!(solve(&["(x^2 + 1) * (x^3 - x - 1)"], &["x"]).unwrap(), expected);
+}
+
+#[test]
+pub fn numeric_solutions() {
+    let equations = ["x^3 - x - 1", "y - x^2"].map(expr);
+    let solutions = solve_system_numeric(&equations, &["x", "y"]).unwrap();
+    assert_eq!(solutions.len(), 3);
+    for s in &solutions {
+        assert!((s[0].powu(3) - s[0] - 1.0).norm() < 1e-12);
+        assert!((s[1] - s[0] * s[0]).norm() < 1e-12);
+    }
+    assert!((solutions[0][0].re - 1.324717957244746).abs() < 1e-12);
+    assert_eq!(solutions[0][0].im, 0.0);
+    assert!(solutions[1][0].im != 0.0 && solutions[1][0] == solutions[2][0].conj());
+
+    let equations = ["x^2 + y^2 + 1", "x - y"].map(expr);
+    let solutions = solve_system_numeric(&equations, &["x", "y"]).unwrap();
+    assert_eq!(solutions.len(), 2);
+    assert!((solutions[0][0].im + 0.5f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+pub fn degenerate_systems() {
+    assert_eq!(
+        solve(&["x + y", "x + y - 1"], &["x", "y"]).unwrap(),
+        Vec::<Vec<SimpleExpr>>::new()
+    );
+    assert_eq!(solve(&["1"], &[]).unwrap(), Vec::<Vec<SimpleExpr>>::new());
+    assert_eq!(
+        solve(&["x * y"], &["x", "y"]),
+        Err(SolveError::InfinitelyMany)
+    );
+    assert_eq!(
+        solve(&["x - 1"], &["x", "y"]),
+        Err(SolveError::InfinitelyMany)
+    );
+    assert_eq!(solve(&["Sin[x]"], &["x"]), Err(SolveError::NotPolynomial));
+    assert_eq!(solve(&["x - a"], &["x"]), Err(SolveError::NotPolynomial));
+}
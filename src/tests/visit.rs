@@ -0,0 +1,138 @@
+use std::ops::ControlFlow;
+
+use crate::polynomials::substitute;
+use crate::visit::{Fold, Position, Visitor, Walk};
+use crate::BasicAlgebraicExpr;
+
+fn expr(s: &str) -> BasicAlgebraicExpr {
+    crate::parse(s).unwrap()
+}
+
+#[test]
+pub fn children() {
+    let x = expr("a^b");
+    assert_eq!(x.children().collect::<Vec<_>>(), [&expr("a"), &expr("b")]);
+    assert_eq!(
+        x.children().rev().collect::<Vec<_>>(),
+        [&expr("b"), &expr("a")]
+    );
+    assert_eq!(x.children().len(), 2);
+    assert_eq!(expr("Sin[x, y, z]").children().len(), 3);
+    assert_eq!(expr("n!").children().collect::<Vec<_>>(), [&expr("n")]);
+    assert_eq!(expr("x").children().next(), None);
+}
+
+#[test]
+pub fn traversal_orders() {
+    let x = expr("a * Sin[b + c]^2");
+    let pre_order = x.pre_order().map(|(_, x)| x.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        pre_order,
+        [
+            "a * Sin[b + c]^2",
+            "a",
+            "Sin[b + c]^2",
+            "Sin[b + c]",
+            "b + c",
+            "b",
+            "c",
+            "2"
+        ]
+        .map(expr)
+    );
+    let post_order = x.post_order().map(|(_, x)| x.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        post_order,
+        [
+            "a",
+            "b",
+            "c",
+            "b + c",
+            "Sin[b + c]",
+            "2",
+            "Sin[b + c]^2",
+            "a * Sin[b + c]^2"
+        ]
+        .map(expr)
+    );
+
+    let depths = x.pre_order().map(|(p, _)| p.depth).collect::<Vec<_>>();
+    assert_eq!(depths, [0, 1, 1, 2, 3, 4, 4, 2]);
+    let positions = x.post_order().map(|(p, _)| p).collect::<Vec<_>>();
+    assert_eq!(positions[2], Position { depth: 4, index: 1 });
+    assert_eq!(positions[5], Position { depth: 2, index: 1 });
+
+    // the paths lead back to the subexpressions
+    let mut pre_order = x.pre_order();
+    while let Some((_, y)) = pre_order.next() {
+        assert_eq!(x.at(pre_order.path()), Some(y));
+    }
+    let mut post_order = x.post_order();
+    while let Some((_, y)) = post_order.next() {
+        assert_eq!(x.at(post_order.path()), Some(y));
+    }
+    assert_eq!(x.at(&[1, 0, 0, 1]), Some(&expr("c")));
+    assert_eq!(x.at(&[2]), None);
+}
+
+#[test]
+pub fn visitors() {
+    // counts the symbols outside of function arguments, and stops at the first factorial
+    #[derive(Default)]
+    struct Symbols(usize);
+
+    impl Visitor for Symbols {
+        type Break = BasicAlgebraicExpr;
+
+        fn enter(&mut self, x: &BasicAlgebraicExpr) -> ControlFlow<Self::Break, Walk> {
+            match x {
+                BasicAlgebraicExpr::Symbol(_) => self.0 += 1,
+                BasicAlgebraicExpr::Function(..) => return ControlFlow::Continue(Walk::Skip),
+                BasicAlgebraicExpr::Factorial(_) => return ControlFlow::Break(x.clone()),
+                _ => {}
+            }
+            ControlFlow::Continue(Walk::Children)
+        }
+    }
+
+    let mut symbols = Symbols::default();
+    assert!(expr("x * Sin[y] + z^x").visit(&mut symbols).is_continue());
+    assert_eq!(symbols.0, 3);
+    let mut symbols = Symbols::default();
+    let result = expr("x + y * n! + z").visit(&mut symbols);
+    assert_eq!(result, ControlFlow::Break(expr("n!")));
+    assert_eq!(symbols.0, 2);
+
+    assert!(expr("x + Cos[y^2]").any(|x| *x == expr("y")));
+    assert!(!expr("x + Cos[y^2]").any(|x| *x == expr("z")));
+}
+
+#[test]
+pub fn folds() {
+    // replaces every power by its base
+    struct Bases;
+
+    impl Fold for Bases {
+        fn fold_node(&mut self, x: BasicAlgebraicExpr) -> BasicAlgebraicExpr {
+            match x {
+                BasicAlgebraicExpr::Pow(x) => x.0,
+                x => x,
+            }
+        }
+    }
+
+    assert_eq!(
+        Bases.fold(expr("(x^2 + y)^3 * Sin[z^4]")),
+        expr("(x + y) * Sin[z]")
+    );
+    assert_eq!(
+        substitute(&expr("x^2 + Sin[x * y]"), &expr("x"), &expr("t + 1")),
+        BasicAlgebraicExpr::Sum(vec![
+            expr("(t + 1)^2"),
+            BasicAlgebraicExpr::Function(
+                "Sin".into(),
+                vec![BasicAlgebraicExpr::Product(vec![expr("t + 1"), expr("y")])]
+            ),
+        ])
+    );
+}
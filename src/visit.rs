@@ -0,0 +1,324 @@
+//! Traversals of expression trees.
+//!
+//! [`BasicAlgebraicExpr::children`] iterates over the direct subexpressions of an expression
+//! without allocating, and everything else is built on it: [`Visitor`]s walk an expression
+//! recursively and can stop early or skip subtrees, [`PreOrder`] and [`PostOrder`] iterate over
+//! all the subexpressions together with their positions, and [`Fold`] rebuilds an expression
+//! bottom-up with [`BasicAlgebraicExpr::map_children`].
+
+use std::iter::FusedIterator;
+use std::ops::ControlFlow;
+use std::slice;
+
+use crate::BasicAlgebraicExpr;
+
+/// An iterator over the direct subexpressions of an expression, see
+/// [`BasicAlgebraicExpr::children`].
+#[derive(Clone, Debug)]
+pub struct Children<'a> {
+    first: Option<&'a BasicAlgebraicExpr>,
+    rest: slice::Iter<'a, BasicAlgebraicExpr>,
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a BasicAlgebraicExpr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.take().or_else(|| self.rest.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.first.is_some()) + self.rest.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Children<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rest.next_back().or_else(|| self.first.take())
+    }
+}
+
+impl ExactSizeIterator for Children<'_> {}
+
+impl FusedIterator for Children<'_> {}
+
+/// Whether a [`Visitor`] continues into the children of an expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Walk {
+    Children,
+    Skip,
+}
+
+/// Walks an expression with [`BasicAlgebraicExpr::visit`].
+pub trait Visitor {
+    /// The value the walk stops with.
+    type Break;
+
+    /// Called on an expression before its children, which are skipped if this returns
+    /// [`Walk::Skip`].
+    fn enter(&mut self, _x: &BasicAlgebraicExpr) -> ControlFlow<Self::Break, Walk> {
+        ControlFlow::Continue(Walk::Children)
+    }
+
+    /// Called on an expression after its children.
+    fn leave(&mut self, _x: &BasicAlgebraicExpr) -> ControlFlow<Self::Break> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Rebuilds an expression bottom-up with [`Fold::fold`].
+pub trait Fold {
+    /// Folds `x`. By default this folds the children of `x` and then `x` itself with
+    /// [`Fold::fold_node`]. Overriding it allows to replace whole subtrees, without visiting their
+    /// children.
+    fn fold(&mut self, x: BasicAlgebraicExpr) -> BasicAlgebraicExpr {
+        let x = x.map_children(|child| self.fold(child));
+        self.fold_node(x)
+    }
+
+    /// Folds an expression whose children are already folded.
+    fn fold_node(&mut self, x: BasicAlgebraicExpr) -> BasicAlgebraicExpr {
+        x
+    }
+}
+
+/// The position of a subexpression yielded by [`PreOrder`] or [`PostOrder`]: its depth below the
+/// root, and its index among the children of its parent (zero for the root). The full path from
+/// the root is given by [`PreOrder::path`] and [`PostOrder::path`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub depth: usize,
+    pub index: usize,
+}
+
+fn position(path: &[usize]) -> Position {
+    Position {
+        depth: path.len(),
+        index: path.last().copied().unwrap_or(0),
+    }
+}
+
+/// An iterator over an expression and its subexpressions, each before its children. See
+/// [`BasicAlgebraicExpr::pre_order`].
+#[derive(Clone, Debug)]
+pub struct PreOrder<'a> {
+    root: Option<&'a BasicAlgebraicExpr>,
+    // the remaining children of the ancestors of the next expression, with the index of the next
+    // one
+    stack: Vec<(Children<'a>, usize)>,
+    path: Vec<usize>,
+}
+
+impl PreOrder<'_> {
+    /// The indices of the children to follow from the root to the last expression yielded.
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+}
+
+impl<'a> Iterator for PreOrder<'a> {
+    type Item = (Position, &'a BasicAlgebraicExpr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = match self.root.take() {
+            Some(root) => root,
+            None => loop {
+                let depth = self.stack.len();
+                let (children, next) = self.stack.last_mut()?;
+                if let Some(x) = children.next() {
+                    self.path.truncate(depth - 1);
+                    self.path.push(*next);
+                    *next += 1;
+                    break x;
+                }
+                self.stack.pop();
+            },
+        };
+        let children = x.children();
+        if children.len() > 0 {
+            self.stack.push((children, 0));
+        }
+        Some((position(&self.path), x))
+    }
+}
+
+impl FusedIterator for PreOrder<'_> {}
+
+/// An iterator over an expression and its subexpressions, each after its children. See
+/// [`BasicAlgebraicExpr::post_order`].
+#[derive(Clone, Debug)]
+pub struct PostOrder<'a> {
+    // the ancestors of the next expression, with their remaining children and the index of the
+    // next one
+    stack: Vec<(&'a BasicAlgebraicExpr, Children<'a>, usize)>,
+    path: Vec<usize>,
+}
+
+impl PostOrder<'_> {
+    /// The indices of the children to follow from the root to the last expression yielded.
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+}
+
+impl<'a> Iterator for PostOrder<'a> {
+    type Item = (Position, &'a BasicAlgebraicExpr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.stack.len();
+            let (_, children, next) = self.stack.last_mut()?;
+            if let Some(x) = children.next() {
+                self.path.truncate(depth - 1);
+                self.path.push(*next);
+                *next += 1;
+                self.stack.push((x, x.children(), 0));
+                continue;
+            }
+            let (x, _, _) = self.stack.pop().unwrap();
+            self.path.truncate(self.stack.len());
+            return Some((position(&self.path), x));
+        }
+    }
+}
+
+impl FusedIterator for PostOrder<'_> {}
+
+/// Replaces `x` with `f(x)`, without cloning it.
+fn replace_with(
+    x: &mut BasicAlgebraicExpr,
+    f: &mut impl FnMut(BasicAlgebraicExpr) -> BasicAlgebraicExpr,
+) {
+    let placeholder = BasicAlgebraicExpr::Symbol(String::new());
+    let old = std::mem::replace(x, placeholder);
+    *x = f(old);
+}
+
+impl BasicAlgebraicExpr {
+    /// The direct subexpressions of `self`: the factors of a product, the terms of a sum, the
+    /// base and exponent of a power, the argument of a factorial or the arguments of a function.
+    pub fn children(&self) -> Children<'_> {
+        let (first, rest): (_, &[_]) = match self {
+            Self::Numeric(_) | Self::Symbol(_) => (None, &[]),
+            Self::Product(v) | Self::Sum(v) | Self::Function(_, v) => (None, v),
+            Self::Pow(x) => (Some(&x.0), slice::from_ref(&x.1)),
+            Self::Factorial(x) => (None, slice::from_ref(&**x)),
+        };
+        Children {
+            first,
+            rest: rest.iter(),
+        }
+    }
+
+    /// Replaces the direct subexpressions `c` of `self` (see [`BasicAlgebraicExpr::children`])
+    /// with `f(c)`, reusing the allocations of `self`. The result is not simplified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff::{parse, BasicAlgebraicExpr};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (x, two) = (parse("Sin[x] + y")?, parse("2")?);
+    /// let doubled = x.map_children(|c| BasicAlgebraicExpr::Product(vec![two.clone(), c]));
+    /// assert_eq!(doubled, parse("2 * Sin[x] + 2 * y")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn map_children(self, mut f: impl FnMut(Self) -> Self) -> Self {
+        match self {
+            Self::Numeric(_) | Self::Symbol(_) => self,
+            Self::Product(mut v) => {
+                v.iter_mut().for_each(|x| replace_with(x, &mut f));
+                Self::Product(v)
+            }
+            Self::Sum(mut v) => {
+                v.iter_mut().for_each(|x| replace_with(x, &mut f));
+                Self::Sum(v)
+            }
+            Self::Function(name, mut v) => {
+                v.iter_mut().for_each(|x| replace_with(x, &mut f));
+                Self::Function(name, v)
+            }
+            Self::Pow(mut x) => {
+                replace_with(&mut x.0, &mut f);
+                replace_with(&mut x.1, &mut f);
+                Self::Pow(x)
+            }
+            Self::Factorial(mut x) => {
+                replace_with(&mut x, &mut f);
+                Self::Factorial(x)
+            }
+        }
+    }
+
+    /// Walks `self` with `visitor`, calling [`Visitor::enter`] and [`Visitor::leave`] on every
+    /// subexpression it reaches. Stops as soon as the visitor breaks.
+    pub fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        if visitor.enter(self)? == Walk::Children {
+            for child in self.children() {
+                child.visit(visitor)?;
+            }
+        }
+        visitor.leave(self)
+    }
+
+    /// Whether `f` holds for `self` or one of its subexpressions, which are tried in pre-order.
+    pub fn any(&self, f: impl FnMut(&Self) -> bool) -> bool {
+        struct Any<F>(F);
+
+        impl<F: FnMut(&BasicAlgebraicExpr) -> bool> Visitor for Any<F> {
+            type Break = ();
+
+            fn enter(&mut self, x: &BasicAlgebraicExpr) -> ControlFlow<(), Walk> {
+                if (self.0)(x) {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(Walk::Children)
+                }
+            }
+        }
+
+        self.visit(&mut Any(f)).is_break()
+    }
+
+    /// Iterates over `self` and its subexpressions, each before its children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mathstuff::parse;
+    /// # use mathstuff::visit::Position;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let x = parse("a + b^2")?;
+    /// let mut pre_order = x.pre_order();
+    /// assert_eq!(pre_order.next(), Some((Position { depth: 0, index: 0 }, &x)));
+    /// assert_eq!(pre_order.next().unwrap().1, &parse("a")?);
+    /// assert_eq!(pre_order.next().unwrap().1, &parse("b^2")?);
+    /// assert_eq!(pre_order.next().unwrap(), (Position { depth: 2, index: 0 }, &parse("b")?));
+    /// assert_eq!(pre_order.path(), [1, 0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pre_order(&self) -> PreOrder<'_> {
+        PreOrder {
+            root: Some(self),
+            stack: vec![],
+            path: vec![],
+        }
+    }
+
+    /// Iterates over `self` and its subexpressions, each after its children.
+    pub fn post_order(&self) -> PostOrder<'_> {
+        PostOrder {
+            stack: vec![(self, self.children(), 0)],
+            path: vec![],
+        }
+    }
+
+    /// The subexpression of `self` at `path`, the indices of the children to follow from `self`.
+    pub fn at(&self, path: &[usize]) -> Option<&Self> {
+        path.iter().try_fold(self, |x, &i| x.children().nth(i))
+    }
+}